@@ -3,8 +3,16 @@ use crate::state_management::visual_snippet_component_manager::{
 };
 use crate::utils::sequential_id_generator::Uuid;
 use crate::{
+    core_services::{
+        project_service::UnresolvedProjectReference,
+        secret_store_service::SECRET_MASK,
+        variable_profile_service::{contains_variable_reference, substitute_variables},
+    },
     state_management::{
-        external_snippet_manager::{ExternalSnippet, ExternalSnippetParameterType},
+        external_snippet_manager::{
+            ExternalSnippet, ExternalSnippetMetadata, ExternalSnippetParameterOptions,
+            ExternalSnippetParameterType, PackagePath,
+        },
         visual_snippet_component_manager::{FrontParameterContent, VisualSnippetComponentManager},
     },
     utils::sequential_id_generator::SequentialIdGenerator,
@@ -15,13 +23,20 @@ use petgraph::{
     data::{Build, DataMapMut},
     graph::NodeIndex,
 };
-use pyo3::{IntoPy, Py, PyAny};
+use pyo3::{
+    types::{PyDict, PyDictMethods, PyList, PyListMethods},
+    IntoPy, Py, PyAny,
+};
 use serde::Serialize;
-use std::collections::{HashMap, HashSet};
 use std::thread::yield_now;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::Path,
+};
 use strum_macros::Display;
 
 /// the manager of the snippets, and their links
+#[derive(Clone)]
 pub struct SnippetManager {
     //list of uuid of snippets to index in edge adj list
     //edge adj list for snippets
@@ -47,24 +62,35 @@ pub struct SnippetManager {
 }
 
 /// the actual snippet itself
+#[derive(Clone)]
 pub struct SnippetComponent {
     uuid: Uuid,
     graph_uuid: petgraph::graph::NodeIndex,
     name: String,
     external_snippet_uuid: Uuid,
+    // package path of the snippet this is a placeholder for, if the snippet could not be found
+    missing_package_path: Option<PackagePath>,
     pipeline_connectors: Vec<PipelineConnectorComponent>,
     parameters: Vec<SnippetParameterComponent>,
     x_position: f64,
     y_position: f64,
+    // description, tags and the like of the external snippet, for the canvas
+    metadata: ExternalSnippetMetadata,
 }
 
+#[derive(Clone)]
 pub struct PipelineConnectorComponent {
     uuid: Uuid,
     external_pipeline_connector_uuid: Uuid,
     name: String,
     input: bool,
+    // placeholder for a connector that no longer exists on the snippet
+    missing: bool,
+    // help text of the connector
+    description: Option<String>,
 }
 
+#[derive(Clone)]
 pub struct PipelineComponent {
     uuid: Uuid,
     graph_uuid: petgraph::graph::EdgeIndex,
@@ -78,11 +104,21 @@ pub struct SnippetParameterComponent {
     name: String,
     content: SnippetParameterBaseStorage,
     p_type: ExternalSnippetParameterType,
+    options: ExternalSnippetParameterOptions,
+    // placeholder for a parameter that no longer exists on the snippet
+    missing: bool,
 }
 
-#[derive(Clone)]
+// enum parameters are stored as the string of their choice, and path parameters as their path
+// values referencing project variables are stored as they were given until the variables are substituted
+#[derive(Clone, PartialEq, Debug)]
 pub enum SnippetParameterBaseStorage {
     String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Json(serde_json::Value),
+    Template(String),
 }
 
 impl IntoPy<Py<PyAny>> for SnippetParameterBaseStorage {
@@ -91,16 +127,88 @@ impl IntoPy<Py<PyAny>> for SnippetParameterBaseStorage {
             SnippetParameterBaseStorage::String(val) => {
                 return val.into_py(py);
             }
+            SnippetParameterBaseStorage::Integer(val) => {
+                return val.into_py(py);
+            }
+            SnippetParameterBaseStorage::Float(val) => {
+                return val.into_py(py);
+            }
+            SnippetParameterBaseStorage::Boolean(val) => {
+                return val.into_py(py);
+            }
+            SnippetParameterBaseStorage::Json(val) => {
+                return json_value_into_py(py, &val);
+            }
+            SnippetParameterBaseStorage::Template(val) => {
+                return val.into_py(py);
+            }
+        }
+    }
+}
+
+/// Convert the json value into the python value json.loads would give
+fn json_value_into_py(py: pyo3::Python<'_>, value: &serde_json::Value) -> Py<PyAny> {
+    match value {
+        serde_json::Value::Null => return py.None(),
+        serde_json::Value::Bool(val) => return val.into_py(py),
+        serde_json::Value::Number(val) => {
+            // keep integers as integers
+            if let Some(integer) = val.as_i64() {
+                return integer.into_py(py);
+            }
+
+            return val.as_f64().unwrap_or(f64::NAN).into_py(py);
+        }
+        serde_json::Value::String(val) => return val.into_py(py),
+        serde_json::Value::Array(values) => {
+            let list = PyList::empty_bound(py);
+
+            for val in values {
+                // appending to a new list cannot fail
+                list.append(json_value_into_py(py, val)).unwrap();
+            }
+
+            return list.into_py(py);
+        }
+        serde_json::Value::Object(values) => {
+            let dict = PyDict::new_bound(py);
+
+            for (key, val) in values {
+                // string keys are always hashable
+                dict.set_item(key, json_value_into_py(py, val)).unwrap();
+            }
+
+            return dict.into_py(py);
         }
     }
 }
 
+// the string of the value is what is saved in the project, and what update_value parses back
 impl std::fmt::Display for SnippetParameterBaseStorage {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             SnippetParameterBaseStorage::String(val) => {
                 write!(f, "{}", val)
             }
+            SnippetParameterBaseStorage::Integer(val) => {
+                write!(f, "{}", val)
+            }
+            SnippetParameterBaseStorage::Float(val) => {
+                write!(f, "{}", val)
+            }
+            SnippetParameterBaseStorage::Boolean(val) => {
+                write!(f, "{}", val)
+            }
+            SnippetParameterBaseStorage::Json(val) => {
+                write!(
+                    f,
+                    "{}",
+                    serde_json::to_string_pretty(val).unwrap_or_default()
+                )
+            }
+            SnippetParameterBaseStorage::Template(val) => {
+                write!(f, "{}", val)
+            }
         }
     }
 }
@@ -134,9 +242,8 @@ impl SnippetManager {
         let parameters =
             external_snippet.create_parameter_components_for_parameters(sequential_id_generator);
 
-        //call handler method, return value
-        //return uuid of snippet
-        return self.new_snippet_handler(
+        //call handler method
+        let snippet_uuid = self.new_snippet_handler(
             sequential_id_generator,
             pipeline_connectors,
             parameters,
@@ -145,6 +252,14 @@ impl SnippetManager {
             x_position,
             y_position,
         );
+
+        // metadata is only shown, so it is kept with the snippet for the canvas
+        if let Some(snippet) = self.snippets.get_mut(&snippet_uuid) {
+            snippet.metadata = external_snippet.get_metadata().to_owned();
+        }
+
+        //return uuid of snippet
+        return snippet_uuid;
     }
 
     fn new_snippet_handler(
@@ -202,6 +317,300 @@ impl SnippetManager {
         return snippet_uuid;
     }
 
+    /// restore a previously deleted snippet, keeping its uuid and the uuids of its
+    /// pipeline connectors and parameters
+    ///
+    /// # Arguments
+    /// * 'snippet' - copy of the snippet before it was deleted
+    pub fn restore_snippet(&mut self, snippet: SnippetComponent) -> Result<Uuid, &'static str> {
+        let mut snippet = snippet;
+        let snippet_uuid = snippet.uuid;
+
+        if self.snippets.contains_key(&snippet_uuid) {
+            return Err("snippet with snippet uuid already exists in snippet manager");
+        }
+
+        //add snippet to graph
+        snippet.graph_uuid = self.snippet_graph.add_node(());
+
+        // add to mapping
+        self.snippet_to_node_index
+            .insert(snippet_uuid, snippet.graph_uuid);
+
+        //add pipeline connector uuid to snippet mapping
+        for pipeline_connector in snippet.pipeline_connectors.iter() {
+            self.pipeline_connector_to_snippet
+                .insert(pipeline_connector.get_uuid(), snippet_uuid);
+        }
+
+        //add parameters to parameter to snippet mapping
+        for parameter in snippet.parameters.iter() {
+            self.parameter_to_snippet
+                .insert(parameter.uuid, snippet_uuid);
+        }
+
+        self.snippets.insert(snippet_uuid, snippet);
+
+        return Ok(snippet_uuid);
+    }
+
+    /// create a placeholder snippet for a snippet whose package path could not be found
+    /// the placeholder starts without connectors and parameters, these are added as
+    /// they are referenced
+    pub fn new_missing_snippet(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        package_path: PackagePath,
+        x_position: f64,
+        y_position: f64,
+    ) -> Uuid {
+        // name the placeholder after the last part of the package path
+        let name = match package_path.to_owned().into_iter().last() {
+            Some(name) => name,
+            None => package_path.to_string(),
+        };
+
+        let snippet_uuid = self.new_snippet_handler(
+            sequential_id_generator,
+            Vec::new(),
+            Vec::new(),
+            0,
+            name,
+            x_position,
+            y_position,
+        );
+
+        // can unwrap safely, as the snippet was just created
+        self.snippets
+            .get_mut(&snippet_uuid)
+            .unwrap()
+            .missing_package_path = Some(package_path);
+
+        return snippet_uuid;
+    }
+
+    /// add a placeholder pipeline connector to a snippet, for a connector that no longer exists
+    ///
+    /// # Arguments
+    /// * 'snippet_uuid' - uuid of the snippet to add the connector to
+    /// * 'name' - name of the missing connector
+    /// * 'input' - if the missing connector is an input
+    pub fn add_missing_pipeline_connector(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        snippet_uuid: Uuid,
+        name: &str,
+        input: bool,
+    ) -> Result<Uuid, &'static str> {
+        let snippet = match self.snippets.get_mut(&snippet_uuid) {
+            Some(snippet) => snippet,
+            None => {
+                return Err(
+                    "snippet component with snippet uuid does not exist in snippet manager",
+                );
+            }
+        };
+
+        let mut pipeline_connector =
+            PipelineConnectorComponent::new(sequential_id_generator, 0, name, input);
+        pipeline_connector.missing = true;
+
+        let pipeline_connector_uuid = pipeline_connector.uuid;
+
+        snippet.pipeline_connectors.push(pipeline_connector);
+
+        self.pipeline_connector_to_snippet
+            .insert(pipeline_connector_uuid, snippet_uuid);
+
+        return Ok(pipeline_connector_uuid);
+    }
+
+    /// add a placeholder parameter to a snippet, for a parameter that no longer exists
+    /// keeping its value
+    ///
+    /// # Arguments
+    /// * 'snippet_uuid' - uuid of the snippet to add the parameter to
+    /// * 'name' - name of the missing parameter
+    /// * 'value' - value of the missing parameter in string format
+    pub fn add_missing_parameter(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        snippet_uuid: Uuid,
+        name: String,
+        value: String,
+    ) -> Result<Uuid, &'static str> {
+        let snippet = match self.snippets.get_mut(&snippet_uuid) {
+            Some(snippet) => snippet,
+            None => {
+                return Err(
+                    "snippet component with snippet uuid does not exist in snippet manager",
+                );
+            }
+        };
+
+        let mut parameter = SnippetParameterComponent::new(
+            SnippetParameterBaseStorage::String(value),
+            name,
+            ExternalSnippetParameterType::SingleLineText,
+            sequential_id_generator,
+        );
+        parameter.missing = true;
+
+        let parameter_uuid = parameter.uuid;
+
+        snippet.parameters.push(parameter);
+
+        self.parameter_to_snippet
+            .insert(parameter_uuid, snippet_uuid);
+
+        return Ok(parameter_uuid);
+    }
+
+    /// bring the snippets made from the external snippet up to date after it was reloaded,
+    /// including placeholders for its package path. Connectors and parameters that are new are
+    /// added, ones that no longer exist are flagged as missing, and ones that came back are not
+    /// missing anymore. Values of parameters are kept
+    /// returns the parameter values that no longer fit the type of their parameter
+    ///
+    /// # Arguments
+    /// * 'external_snippet' - external snippet as it was reloaded
+    pub fn reconcile_snippets_with_external_snippet(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        external_snippet: &ExternalSnippet,
+    ) -> Vec<UnresolvedProjectReference> {
+        let mut unresolved_references = Vec::<UnresolvedProjectReference>::new();
+        let external_snippet_uuid = external_snippet.get_uuid();
+        let package_path = external_snippet.get_package_path();
+
+        let snippet_uuids: Vec<Uuid> = self
+            .snippets
+            .values()
+            .filter(|snippet| {
+                snippet.external_snippet_uuid == external_snippet_uuid
+                    || snippet.missing_package_path.as_ref() == Some(&package_path)
+            })
+            .map(|snippet| snippet.uuid)
+            .collect();
+
+        for snippet_uuid in snippet_uuids {
+            // can safely unwrap as the uuid was just taken from the snippets
+            let snippet = self.snippets.get_mut(&snippet_uuid).unwrap();
+
+            snippet.external_snippet_uuid = external_snippet_uuid;
+            snippet.missing_package_path = None;
+            snippet.name = external_snippet.get_name();
+            snippet.metadata = external_snippet.get_metadata().to_owned();
+
+            let pipeline_connectors =
+                external_snippet.create_pipeline_connectors_for_io_points(sequential_id_generator);
+
+            // connectors the external snippet no longer has keep their pipelines, flagged as missing
+            for existing_pipeline_connector in snippet.pipeline_connectors.iter_mut() {
+                existing_pipeline_connector.missing =
+                    !pipeline_connectors.iter().any(|pipeline_connector| {
+                        pipeline_connector.name == existing_pipeline_connector.name
+                            && pipeline_connector.input == existing_pipeline_connector.input
+                    });
+            }
+
+            for pipeline_connector in pipeline_connectors {
+                match snippet
+                    .pipeline_connectors
+                    .iter_mut()
+                    .find(|existing_pipeline_connector| {
+                        existing_pipeline_connector.name == pipeline_connector.name
+                            && existing_pipeline_connector.input == pipeline_connector.input
+                    }) {
+                    Some(existing_pipeline_connector) => {
+                        existing_pipeline_connector.external_pipeline_connector_uuid =
+                            pipeline_connector.external_pipeline_connector_uuid;
+                        existing_pipeline_connector.description = pipeline_connector.description;
+                    }
+                    None => {
+                        self.pipeline_connector_to_snippet
+                            .insert(pipeline_connector.uuid, snippet_uuid);
+
+                        snippet.pipeline_connectors.push(pipeline_connector);
+                    }
+                };
+            }
+
+            let parameters = external_snippet
+                .create_parameter_components_for_parameters(sequential_id_generator);
+
+            for existing_parameter in snippet.parameters.iter_mut() {
+                existing_parameter.missing = !parameters
+                    .iter()
+                    .any(|parameter| parameter.name == existing_parameter.name);
+            }
+
+            for parameter in parameters {
+                match snippet
+                    .parameters
+                    .iter_mut()
+                    .find(|existing_parameter| existing_parameter.name == parameter.name)
+                {
+                    Some(existing_parameter) => {
+                        let value = existing_parameter.content.to_string();
+
+                        existing_parameter.options = parameter.options;
+
+                        // a value that does not fit the new type is replaced by the default
+                        if existing_parameter.p_type != parameter.p_type {
+                            existing_parameter.p_type = parameter.p_type;
+                            existing_parameter.content = parameter.content;
+
+                            match existing_parameter.update_value(value.to_owned()) {
+                                Ok(()) => (),
+                                // an empty value was never set
+                                Err(_) if value.is_empty() => (),
+                                Err(_) => {
+                                    unresolved_references.push(
+                                        UnresolvedProjectReference::InvalidParameterValue {
+                                            package_path: package_path.to_owned(),
+                                            parameter_name: existing_parameter.name.to_owned(),
+                                            parameter_value: value,
+                                        },
+                                    );
+                                }
+                            };
+                        }
+                    }
+                    None => {
+                        self.parameter_to_snippet
+                            .insert(parameter.uuid, snippet_uuid);
+
+                        snippet.parameters.push(parameter);
+                    }
+                };
+            }
+        }
+
+        return unresolved_references;
+    }
+
+    /// turn the snippets made from an external snippet that no longer exists into placeholders
+    /// for its package path, so they are brought back once it is reloaded
+    ///
+    /// # Arguments
+    /// * 'external_snippet_uuid' - uuid of the external snippet that was removed
+    /// * 'package_path' - package path of the external snippet that was removed
+    pub fn flag_snippets_of_removed_external_snippet(
+        &mut self,
+        external_snippet_uuid: Uuid,
+        package_path: &PackagePath,
+    ) {
+        for snippet in self.snippets.values_mut() {
+            if snippet.external_snippet_uuid != external_snippet_uuid {
+                continue;
+            }
+
+            snippet.external_snippet_uuid = 0;
+            snippet.missing_package_path = Some(package_path.to_owned());
+        }
+    }
+
     /// delete snippet component and it's pipeline connector components
     /// assumes all pipelines associated with this have been disconnected / removed
     ///
@@ -295,6 +704,38 @@ impl SnippetManager {
         return self.snippets.values().collect::<Vec<&SnippetComponent>>();
     }
 
+    /// get reference to pipelines
+    pub fn get_pipelines_as_ref(&self) -> Vec<&PipelineComponent> {
+        return self.pipelines.values().collect::<Vec<&PipelineComponent>>();
+    }
+
+    /// find uuids of all the pipelines connected to the snippet
+    ///
+    /// # Arguments
+    /// * 'uuid' - uuid of the snippet
+    pub fn find_pipeline_uuids_from_snippet(&self, uuid: &Uuid) -> Vec<Uuid> {
+        let snippet = match self.snippets.get(uuid) {
+            Some(snippet) => snippet,
+            None => {
+                return Vec::new();
+            }
+        };
+
+        let mut pipeline_uuids = Vec::<Uuid>::new();
+
+        for pipeline_connector in snippet.pipeline_connectors.iter() {
+            for pipeline_uuid in
+                self.find_pipeline_uuids_from_pipeline_connector(&pipeline_connector.uuid)
+            {
+                if !pipeline_uuids.contains(&pipeline_uuid) {
+                    pipeline_uuids.push(pipeline_uuid);
+                }
+            }
+        }
+
+        return pipeline_uuids;
+    }
+
     /// find snippet from uuid
     ///
     /// # Arguments
@@ -417,6 +858,39 @@ impl SnippetManager {
         sequential_id_generator: &mut SequentialIdGenerator,
         from_uuid: Uuid,
         to_uuid: Uuid,
+    ) -> Result<Uuid, &'static str> {
+        return self.create_pipeline_handler(sequential_id_generator, None, from_uuid, to_uuid);
+    }
+
+    /// restore a previously deleted pipeline, keeping its uuid
+    /// assumes both of its pipeline connectors exist
+    ///
+    /// # Arguments
+    /// * 'pipeline' - copy of the pipeline before it was deleted
+    pub fn restore_pipeline(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        pipeline: &PipelineComponent,
+    ) -> Result<Uuid, &'static str> {
+        if self.pipelines.contains_key(&pipeline.uuid) {
+            return Err("pipeline with pipeline uuid already exists in snippet manager");
+        }
+
+        return self.create_pipeline_handler(
+            sequential_id_generator,
+            Some(pipeline.uuid),
+            pipeline.from_pipeline_connector_uuid,
+            pipeline.to_pipeline_connector_uuid,
+        );
+    }
+
+    /// create pipeline, with the given uuid if there is one
+    fn create_pipeline_handler(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        pipeline_uuid: Option<Uuid>,
+        from_uuid: Uuid,
+        to_uuid: Uuid,
     ) -> Result<Uuid, &'static str> {
         //get valid direction of pipeline, as from_uuid and to_uuid are not guarnteed to be input:false -> input:true
         let mut from_uuid = from_uuid;
@@ -533,13 +1007,18 @@ impl SnippetManager {
         }
 
         //create new pipeline
-        let pipeline_component = PipelineComponent::new(
+        let mut pipeline_component = PipelineComponent::new(
             graph_uuid_container.unwrap(),
             sequential_id_generator,
             &from_uuid,
             &to_uuid,
         );
 
+        // keep uuid of restored pipeline
+        if let Some(pipeline_uuid) = pipeline_uuid {
+            pipeline_component.uuid = pipeline_uuid;
+        }
+
         //get pipeline uuid for return
         let pipeline_uuid = pipeline_component.get_uuid();
 
@@ -845,7 +1324,11 @@ impl SnippetManager {
     /// Validate if the current snippet configuration is ready being being ran
     /// i.e in valid run state
     pub fn validate_for_run(&self) -> bool {
-        return true;
+        // placeholders for missing snippets, connectors or parameters cannot be ran
+        return !self
+            .snippets
+            .values()
+            .any(|snippet| snippet.has_missing_components());
     }
 
     /// Generate mapping of each from (snippet_uuid, output_name) -> [(snippet_uuid, input_name), ...]
@@ -978,10 +1461,12 @@ impl SnippetComponent {
             graph_uuid: graph_uuid,
             name: String::new(),
             external_snippet_uuid: 0,
+            missing_package_path: None,
             pipeline_connectors: Vec::new(),
             parameters: Vec::new(),
             x_position: x_position,
             y_position: y_position,
+            metadata: ExternalSnippetMetadata::default(),
         };
     }
 
@@ -1027,6 +1512,19 @@ impl SnippetComponent {
             .find(|pipe: &&PipelineConnectorComponent| pipe.name == name);
     }
 
+    /// find reference to pipeline connector from name and direction,
+    /// as an input and an output can share a name
+    pub fn find_pipeline_connector_from_name_and_input(
+        &self,
+        name: String,
+        input: bool,
+    ) -> Option<&PipelineConnectorComponent> {
+        return self
+            .pipeline_connectors
+            .iter()
+            .find(|pipe: &&PipelineConnectorComponent| pipe.name == name && pipe.input == input);
+    }
+
     /// find reference to pipeline parameter from name
     pub fn find_parameter_from_name(&self, name: String) -> Option<&SnippetParameterComponent> {
         //find pipeline in vector
@@ -1077,6 +1575,10 @@ impl SnippetComponent {
             sequential_id_generator.get_id(),
             self.get_name(),
             self.get_uuid(),
+            self.x_position,
+            self.y_position,
+            self.missing_package_path.is_some(),
+            self.metadata.to_owned(),
             front_pipeline_connectors,
             front_parameters,
         );
@@ -1103,6 +1605,8 @@ impl SnippetComponent {
                 pipeline_connector.uuid.clone(),
                 pipeline_connector.name.clone(),
                 pipeline_connector.input,
+                pipeline_connector.missing,
+                pipeline_connector.description.to_owned(),
             ));
         }
 
@@ -1125,6 +1629,10 @@ impl SnippetComponent {
                 parameter.uuid,
                 parameter.name.to_owned(),
                 parameter.p_type.to_string(),
+                parameter.get_front_value(),
+                parameter.options.get_description(),
+                parameter.options.is_required(),
+                parameter.missing,
             ));
         }
 
@@ -1164,6 +1672,22 @@ impl SnippetComponent {
     pub fn get_position(&self) -> (f64, f64) {
         return (self.x_position, self.y_position);
     }
+
+    /// get the package path of the snippet this is a placeholder for
+    /// none if this is not a placeholder for a missing snippet
+    pub fn get_missing_package_path(&self) -> Option<PackagePath> {
+        return self.missing_package_path.to_owned();
+    }
+
+    /// if this snippet is a placeholder, or has placeholder connectors or parameters
+    pub fn has_missing_components(&self) -> bool {
+        return self.missing_package_path.is_some()
+            || self
+                .pipeline_connectors
+                .iter()
+                .any(|connector| connector.missing)
+            || self.parameters.iter().any(|parameter| parameter.missing);
+    }
 }
 
 impl PipelineConnectorComponent {
@@ -1178,9 +1702,16 @@ impl PipelineConnectorComponent {
             external_pipeline_connector_uuid: external_pipeline_connector_uuid,
             name: name.to_string(),
             input: input,
+            missing: false,
+            description: None,
         };
     }
 
+    /// set the help text of the connector
+    pub fn set_description(&mut self, description: Option<String>) {
+        self.description = description;
+    }
+
     pub fn get_name(&self) -> String {
         return self.name.to_owned();
     }
@@ -1214,21 +1745,47 @@ impl PipelineComponent {
     }
 
     /// creates a new front content for this pipeline
+    /// assumes the front pipeline connectors of both ends already exist
     /// returns the front pipeline content
     pub fn create_pipeline_as_front_content(
         &self,
         visual_snippet_component_manager: &mut VisualSnippetComponentManager,
         sequential_id_generator: &mut SequentialIdGenerator,
-    ) -> FrontPipelineContent {
+    ) -> Result<FrontPipelineContent, &'static str> {
+        // get front uuids of the connecting pipeline connectors
+        let from_pipeline_connector_front_uuid = match visual_snippet_component_manager
+            .find_pipeline_connector_front_uuid(&self.from_pipeline_connector_uuid)
+        {
+            Some(uuid) => uuid,
+            None => {
+                return Err(
+                    "could not find front from pipeline connector uuid from pipeline component",
+                );
+            }
+        };
+
+        let to_pipeline_connector_front_uuid = match visual_snippet_component_manager
+            .find_pipeline_connector_front_uuid(&self.to_pipeline_connector_uuid)
+        {
+            Some(uuid) => uuid,
+            None => {
+                return Err(
+                    "could not find front to pipeline connector uuid from pipeline component",
+                );
+            }
+        };
+
         let front_pipeline_content = FrontPipelineContent::new(
             visual_snippet_component_manager,
             sequential_id_generator.get_id(),
             self.uuid,
+            from_pipeline_connector_front_uuid,
+            to_pipeline_connector_front_uuid,
         );
 
         visual_snippet_component_manager.put_pipeline(front_pipeline_content.get_uuid(), self.uuid);
 
-        return front_pipeline_content;
+        return Ok(front_pipeline_content);
     }
 
     /// get front from pipeline connector
@@ -1254,39 +1811,157 @@ impl SnippetParameterComponent {
             name: name,
             content: storage,
             p_type: p_type,
+            options: ExternalSnippetParameterOptions::default(),
+            missing: false,
         };
     }
 
+    pub fn set_options(&mut self, options: ExternalSnippetParameterOptions) {
+        self.options = options;
+    }
+
     /// Update value of parameter, value will be given in string format
-    /// and attempt to convert to its base type, failing on failure.
-    pub fn update_value(&mut self, value: String) -> Result<(), &'static str> {
-        self.content = match self.content {
-            SnippetParameterBaseStorage::String(_) => {
-                // attempt to convert to base type, which is string
-                // since we are already a string, no strict conversion needed
-                SnippetParameterBaseStorage::String(value)
+    /// and attempt to convert to the base type of its parameter type, failing on failure
+    /// or when the value does not fit the options of the parameter.
+    /// Values referencing project variables are converted once they are resolved for a run
+    pub fn update_value(&mut self, value: String) -> Result<(), String> {
+        // the front only knows the mask of a secret, given back it leaves the secret unchanged
+        if self.is_secret() && value == SECRET_MASK {
+            return Ok(());
+        }
+
+        if contains_variable_reference(&value) {
+            self.content = SnippetParameterBaseStorage::Template(value);
+
+            return Ok(());
+        }
+
+        let content = match self.p_type.parse_value(value) {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(format!("parameter {}: {}", self.name, e));
             }
         };
 
+        self.options.check_value(&self.name, &content)?;
+
+        self.content = content;
+
         return Ok(());
     }
 
-    pub fn get_uuid(&self) -> Uuid {
-        return self.uuid.to_owned();
+    /// Check the value of the parameter fits its options, for a value it was created with
+    pub fn check_value(&self) -> Result<(), String> {
+        return self.options.check_value(&self.name, &self.content);
     }
 
-    pub fn get_name(&self) -> String {
-        return self.name.to_owned();
-    }
+    /// Substitute the project variables referenced by the value of the parameter,
+    /// and convert it to the base type of its parameter type
+    pub fn resolve_variables(
+        &mut self,
+        variables: &BTreeMap<String, String>,
+    ) -> Result<(), String> {
+        let template = match &self.content {
+            SnippetParameterBaseStorage::Template(some) => some,
+            _ => {
+                return Ok(());
+            }
+        };
 
-    pub fn get_storage(&self) -> &SnippetParameterBaseStorage {
-        return &self.content;
-    }
+        let value = match substitute_variables(template, variables) {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(format!("parameter {}: {}", self.name, e));
+            }
+        };
 
-    pub fn get_p_type(&self) -> ExternalSnippetParameterType {
-        return self.p_type.clone();
-    }
-}
+        self.content = match self.p_type.parse_value(value) {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(format!(
+                    "parameter {} with its variables substituted: {}",
+                    self.name, e
+                ));
+            }
+        };
+
+        return Ok(());
+    }
+
+    /// Resolve the path of a file or directory path parameter relative to the base directory,
+    /// failing if there is no such file or directory. Other parameters are left unchanged
+    pub fn resolve_path(&mut self, base_directory: &Path) -> Result<(), String> {
+        let must_be_file = match self.p_type {
+            ExternalSnippetParameterType::FilePath => true,
+            ExternalSnippetParameterType::DirectoryPath => false,
+            _ => {
+                return Ok(());
+            }
+        };
+
+        let path = self.content.to_string();
+
+        if path.is_empty() {
+            return Err(format!("parameter {} has no path", self.name));
+        }
+
+        // absolute paths are kept as they are by join
+        let resolved_path = base_directory.join(&path);
+
+        if must_be_file && !resolved_path.is_file() {
+            return Err(format!(
+                "file {} of parameter {} does not exist",
+                resolved_path.to_string_lossy(),
+                self.name
+            ));
+        }
+
+        if !must_be_file && !resolved_path.is_dir() {
+            return Err(format!(
+                "directory {} of parameter {} does not exist",
+                resolved_path.to_string_lossy(),
+                self.name
+            ));
+        }
+
+        self.content =
+            SnippetParameterBaseStorage::String(resolved_path.to_string_lossy().to_string());
+
+        return Ok(());
+    }
+
+    pub fn get_uuid(&self) -> Uuid {
+        return self.uuid.to_owned();
+    }
+
+    pub fn get_name(&self) -> String {
+        return self.name.to_owned();
+    }
+
+    pub fn get_storage(&self) -> &SnippetParameterBaseStorage {
+        return &self.content;
+    }
+
+    /// if the value of the parameter is kept out of the project file and masked
+    pub fn is_secret(&self) -> bool {
+        return self.p_type == ExternalSnippetParameterType::Secret;
+    }
+
+    /// get the value of the parameter as shown in the front, secrets that are set are masked
+    pub fn get_front_value(&self) -> String {
+        let value = self.content.to_string();
+
+        if self.is_secret() && !value.is_empty() {
+            return SECRET_MASK.to_string();
+        }
+
+        return value;
+    }
+
+    pub fn get_p_type(&self) -> ExternalSnippetParameterType {
+        return self.p_type.clone();
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -2086,4 +2761,597 @@ mod tests {
         // validate three to one
         assert!(!snippet_manager.validate_pipeline(14, 6).unwrap());
     }
+
+    /// placeholders for missing snippets and connectors can be connected, but not ran
+    #[test]
+    fn test_missing_snippet_placeholders() {
+        let mut snippet_manager = SnippetManager::default();
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        // snippet that exists, with an output
+        let mut pipeline_connectors = Vec::<PipelineConnectorComponent>::new();
+        let external_pipeline_connector_uuid = sequential_id_generator.get_id();
+        pipeline_connectors.push(PipelineConnectorComponent::new(
+            &mut sequential_id_generator,
+            external_pipeline_connector_uuid,
+            "output_one",
+            false,
+        ));
+        let output_uuid = pipeline_connectors[0].get_uuid();
+
+        let external_snippet_uuid = sequential_id_generator.get_id();
+        snippet_manager.new_snippet_handler(
+            &mut sequential_id_generator,
+            pipeline_connectors,
+            Vec::new(),
+            external_snippet_uuid,
+            "testing_snippet_one".to_string(),
+            0.0,
+            0.0,
+        );
+
+        assert!(snippet_manager.validate_for_run());
+
+        // placeholder for a snippet that no longer exists
+        let missing_snippet_uuid = snippet_manager.new_missing_snippet(
+            &mut sequential_id_generator,
+            PackagePath::from("basic.missing_snippet".to_string()),
+            10.0,
+            20.0,
+        );
+
+        let missing_snippet = snippet_manager.find_snippet(&missing_snippet_uuid).unwrap();
+        assert_eq!(missing_snippet.get_name(), "missing_snippet");
+        assert_eq!(missing_snippet.get_position(), (10.0, 20.0));
+
+        let input_uuid = snippet_manager
+            .add_missing_pipeline_connector(
+                &mut sequential_id_generator,
+                missing_snippet_uuid,
+                "input_one",
+                true,
+            )
+            .unwrap();
+
+        snippet_manager
+            .add_missing_parameter(
+                &mut sequential_id_generator,
+                missing_snippet_uuid,
+                "param_one".to_string(),
+                "value".to_string(),
+            )
+            .unwrap();
+
+        // pipelines to placeholders are kept
+        snippet_manager
+            .create_pipeline(&mut sequential_id_generator, output_uuid, input_uuid)
+            .unwrap();
+
+        let missing_snippet = snippet_manager.find_snippet(&missing_snippet_uuid).unwrap();
+        assert_eq!(
+            missing_snippet
+                .find_parameter_from_name("param_one".to_string())
+                .unwrap()
+                .get_storage()
+                .to_string(),
+            "value"
+        );
+
+        assert!(!snippet_manager.validate_for_run());
+
+        // removing the placeholder makes the project runnable again
+        let pipeline_uuid =
+            snippet_manager.find_pipeline_uuids_from_pipeline_connector(&input_uuid)[0];
+        snippet_manager.delete_pipeline(&pipeline_uuid).unwrap();
+        snippet_manager
+            .delete_snippet(&missing_snippet_uuid)
+            .unwrap();
+
+        assert!(snippet_manager.validate_for_run());
+    }
+
+    #[test]
+    fn test_reconcile_snippets_with_reloaded_external_snippet() {
+        use crate::state_management::external_snippet_manager::{
+            BuiltinSnippetKind, ExternalSnippetManager, BUILTIN_CONSTANT_VALUE,
+        };
+
+        let mut snippet_manager = SnippetManager::default();
+        let mut external_snippet_manager = ExternalSnippetManager::default();
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let package_path = PackagePath::from("builtin.constant_integer".to_string());
+
+        // placeholder placed before the snippet was loaded, with a connector it does not have
+        let snippet_uuid = snippet_manager.new_missing_snippet(
+            &mut sequential_id_generator,
+            package_path.to_owned(),
+            0.0,
+            0.0,
+        );
+        let old_input_uuid = snippet_manager
+            .add_missing_pipeline_connector(
+                &mut sequential_id_generator,
+                snippet_uuid,
+                "old_input",
+                true,
+            )
+            .unwrap();
+        snippet_manager
+            .add_missing_parameter(
+                &mut sequential_id_generator,
+                snippet_uuid,
+                BUILTIN_CONSTANT_VALUE.to_string(),
+                "42".to_string(),
+            )
+            .unwrap();
+
+        let external_snippet_uuid = external_snippet_manager
+            .create_builtin_snippet(
+                BuiltinSnippetKind::ConstantInteger,
+                package_path.to_owned(),
+                0,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+        let external_snippet = external_snippet_manager
+            .find_external_snippet(external_snippet_uuid)
+            .unwrap();
+
+        let unresolved_references = snippet_manager.reconcile_snippets_with_external_snippet(
+            &mut sequential_id_generator,
+            external_snippet,
+        );
+        assert!(unresolved_references.is_empty());
+
+        let snippet = snippet_manager.find_snippet(&snippet_uuid).unwrap();
+        assert_eq!(snippet.get_external_snippet_id(), external_snippet_uuid);
+        assert!(snippet.get_missing_package_path().is_none());
+
+        // the new output is added, the connector that does not exist stays missing
+        let output = snippet
+            .find_pipeline_connector_from_name_and_input(BUILTIN_CONSTANT_VALUE.to_string(), false)
+            .unwrap();
+        assert!(!output.missing);
+        assert_eq!(
+            snippet_manager.find_snippet_uuid_from_pipeline_connector(&output.get_uuid()),
+            Some(snippet_uuid)
+        );
+        assert!(
+            snippet
+                .find_pipeline_connector(old_input_uuid)
+                .unwrap()
+                .missing
+        );
+
+        // the parameter is no longer missing, and its value is kept in its new type
+        let parameter = snippet
+            .find_parameter_from_name(BUILTIN_CONSTANT_VALUE.to_string())
+            .unwrap();
+        assert!(!parameter.missing);
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Integer(42)
+        );
+
+        // once the external snippet is removed, the snippet is a placeholder again
+        snippet_manager
+            .flag_snippets_of_removed_external_snippet(external_snippet_uuid, &package_path);
+
+        let snippet = snippet_manager.find_snippet(&snippet_uuid).unwrap();
+        assert!(snippet.get_missing_package_path() == Some(package_path));
+    }
+
+    #[test]
+    fn test_reconcile_snippets_reports_invalid_parameter_value() {
+        use crate::state_management::external_snippet_manager::{
+            BuiltinSnippetKind, ExternalSnippetManager, BUILTIN_CONSTANT_VALUE,
+        };
+
+        let mut snippet_manager = SnippetManager::default();
+        let mut external_snippet_manager = ExternalSnippetManager::default();
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let package_path = PackagePath::from("builtin.constant_integer".to_string());
+
+        // placeholder whose value is not an integer
+        let snippet_uuid = snippet_manager.new_missing_snippet(
+            &mut sequential_id_generator,
+            package_path.to_owned(),
+            0.0,
+            0.0,
+        );
+        snippet_manager
+            .add_missing_parameter(
+                &mut sequential_id_generator,
+                snippet_uuid,
+                BUILTIN_CONSTANT_VALUE.to_string(),
+                "not a number".to_string(),
+            )
+            .unwrap();
+
+        let external_snippet_uuid = external_snippet_manager
+            .create_builtin_snippet(
+                BuiltinSnippetKind::ConstantInteger,
+                package_path.to_owned(),
+                0,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+        let external_snippet = external_snippet_manager
+            .find_external_snippet(external_snippet_uuid)
+            .unwrap();
+
+        let unresolved_references = snippet_manager.reconcile_snippets_with_external_snippet(
+            &mut sequential_id_generator,
+            external_snippet,
+        );
+
+        // the value is reported, and the parameter falls back to the default
+        assert!(
+            unresolved_references
+                == vec![UnresolvedProjectReference::InvalidParameterValue {
+                    package_path: package_path,
+                    parameter_name: BUILTIN_CONSTANT_VALUE.to_string(),
+                    parameter_value: "not a number".to_string(),
+                }]
+        );
+
+        let snippet = snippet_manager.find_snippet(&snippet_uuid).unwrap();
+        let parameter = snippet
+            .find_parameter_from_name(BUILTIN_CONSTANT_VALUE.to_string())
+            .unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Integer(0)
+        );
+    }
+
+    #[test]
+    fn test_restore_deleted_snippet_with_pipelines() {
+        let mut snippet_manager = SnippetManager::default();
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        // snippet with an output
+        let mut pipeline_connectors = Vec::<PipelineConnectorComponent>::new();
+        let external_pipeline_connector_uuid = sequential_id_generator.get_id();
+        pipeline_connectors.push(PipelineConnectorComponent::new(
+            &mut sequential_id_generator,
+            external_pipeline_connector_uuid,
+            "output_one",
+            false,
+        ));
+        let output_uuid = pipeline_connectors[0].get_uuid();
+
+        let external_snippet_uuid = sequential_id_generator.get_id();
+        let first_snippet_uuid = snippet_manager.new_snippet_handler(
+            &mut sequential_id_generator,
+            pipeline_connectors,
+            Vec::new(),
+            external_snippet_uuid,
+            "testing_snippet_one".to_string(),
+            0.0,
+            0.0,
+        );
+
+        // snippet with an input
+        let mut pipeline_connectors = Vec::<PipelineConnectorComponent>::new();
+        let external_pipeline_connector_uuid = sequential_id_generator.get_id();
+        pipeline_connectors.push(PipelineConnectorComponent::new(
+            &mut sequential_id_generator,
+            external_pipeline_connector_uuid,
+            "input_one",
+            true,
+        ));
+        let input_uuid = pipeline_connectors[0].get_uuid();
+
+        let external_snippet_uuid = sequential_id_generator.get_id();
+        let second_snippet_uuid = snippet_manager.new_snippet_handler(
+            &mut sequential_id_generator,
+            pipeline_connectors,
+            Vec::new(),
+            external_snippet_uuid,
+            "testing_snippet_two".to_string(),
+            0.0,
+            0.0,
+        );
+
+        let pipeline_uuid = snippet_manager
+            .create_pipeline(&mut sequential_id_generator, output_uuid, input_uuid)
+            .unwrap();
+
+        assert_eq!(
+            snippet_manager.find_pipeline_uuids_from_snippet(&second_snippet_uuid),
+            vec![pipeline_uuid]
+        );
+
+        // keep copies, then delete the second snippet along with its pipeline
+        let snippet = snippet_manager
+            .find_snippet(&second_snippet_uuid)
+            .unwrap()
+            .clone();
+        let pipeline = snippet_manager
+            .find_pipeline(&pipeline_uuid)
+            .unwrap()
+            .clone();
+
+        snippet_manager.delete_pipeline(&pipeline_uuid).unwrap();
+        snippet_manager
+            .delete_snippet(&second_snippet_uuid)
+            .unwrap();
+
+        assert!(snippet_manager.find_snippet(&second_snippet_uuid).is_none());
+        assert!(snippet_manager
+            .find_pipeline_uuids_from_snippet(&first_snippet_uuid)
+            .is_empty());
+
+        // restore keeps the uuids
+        assert_eq!(
+            snippet_manager.restore_snippet(snippet.clone()).unwrap(),
+            second_snippet_uuid
+        );
+        assert_eq!(
+            snippet_manager
+                .restore_pipeline(&mut sequential_id_generator, &pipeline)
+                .unwrap(),
+            pipeline_uuid
+        );
+
+        assert_eq!(
+            snippet_manager.find_snippet_uuid_from_pipeline_connector(&input_uuid),
+            Some(second_snippet_uuid)
+        );
+        assert_eq!(
+            snippet_manager.find_pipeline_uuids_from_snippet(&first_snippet_uuid),
+            vec![pipeline_uuid]
+        );
+        assert!(snippet_manager.check_pipeline_connector_capacity_full(&input_uuid));
+
+        // cannot restore what already exists
+        assert!(snippet_manager.restore_snippet(snippet).is_err());
+        assert!(snippet_manager
+            .restore_pipeline(&mut sequential_id_generator, &pipeline)
+            .is_err());
+
+        // restored snippet and pipeline can be deleted again
+        snippet_manager.delete_pipeline(&pipeline_uuid).unwrap();
+        snippet_manager
+            .delete_snippet(&second_snippet_uuid)
+            .unwrap();
+    }
+
+    /// values are converted to the base type of the parameter type, and saved as strings that convert back
+    #[test]
+    fn test_typed_parameter_values() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let new_parameter =
+            |p_type: ExternalSnippetParameterType,
+             sequential_id_generator: &mut SequentialIdGenerator| {
+                return SnippetParameterComponent::new(
+                    ExternalSnippetParameterType::into_storage_type(&p_type),
+                    "param_one".to_string(),
+                    p_type,
+                    sequential_id_generator,
+                );
+            };
+
+        // integer
+        let mut parameter = new_parameter(
+            ExternalSnippetParameterType::Integer,
+            &mut sequential_id_generator,
+        );
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Integer(0)
+        );
+        parameter.update_value(" 42 ".to_string()).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Integer(42)
+        );
+        assert!(parameter.update_value("3.5".to_string()).is_err());
+        // failed updates keep the value
+        assert_eq!(parameter.get_storage().to_string(), "42");
+
+        // float
+        let mut parameter = new_parameter(
+            ExternalSnippetParameterType::Float,
+            &mut sequential_id_generator,
+        );
+        parameter.update_value("3.5".to_string()).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Float(3.5)
+        );
+        assert!(parameter.update_value("three".to_string()).is_err());
+        assert!(parameter.update_value("inf".to_string()).is_err());
+
+        let saved_value = parameter.get_storage().to_string();
+        parameter.update_value(saved_value).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Float(3.5)
+        );
+
+        // boolean
+        let mut parameter = new_parameter(
+            ExternalSnippetParameterType::Boolean,
+            &mut sequential_id_generator,
+        );
+        assert_eq!(parameter.get_storage().to_string(), "false");
+        parameter.update_value("true".to_string()).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Boolean(true)
+        );
+        assert!(parameter.update_value("yes".to_string()).is_err());
+
+        // enum defaults to its first choice
+        let mut parameter = new_parameter(
+            ExternalSnippetParameterType::Enum(vec!["fast".to_string(), "slow".to_string()]),
+            &mut sequential_id_generator,
+        );
+        assert_eq!(parameter.get_storage().to_string(), "fast");
+        parameter.update_value("slow".to_string()).unwrap();
+        assert_eq!(parameter.get_storage().to_string(), "slow");
+        assert!(parameter.update_value("medium".to_string()).is_err());
+    }
+
+    /// json values are parsed when set, and paths are resolved and checked for a run
+    #[test]
+    fn test_rich_parameter_values() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        // json
+        let mut parameter = SnippetParameterComponent::new(
+            ExternalSnippetParameterType::into_storage_type(&ExternalSnippetParameterType::Json),
+            "param_one".to_string(),
+            ExternalSnippetParameterType::Json,
+            &mut sequential_id_generator,
+        );
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Json(serde_json::Value::Null)
+        );
+        parameter
+            .update_value("{\"rows\": [1, 2]}".to_string())
+            .unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Json(serde_json::json!({"rows": [1, 2]}))
+        );
+        assert!(parameter.update_value("{\"rows\": ".to_string()).is_err());
+
+        let saved_value = parameter.get_storage().to_string();
+        parameter.update_value(saved_value).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Json(serde_json::json!({"rows": [1, 2]}))
+        );
+
+        // paths are resolved relative to the base directory
+        let base_directory = std::env::temp_dir();
+        let directory_name = format!("snippet_manager_test_{}", std::process::id());
+        std::fs::create_dir_all(base_directory.join(&directory_name)).unwrap();
+
+        let mut parameter = SnippetParameterComponent::new(
+            SnippetParameterBaseStorage::String(String::new()),
+            "param_two".to_string(),
+            ExternalSnippetParameterType::DirectoryPath,
+            &mut sequential_id_generator,
+        );
+
+        // a path must be given
+        assert!(parameter.resolve_path(&base_directory).is_err());
+
+        parameter.update_value(directory_name.to_owned()).unwrap();
+        parameter.resolve_path(&base_directory).unwrap();
+        assert_eq!(
+            parameter.get_storage().to_string(),
+            base_directory
+                .join(&directory_name)
+                .to_string_lossy()
+                .to_string()
+        );
+
+        // a directory is not a file
+        let mut parameter = SnippetParameterComponent::new(
+            SnippetParameterBaseStorage::String(String::new()),
+            "param_three".to_string(),
+            ExternalSnippetParameterType::FilePath,
+            &mut sequential_id_generator,
+        );
+        parameter.update_value(directory_name.to_owned()).unwrap();
+        assert!(parameter.resolve_path(&base_directory).is_err());
+
+        std::fs::remove_dir_all(base_directory.join(&directory_name)).unwrap();
+    }
+
+    #[test]
+    fn test_parameter_variables() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let mut variables = BTreeMap::<String, String>::new();
+        variables.insert("timeout".to_string(), "30".to_string());
+        variables.insert("base_url".to_string(), "http://localhost".to_string());
+
+        // values referencing variables are kept as given until resolved
+        let mut parameter = SnippetParameterComponent::new(
+            SnippetParameterBaseStorage::Integer(0),
+            "param_one".to_string(),
+            ExternalSnippetParameterType::Integer,
+            &mut sequential_id_generator,
+        );
+        parameter.update_value("${timeout}".to_string()).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Template("${timeout}".to_string())
+        );
+        assert_eq!(parameter.get_storage().to_string(), "${timeout}");
+
+        parameter.resolve_variables(&variables).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::Integer(30)
+        );
+
+        // substituted values must still be of the parameter type
+        parameter.update_value("${base_url}".to_string()).unwrap();
+        assert!(parameter.resolve_variables(&variables).is_err());
+
+        // references are substituted within text, and escaped references are kept
+        let mut parameter = SnippetParameterComponent::new(
+            SnippetParameterBaseStorage::String(String::new()),
+            "param_two".to_string(),
+            ExternalSnippetParameterType::SingleLineText,
+            &mut sequential_id_generator,
+        );
+        parameter
+            .update_value("${base_url}/users?limit=$${limit}".to_string())
+            .unwrap();
+        parameter.resolve_variables(&variables).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::String(
+                "http://localhost/users?limit=${limit}".to_string()
+            )
+        );
+
+        // variables that are not in the profile cannot be resolved
+        parameter.update_value("${data_dir}".to_string()).unwrap();
+        assert!(parameter.resolve_variables(&variables).is_err());
+
+        parameter.update_value("${base_url".to_string()).unwrap();
+        assert!(parameter.resolve_variables(&variables).is_err());
+    }
+
+    #[test]
+    fn test_secret_parameter_values() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let mut parameter = SnippetParameterComponent::new(
+            SnippetParameterBaseStorage::String(String::new()),
+            "token".to_string(),
+            ExternalSnippetParameterType::Secret,
+            &mut sequential_id_generator,
+        );
+
+        // an unset secret shows as empty
+        assert_eq!(parameter.get_front_value(), "");
+
+        parameter.update_value("abc123".to_string()).unwrap();
+        assert_eq!(parameter.get_front_value(), SECRET_MASK);
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::String("abc123".to_string())
+        );
+
+        // the mask given back by the front leaves the secret unchanged
+        parameter.update_value(SECRET_MASK.to_string()).unwrap();
+        assert_eq!(
+            parameter.get_storage(),
+            &SnippetParameterBaseStorage::String("abc123".to_string())
+        );
+    }
 }
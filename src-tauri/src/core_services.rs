@@ -1,9 +1,13 @@
 pub mod concurrent_processes;
 pub mod directory_manager;
+pub mod history_manager;
 pub mod installation_manager;
 pub mod project_service;
+pub mod run_history_service;
 pub mod runtime_logging_service;
 pub mod schema_validator;
+pub mod secret_store_service;
+pub mod variable_profile_service;
 pub mod visual_directory_component_manager;
 pub mod visual_workspace_component_manager;
 pub mod workspace_manager;
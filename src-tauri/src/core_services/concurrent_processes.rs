@@ -2,17 +2,30 @@ use std::{
     env,
     ops::DerefMut,
     path::PathBuf,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
 };
 
 use tauri::Manager;
 
 use crate::{
     python_libraries::python_run_module::InitializedPythonSnippetRunnerBuilder,
-    state_management::ApplicationState,
+    state_management::{external_snippet_manager::SnippetInitializationError, ApplicationState},
 };
 
-use super::{runtime_logging_service::LoggingStreamInstance, workspace_manager::WorkspaceManager};
+use super::{
+    directory_manager::{find_changed_paths, fingerprint_snippet_directory},
+    project_service::UnresolvedProjectReference,
+    run_history_service::{write_run_record, RunStatus},
+    runtime_logging_service::{LogLevel, LoggingStreamInstance},
+    workspace_manager::WorkspaceManager,
+};
+
+/// snippet directory, relative to the runables directory
+const RELATIVE_SNIPPET_DIRECTORY: &str = "snippets/root";
 
 /// This event spawns the initalize directory and workspace event, returning the event id and the log file id.
 /// This will emit the event id to the front id  when the process is complete
@@ -30,19 +43,57 @@ pub async fn spawn_initialize_directory_event(
 
     // Initialize directory of snippets
     directory_manager
-        .initialize(&"snippets/root".to_string(), sequential_id_generator)
+        .initialize(
+            &RELATIVE_SNIPPET_DIRECTORY.to_string(),
+            sequential_id_generator,
+        )
         .unwrap();
 
     // create external snippets from directory manager
     let result = external_snippet_manager
         .create_external_snippets_from_directory(directory_manager, sequential_id_generator);
 
-    // TODO very basic logging, will expand in future
     if let Err(e) = result {
-        logging_stream_instance.append_log(format!("Failed to initialize snippet: {}", e));
-    } else {
+        logging_stream_instance.append_leveled_log(
+            LogLevel::Error,
+            format!("Failed to initialize snippets: {}", e),
+        );
+
+        return;
+    }
+
+    // the other snippets are still loaded when some cannot be initialized
+    let initialization_errors = external_snippet_manager.get_initialization_errors();
+
+    log_initialization_errors(logging_stream_instance, initialization_errors);
+
+    if initialization_errors.is_empty() {
         logging_stream_instance
             .append_log(format!("Finished successfully intializing all snippets"));
+    } else {
+        logging_stream_instance.append_leveled_log(
+            LogLevel::Warning,
+            format!(
+                "Finished intializing snippets, {} could not be initialized",
+                initialization_errors.len()
+            ),
+        );
+    }
+}
+
+/// Log why each of the snippets could not be initialized, with the python traceback if there is one
+fn log_initialization_errors(
+    logging_stream_instance: &mut LoggingStreamInstance,
+    initialization_errors: &Vec<SnippetInitializationError>,
+) {
+    for initialization_error in initialization_errors.iter() {
+        let mut log = format!("Failed to initialize snippet {}", initialization_error);
+
+        if let Some(traceback) = initialization_error.get_traceback() {
+            log.push_str(&format!("\n{}", traceback));
+        }
+
+        logging_stream_instance.append_leveled_log(LogLevel::Error, log);
     }
 }
 
@@ -58,27 +109,50 @@ pub async fn spawn_initialize_workspace_event(application_state: Arc<Mutex<Appli
     state.workspace_manager = new_workspace_manager;
 }
 
+/// This runs the built snippets, recording the run in the runs directory once it is done
 pub async fn spawn_run_snippets_event(
     build_state: InitializedPythonSnippetRunnerBuilder,
     mut logging_stream_instance: LoggingStreamInstance,
+    cancelled: Arc<AtomicBool>,
+    runs_directory: PathBuf,
 ) {
+    let mut run_record = build_state.create_run_record();
+
+    // secrets of the run are masked in its logs and its record
+    let secrets = build_state.get_secret_values();
+    logging_stream_instance.set_redacted_secrets(secrets.clone());
+
     // run the build state
-    match build_state.run(&mut logging_stream_instance) {
+    let status = match build_state.run(&mut logging_stream_instance, &cancelled, &mut run_record) {
         Ok(_) => {
             logging_stream_instance
                 .append_log(format!("Finished successfully running all snippets"));
+
+            RunStatus::Succeeded
         }
         Err(e) => {
             // TODO remove
-            logging_stream_instance.append_log(e);
+            logging_stream_instance.append_leveled_log(LogLevel::Error, e);
+
+            match cancelled.load(Ordering::Relaxed) {
+                true => RunStatus::Cancelled,
+                false => RunStatus::Failed,
+            }
         }
     };
 
-    // close the log
-    let app_handle = logging_stream_instance.close_log();
+    // record the run, with the logs of its stream
+    run_record.finish(status, logging_stream_instance.get_logs());
+    run_record.redact_secrets(&secrets);
+
+    if let Err(e) = write_run_record(&runs_directory, &mut run_record) {
+        logging_stream_instance.append_leveled_log(LogLevel::Warning, e);
+    }
 
-    // emit event back to front end
-    app_handle.emit_all("snippets_ran", "".to_string()).unwrap();
+    // close the log, emit event back to front end
+    if let Some(app_handle) = logging_stream_instance.close_log() {
+        app_handle.emit_all("snippets_ran", "".to_string()).unwrap();
+    }
 }
 
 pub fn get_working_directory() -> PathBuf {
@@ -123,3 +197,282 @@ pub fn get_projects_directory() -> PathBuf {
             .join("SnippetTestBuilder/projects");
     }
 }
+
+// get autosave recovery directory
+pub fn get_recovery_directory() -> PathBuf {
+    // if we are in test mode
+    if cfg!(test) {
+        return get_working_directory().join("recovery");
+    } else {
+        // base directory
+        return directories::UserDirs::new()
+            .unwrap()
+            .home_dir()
+            .join("SnippetTestBuilder/recovery");
+    }
+}
+
+// get directory of the logs which do not belong to a saved project
+pub fn get_logs_directory() -> PathBuf {
+    // if we are in test mode
+    if cfg!(test) {
+        return get_working_directory().join("logs");
+    } else {
+        // base directory
+        return directories::UserDirs::new()
+            .unwrap()
+            .home_dir()
+            .join("SnippetTestBuilder/logs");
+    }
+}
+
+// get directory of the runs which do not belong to a saved project
+pub fn get_runs_directory() -> PathBuf {
+    // if we are in test mode
+    if cfg!(test) {
+        return get_working_directory().join("runs");
+    } else {
+        // base directory
+        return directories::UserDirs::new()
+            .unwrap()
+            .home_dir()
+            .join("SnippetTestBuilder/runs");
+    }
+}
+
+// get directory of the secret parameter values of the projects, away from the project files
+pub fn get_secrets_directory() -> PathBuf {
+    // if we are in test mode
+    if cfg!(test) {
+        return get_working_directory().join("secrets");
+    } else {
+        // base directory
+        return directories::UserDirs::new()
+            .unwrap()
+            .home_dir()
+            .join("SnippetTestBuilder/secrets");
+    }
+}
+
+/// how often the projects of the window sessions are autosaved
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// This spawns the autosave thread, which periodically writes the project of each window session
+/// to its recovery file. This runs on its own thread, so the recovery files survive the embedded
+/// interpreter taking down the process
+pub fn spawn_autosave_thread(application_state: Arc<Mutex<ApplicationState>>) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(AUTOSAVE_INTERVAL);
+
+        // lock the application state
+        let mut state_guard = match application_state.lock() {
+            Ok(state_guard) => state_guard,
+            Err(_) => {
+                // state is poisoned, nothing can be saved anymore
+                return;
+            }
+        };
+        let state = state_guard.deref_mut();
+
+        // borrow split
+        let window_manager = &mut state.window_manager;
+        let external_snippet_manager = &state.external_snippet_manager;
+
+        for window_session in window_manager.get_window_sessions_mut() {
+            let window_session_uuid = window_session.uuid;
+
+            for project_manager in window_session.get_project_managers_mut() {
+                if let Err(e) = project_manager.autosave_project(external_snippet_manager) {
+                    println!(
+                        "Unable to autosave project of window session {}: {}",
+                        window_session_uuid, e
+                    );
+                }
+            }
+        }
+    });
+}
+
+/// how often the snippet directory is checked for changed snippet files
+const SNIPPET_DIRECTORY_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// This spawns the snippet directory watcher thread, which reloads the snippets whose files changed
+/// so changing a snippet does not require restarting the app. Once reloaded, the front end is told
+/// to reload the snippet directory and redraw the open project
+pub fn spawn_snippet_directory_watcher_thread(
+    application_state: Arc<Mutex<ApplicationState>>,
+    app_handle: tauri::AppHandle,
+) {
+    let snippets_directory = get_runables_directory().join(RELATIVE_SNIPPET_DIRECTORY);
+
+    std::thread::spawn(move || {
+        let mut fingerprint = fingerprint_snippet_directory(&snippets_directory);
+
+        loop {
+            std::thread::sleep(SNIPPET_DIRECTORY_WATCH_INTERVAL);
+
+            let new_fingerprint = fingerprint_snippet_directory(&snippets_directory);
+            let changed_paths = find_changed_paths(&fingerprint, &new_fingerprint);
+
+            fingerprint = new_fingerprint;
+
+            if changed_paths.is_empty() {
+                continue;
+            }
+
+            if let Err(e) =
+                reload_snippet_directory(&application_state, &app_handle, &changed_paths)
+            {
+                println!("Unable to reload snippet directory: {}", e);
+            }
+        }
+    });
+}
+
+/// Rescan the snippet directory and build again the snippets whose files changed, bringing
+/// the snippets placed in the open projects up to date with them. The python of the changed
+/// snippets is initialized without holding the application state, as it can take a while.
+/// Once reloaded, the directory_changed event is emitted with the log stream of the snippets
+/// that could not be initialized, if any
+fn reload_snippet_directory(
+    application_state: &Arc<Mutex<ApplicationState>>,
+    app_handle: &tauri::AppHandle,
+    changed_paths: &Vec<PathBuf>,
+) -> Result<(), String> {
+    let external_snippet_build = {
+        // lock the application state
+        let mut state_guard = match application_state.lock() {
+            Ok(state_guard) => state_guard,
+            Err(_) => {
+                return Err("Application state is poisoned".to_string());
+            }
+        };
+        let state = state_guard.deref_mut();
+
+        // nothing was loaded yet, the changes are read when it is initialized
+        if !state.directory_manager.is_initialized() {
+            return Ok(());
+        }
+
+        // rescan, the snippets still in the directory keep their directory uuids
+        state.directory_manager.initialize(
+            &RELATIVE_SNIPPET_DIRECTORY.to_string(),
+            &mut state.sequential_id_generator,
+        )?;
+
+        state
+            .external_snippet_manager
+            .prepare_external_snippets_build(
+                &state.directory_manager,
+                Some(changed_paths),
+                &mut state.sequential_id_generator,
+            )?
+    };
+
+    // run the init of the changed snippets while the application state is free
+    let external_snippet_build = external_snippet_build.build();
+
+    // lock the application state again
+    let mut state_guard = match application_state.lock() {
+        Ok(state_guard) => state_guard,
+        Err(_) => {
+            return Err("Application state is poisoned".to_string());
+        }
+    };
+    let state = state_guard.deref_mut();
+
+    // borrow split
+    let sequential_id_generator = &mut state.sequential_id_generator;
+    let logging_manager = &mut state.logging_manager;
+    let external_snippet_manager = &mut state.external_snippet_manager;
+    let window_manager = &mut state.window_manager;
+
+    let external_snippet_reload = external_snippet_manager
+        .finish_external_snippets_build(external_snippet_build, sequential_id_generator);
+
+    // parameter values of the open projects that no longer fit their parameter
+    let mut unresolved_references = Vec::<UnresolvedProjectReference>::new();
+
+    for window_session in window_manager.get_window_sessions_mut() {
+        for project_manager in window_session.get_project_managers_mut() {
+            let snippet_manager = &mut project_manager.snippet_manager;
+
+            for (external_snippet_uuid, package_path) in external_snippet_reload.get_removed() {
+                snippet_manager.flag_snippets_of_removed_external_snippet(
+                    *external_snippet_uuid,
+                    package_path,
+                );
+            }
+
+            for external_snippet_uuid in external_snippet_reload.get_reloaded() {
+                if let Some(external_snippet) =
+                    external_snippet_manager.find_external_snippet(*external_snippet_uuid)
+                {
+                    unresolved_references.extend(
+                        snippet_manager.reconcile_snippets_with_external_snippet(
+                            sequential_id_generator,
+                            external_snippet,
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    // only the errors of this reload are logged, the older ones were logged when they happened.
+    // the snippets are reloaded for every window, so the stream belongs to no window session
+    let initialization_errors = external_snippet_reload.get_initialization_errors();
+    let mut logging_stream_instance = None;
+
+    if !initialization_errors.is_empty() || !unresolved_references.is_empty() {
+        match logging_manager.create_application_stream(
+            app_handle.clone(),
+            sequential_id_generator,
+            get_logs_directory().join("initialization"),
+        ) {
+            Ok(some) => logging_stream_instance = Some(some),
+            Err(e) => {
+                println!(
+                    "Unable to create the log stream of the snippet reload: {}",
+                    e
+                );
+            }
+        };
+    }
+
+    // the logs are written without holding the application state
+    drop(state_guard);
+
+    let stream_i = match logging_stream_instance {
+        Some(mut logging_stream_instance) => {
+            log_initialization_errors(&mut logging_stream_instance, initialization_errors);
+
+            for unresolved_reference in unresolved_references.iter() {
+                logging_stream_instance
+                    .append_leveled_log(LogLevel::Warning, unresolved_reference.to_string());
+            }
+
+            logging_stream_instance.append_leveled_log(
+                LogLevel::Warning,
+                format!(
+                    "Finished reloading snippets, {} could not be initialized and {} parameter values were reset",
+                    initialization_errors.len(),
+                    unresolved_references.len()
+                ),
+            );
+
+            let stream_i = logging_stream_instance.get_stream_i();
+            logging_stream_instance.close_log();
+
+            Some(stream_i)
+        }
+        None => None,
+    };
+
+    // the front end reloads the snippet directory and redraws the open project
+    if let Err(e) = app_handle.emit_all("directory_changed", stream_i) {
+        println!("Could not emit directory_changed event: {}", e);
+    }
+
+    return Ok(());
+}
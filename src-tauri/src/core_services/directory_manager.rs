@@ -1,14 +1,20 @@
 use enum_as_inner::EnumAsInner;
 use std::path::Path;
 use std::{
+    collections::{BTreeMap, HashMap},
     ffi::OsStr,
     fs::{self, File},
     path::PathBuf,
+    time::SystemTime,
 };
 use tauri::RunEvent;
 
+use strum::IntoEnumIterator;
+
 use crate::{
-    state_management::external_snippet_manager::PackagePath,
+    state_management::external_snippet_manager::{
+        BuiltinSnippetKind, ExternalSnippetManager, PackagePath,
+    },
     utils::sequential_id_generator::{SequentialIdGenerator, Uuid},
 };
 
@@ -17,6 +23,15 @@ use super::{
     visual_directory_component_manager::{FrontDirectoryContent, VisualDirectoryComponentManager},
 };
 
+/// file of a composite snippet, a snippet built from other snippets
+pub const COMPOSITE_SNIPPET_FILE: &str = "composite.json";
+
+/// category in the snippet directory composite snippets are saved to
+pub const COMPOSITE_SNIPPETS_CATEGORY: &str = "composites";
+
+/// category in the snippet directory of the snippets implemented in rust
+pub const BUILTIN_SNIPPETS_CATEGORY: &str = "builtin";
+
 // This here is not ui related
 pub struct DirectoryManager {
     //snippet directory
@@ -46,7 +61,10 @@ pub enum SnippetDirectoryType {
     Snippet(SnippetDirectorySnippet),
 }
 
-pub struct SnippetDirectorySnippet {}
+pub struct SnippetDirectorySnippet {
+    // builtin snippets have no directory
+    builtin: bool,
+}
 
 pub struct SnippetDirectoryCategory {
     children: Vec<SnippetDirectoryEntry>,
@@ -68,28 +86,43 @@ impl DirectoryManager {
         relative_snippet_directory: &String,
         sequential_id_generator: &mut SequentialIdGenerator,
     ) -> Result<(), String> {
-        // if we are already initialized
-        if let Some(_) = self.snippet_directory.root {
-            // unitialize snippet directory
-            todo!();
+        // if we are already initialized, rescan into a new snippet directory, where the entries
+        // still there keep their uuids so their snippets and front entries can be found again
+        if self.snippet_directory.is_initialized() {
+            let mut snippet_directory = SnippetDirectory::default();
+
+            snippet_directory.initialize(relative_snippet_directory, sequential_id_generator)?;
+            snippet_directory
+                .add_builtin_snippets(relative_snippet_directory, sequential_id_generator)?;
+            snippet_directory.carry_over_uuids(&self.snippet_directory);
+
+            self.snippet_directory = snippet_directory;
 
-            // clear visual component manager
+            return Ok(());
         }
 
         // Then create the snippet directory
         self.snippet_directory
             .initialize(relative_snippet_directory, sequential_id_generator)?;
 
+        // builtin snippets are always available
+        self.snippet_directory
+            .add_builtin_snippets(relative_snippet_directory, sequential_id_generator)?;
+
         return Ok(());
     }
 
     /// Get directory manager as front
     pub fn get_as_front(
         &mut self,
+        external_snippet_manager: &ExternalSnippetManager,
         sequential_id_generator: &mut SequentialIdGenerator,
     ) -> Vec<FrontDirectoryContent> {
-        self.visual_component_manager
-            .get_directory_as_front(&self.snippet_directory, sequential_id_generator)
+        self.visual_component_manager.get_directory_as_front(
+            &self.snippet_directory,
+            external_snippet_manager,
+            sequential_id_generator,
+        )
     }
 
     /// find directory entry from package path
@@ -132,6 +165,84 @@ impl DirectoryManager {
     pub fn is_initialized(&self) -> bool {
         return self.snippet_directory.is_initialized();
     }
+
+    /// add the snippet at the path to a category directly under the root, creating the category
+    /// if it does not exist yet, so snippets created while the app is running can be used
+    ///
+    /// # Arguments
+    /// * 'category_name' - name of the category under the root
+    /// * 'name' - name of the snippet, which is also its directory name
+    /// * 'path' - path of the snippet directory
+    ///
+    /// returns the directory entry uuid and the package path of the snippet
+    pub fn add_snippet_entry(
+        &mut self,
+        category_name: &String,
+        name: String,
+        path: PathBuf,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<(Uuid, PackagePath), String> {
+        let root_entry = match self.snippet_directory.root.as_mut() {
+            Some(some) => some,
+            None => {
+                return Err("Snippet directory is not initialized".to_string());
+            }
+        };
+
+        let root_path = root_entry.get_path();
+        let root_category = root_entry.get_as_category()?;
+
+        // find or create the category
+        let category_index = match root_category
+            .children
+            .iter()
+            .position(|child| child.get_name().eq(category_name))
+        {
+            Some(index) => index,
+            None => {
+                root_category.add_child(SnippetDirectoryEntry::new_category(
+                    category_name.to_owned(),
+                    root_path.join(category_name),
+                    sequential_id_generator,
+                ));
+
+                root_category.children.len() - 1
+            }
+        };
+
+        let category = root_category.children[category_index].get_as_category()?;
+
+        if category
+            .children
+            .iter()
+            .any(|child| child.get_name().eq(&name))
+        {
+            return Err(format!(
+                "A snippet named {} already exists in {}",
+                name, category_name
+            ));
+        }
+
+        let snippet_entry =
+            SnippetDirectoryEntry::new_snippet(name.to_owned(), path, sequential_id_generator);
+        let directory_uuid = snippet_entry.get_uuid();
+
+        category.add_child(snippet_entry);
+
+        let mut package_path = PackagePath::default();
+        package_path.add(category_name.to_owned());
+        package_path.add(name);
+
+        return Ok((directory_uuid, package_path));
+    }
+
+    /// get the directory composite snippets are saved to
+    pub fn get_composite_snippets_directory(&self) -> Result<PathBuf, String> {
+        return match self.snippet_directory.get_root_directory_entry() {
+            Some(root_entry) => Ok(root_entry.get_path().join(COMPOSITE_SNIPPETS_CATEGORY)),
+            None => Err("Snippet directory is not initialized".to_string()),
+        };
+    }
 }
 
 impl Default for SnippetDirectory {
@@ -370,12 +481,71 @@ impl SnippetDirectory {
                         return Ok(true);
                     }
                 }
+
+                // composite snippets are snippets as well
+                if entry.file_name().eq(OsStr::new(COMPOSITE_SNIPPET_FILE)) {
+                    return Ok(true);
+                }
             }
         }
 
         return Ok(false);
     }
 
+    /// add the builtin category with the builtin snippets under the root,
+    /// creating an empty root if there is no snippet directory
+    fn add_builtin_snippets(
+        &mut self,
+        relative_snippet_directory: &String,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<(), String> {
+        if let None = self.root {
+            self.root = Some(SnippetDirectoryEntry::new_category(
+                "root".to_string(),
+                get_runables_directory().join(relative_snippet_directory),
+                sequential_id_generator,
+            ));
+        }
+
+        // can safely unwrap as we just made sure there is a root
+        let root_entry = self.root.as_mut().unwrap();
+        let root_path = root_entry.get_path();
+
+        let mut builtin_entry = SnippetDirectoryEntry::new_category(
+            BUILTIN_SNIPPETS_CATEGORY.to_string(),
+            root_path.join(BUILTIN_SNIPPETS_CATEGORY),
+            sequential_id_generator,
+        );
+
+        let builtin_category = builtin_entry.get_as_category()?;
+
+        for builtin_snippet_kind in BuiltinSnippetKind::iter() {
+            builtin_category.add_child(SnippetDirectoryEntry::new_builtin_snippet(
+                builtin_snippet_kind.to_string(),
+                sequential_id_generator,
+            ));
+        }
+
+        root_entry.get_as_category()?.add_child(builtin_entry);
+
+        return Ok(());
+    }
+
+    /// give the entries the uuids of the entries at the same package path in the previous
+    /// snippet directory, as long as they are still of the same kind
+    fn carry_over_uuids(&mut self, previous_snippet_directory: &SnippetDirectory) {
+        // (package path, is snippet) -> uuid
+        let mut previous_uuids = HashMap::<(PackagePath, bool), Uuid>::new();
+
+        if let Some(previous_root) = &previous_snippet_directory.root {
+            previous_root.collect_uuids(PackagePath::default(), &mut previous_uuids);
+        }
+
+        if let Some(root) = &mut self.root {
+            root.carry_over_uuids(PackagePath::default(), &previous_uuids);
+        }
+    }
+
     pub fn get_root_directory_entry(&self) -> Option<&SnippetDirectoryEntry> {
         return self.root.as_ref();
     }
@@ -417,6 +587,68 @@ impl SnippetDirectoryEntry {
         };
     }
 
+    pub fn new_builtin_snippet(
+        name: String,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Self {
+        return SnippetDirectoryEntry {
+            name: name,
+            uuid: sequential_id_generator.get_id(),
+            content: SnippetDirectoryType::Snippet(SnippetDirectorySnippet::new_builtin()),
+            path: PathBuf::new(),
+        };
+    }
+
+    /// collect the uuids of the entry and its children by (package path, is snippet)
+    fn collect_uuids(
+        &self,
+        package_path: PackagePath,
+        uuids: &mut HashMap<(PackagePath, bool), Uuid>,
+    ) {
+        match &self.content {
+            SnippetDirectoryType::Category(category) => {
+                for child in &category.children {
+                    let mut child_package_path = package_path.to_owned();
+                    child_package_path.add(child.get_name());
+
+                    child.collect_uuids(child_package_path, uuids);
+                }
+
+                uuids.insert((package_path, false), self.uuid);
+            }
+            SnippetDirectoryType::Snippet(_) => {
+                uuids.insert((package_path, true), self.uuid);
+            }
+        };
+    }
+
+    /// set the uuids of the entry and its children to the collected uuids
+    fn carry_over_uuids(
+        &mut self,
+        package_path: PackagePath,
+        uuids: &HashMap<(PackagePath, bool), Uuid>,
+    ) {
+        match &mut self.content {
+            SnippetDirectoryType::Category(category) => {
+                for child in category.children.iter_mut() {
+                    let mut child_package_path = package_path.to_owned();
+                    child_package_path.add(child.get_name());
+
+                    child.carry_over_uuids(child_package_path, uuids);
+                }
+
+                if let Some(uuid) = uuids.get(&(package_path, false)) {
+                    self.uuid = *uuid;
+                }
+            }
+            SnippetDirectoryType::Snippet(_) => {
+                if let Some(uuid) = uuids.get(&(package_path, true)) {
+                    self.uuid = *uuid;
+                }
+            }
+        };
+    }
+
     pub fn get_as_category(&mut self) -> Result<&mut SnippetDirectoryCategory, String> {
         match &mut self.content {
             SnippetDirectoryType::Category(some) => {
@@ -459,6 +691,17 @@ impl SnippetDirectoryEntry {
         return self.path.to_owned();
     }
 
+    /// get the composite snippet file, if the snippet is a composite snippet
+    pub fn get_composite_file(&self) -> Option<PathBuf> {
+        let composite_file = self.path.join(COMPOSITE_SNIPPET_FILE);
+
+        if composite_file.is_file() {
+            return Some(composite_file);
+        }
+
+        return None;
+    }
+
     /// get the runnable python file for the directory manager
     pub fn get_python_file(&self) -> Result<PathBuf, String> {
         if self.path.is_dir() {
@@ -526,10 +769,94 @@ impl SnippetDirectoryCategory {
 
 impl SnippetDirectorySnippet {
     fn new() -> Self {
-        return SnippetDirectorySnippet {};
+        return SnippetDirectorySnippet { builtin: false };
+    }
+
+    fn new_builtin() -> Self {
+        return SnippetDirectorySnippet { builtin: true };
+    }
+
+    pub fn is_builtin(&self) -> bool {
+        return self.builtin;
+    }
+}
+
+/// files of the snippet directory with when they were last modified, to find the files that changed
+pub type SnippetDirectoryFingerprint = BTreeMap<PathBuf, SystemTime>;
+
+/// take the fingerprint of the files under the snippet directory, leaving out the files
+/// written while the snippets are loaded
+pub fn fingerprint_snippet_directory(snippets_directory: &Path) -> SnippetDirectoryFingerprint {
+    let mut fingerprint = SnippetDirectoryFingerprint::new();
+
+    fingerprint_walker(snippets_directory, &mut fingerprint);
+
+    return fingerprint;
+}
+
+fn fingerprint_walker(current_path: &Path, fingerprint: &mut SnippetDirectoryFingerprint) {
+    // directories that cannot be read, such as ones removed while walking, are left out
+    let dir_entries = match fs::read_dir(current_path) {
+        Ok(some) => some,
+        Err(_) => {
+            return;
+        }
+    };
+
+    for directory_entry in dir_entries {
+        let entry = match directory_entry {
+            Ok(some) => some,
+            Err(_) => {
+                continue;
+            }
+        };
+
+        let path = entry.path();
+        let file_name = entry.file_name();
+
+        // __init__.py files are written by the directory walker, and __pycache__ by python
+        if file_name.eq(OsStr::new("__init__.py")) || file_name.eq(OsStr::new("__pycache__")) {
+            continue;
+        }
+
+        if path.is_dir() {
+            fingerprint_walker(&path, fingerprint);
+
+            continue;
+        }
+
+        let modified = match entry.metadata().and_then(|metadata| metadata.modified()) {
+            Ok(some) => some,
+            Err(_) => {
+                continue;
+            }
+        };
+
+        fingerprint.insert(path, modified);
     }
 }
 
+/// find the files that were added, modified or removed between the fingerprints
+pub fn find_changed_paths(
+    previous_fingerprint: &SnippetDirectoryFingerprint,
+    fingerprint: &SnippetDirectoryFingerprint,
+) -> Vec<PathBuf> {
+    let mut changed_paths: Vec<PathBuf> = fingerprint
+        .iter()
+        .filter(|(path, modified)| previous_fingerprint.get(*path) != Some(*modified))
+        .map(|(path, _)| path.to_owned())
+        .collect();
+
+    changed_paths.extend(
+        previous_fingerprint
+            .keys()
+            .filter(|path| !fingerprint.contains_key(*path))
+            .cloned(),
+    );
+
+    return changed_paths;
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -538,9 +865,12 @@ mod tests {
         get_runables_directory, get_working_directory,
     };
     use crate::core_services::directory_manager::{SnippetDirectoryEntry, SnippetDirectoryType};
-    use crate::utils::sequential_id_generator::SequentialIdGenerator;
+    use crate::state_management::external_snippet_manager::{ExternalSnippetManager, PackagePath};
+    use crate::utils::sequential_id_generator::{SequentialIdGenerator, Uuid};
 
-    use super::SnippetDirectory;
+    use super::{
+        find_changed_paths, fingerprint_snippet_directory, DirectoryManager, SnippetDirectory,
+    };
 
     #[test]
     fn test_scan_and_map_directory() {
@@ -786,4 +1116,270 @@ mod tests {
             SnippetDirectoryType::Snippet(_) => {}
         };
     }
+
+    #[test]
+    fn test_find_changed_paths() {
+        let snippets_directory = std::env::temp_dir().join(format!(
+            "directory_manager_fingerprint_test_{}",
+            std::process::id()
+        ));
+        let snippet_directory = snippets_directory.join("main").join("snippet");
+
+        std::fs::create_dir_all(&snippet_directory).unwrap();
+        std::fs::write(snippet_directory.join("app.py"), "").unwrap();
+
+        let previous_fingerprint = fingerprint_snippet_directory(&snippets_directory);
+
+        // files written while loading the snippets are not changes
+        std::fs::write(snippet_directory.join("__init__.py"), "").unwrap();
+        std::fs::create_dir_all(snippet_directory.join("__pycache__")).unwrap();
+        std::fs::write(snippet_directory.join("__pycache__").join("app.pyc"), "").unwrap();
+
+        std::fs::write(snippet_directory.join("helper.py"), "").unwrap();
+        std::fs::remove_file(snippet_directory.join("app.py")).unwrap();
+
+        let fingerprint = fingerprint_snippet_directory(&snippets_directory);
+        let mut changed_paths = find_changed_paths(&previous_fingerprint, &fingerprint);
+        changed_paths.sort();
+
+        assert_eq!(
+            changed_paths,
+            vec![
+                snippet_directory.join("app.py"),
+                snippet_directory.join("helper.py")
+            ]
+        );
+
+        std::fs::remove_dir_all(snippets_directory).unwrap();
+    }
+
+    #[test]
+    fn test_carry_over_uuids() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let snippets_directory = std::env::temp_dir().join(format!(
+            "directory_manager_carry_over_test_{}",
+            std::process::id()
+        ));
+        let root_directory = snippets_directory.join("root");
+        let main_directory = root_directory.join("main");
+
+        for name in ["kept", "changed_kind", "removed"] {
+            std::fs::create_dir_all(main_directory.join(name)).unwrap();
+            std::fs::write(main_directory.join(name).join("app.py"), "").unwrap();
+        }
+
+        let relative_snippet_directory = root_directory.to_string_lossy().to_string();
+
+        let mut previous_snippet_directory = SnippetDirectory::default();
+        previous_snippet_directory
+            .initialize(&relative_snippet_directory, &mut sequential_id_generator)
+            .unwrap();
+
+        // a snippet turned into a category, one removed and one added
+        let changed_kind_directory = main_directory.join("changed_kind");
+        std::fs::remove_file(changed_kind_directory.join("app.py")).unwrap();
+        std::fs::create_dir_all(changed_kind_directory.join("inner")).unwrap();
+        std::fs::write(changed_kind_directory.join("inner").join("app.py"), "").unwrap();
+        std::fs::remove_dir_all(main_directory.join("removed")).unwrap();
+        std::fs::create_dir_all(main_directory.join("added")).unwrap();
+        std::fs::write(main_directory.join("added").join("app.py"), "").unwrap();
+
+        let mut snippet_directory = SnippetDirectory::default();
+        snippet_directory
+            .initialize(&relative_snippet_directory, &mut sequential_id_generator)
+            .unwrap();
+
+        // (package path, is snippet) -> uuid, before and after carrying over
+        let mut previous_uuids = HashMap::<(PackagePath, bool), Uuid>::new();
+        previous_snippet_directory
+            .root
+            .as_ref()
+            .unwrap()
+            .collect_uuids(PackagePath::default(), &mut previous_uuids);
+
+        let mut scanned_uuids = HashMap::<(PackagePath, bool), Uuid>::new();
+        snippet_directory
+            .root
+            .as_ref()
+            .unwrap()
+            .collect_uuids(PackagePath::default(), &mut scanned_uuids);
+
+        snippet_directory.carry_over_uuids(&previous_snippet_directory);
+
+        let mut uuids = HashMap::<(PackagePath, bool), Uuid>::new();
+        snippet_directory
+            .root
+            .as_ref()
+            .unwrap()
+            .collect_uuids(PackagePath::default(), &mut uuids);
+
+        let key = |path: &str, snippet: bool| (PackagePath::from(path.to_string()), snippet);
+
+        // entries still there keep their uuids, categories included
+        for (path, snippet) in [("", false), ("main", false), ("main.kept", true)] {
+            assert!(previous_uuids.contains_key(&key(path, snippet)));
+            assert_eq!(
+                uuids.get(&key(path, snippet)),
+                previous_uuids.get(&key(path, snippet))
+            );
+        }
+
+        // a snippet that became a category is a different entry
+        assert_eq!(
+            uuids.get(&key("main.changed_kind", false)),
+            scanned_uuids.get(&key("main.changed_kind", false))
+        );
+        assert_ne!(
+            uuids.get(&key("main.changed_kind", false)),
+            previous_uuids.get(&key("main.changed_kind", true))
+        );
+
+        // new entries keep the uuids they were scanned with
+        for (path, snippet) in [("main.added", true), ("main.changed_kind.inner", true)] {
+            assert!(scanned_uuids.contains_key(&key(path, snippet)));
+            assert_eq!(
+                uuids.get(&key(path, snippet)),
+                scanned_uuids.get(&key(path, snippet))
+            );
+        }
+
+        assert!(!uuids.contains_key(&key("main.removed", true)));
+
+        std::fs::remove_dir_all(snippets_directory).unwrap();
+    }
+
+    #[test]
+    fn test_reload_external_snippets_from_directory() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let snippets_directory = std::env::temp_dir().join(format!(
+            "directory_manager_reload_test_{}",
+            std::process::id()
+        ));
+        let root_directory = snippets_directory.join("root");
+        let app_file = root_directory.join("main").join("add").join("app.py");
+
+        std::fs::create_dir_all(app_file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &app_file,
+            "def init(*args, **kwargs):\n    snippet = args[0]\n    snippet.add_input(\"a\")\n    return snippet\n",
+        )
+        .unwrap();
+
+        let relative_snippet_directory = root_directory.to_string_lossy().to_string();
+
+        let mut directory_manager = DirectoryManager::default();
+        directory_manager
+            .initialize(&relative_snippet_directory, &mut sequential_id_generator)
+            .unwrap();
+
+        let mut external_snippet_manager = ExternalSnippetManager::default();
+        external_snippet_manager
+            .create_external_snippets_from_directory(
+                &directory_manager,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let package_path = PackagePath::from("main.add".to_string());
+        let external_snippet_uuid = external_snippet_manager
+            .find_external_snippet_from_package_path(&package_path)
+            .unwrap()
+            .get_uuid();
+
+        // the snippet gets a second input
+        std::fs::write(
+            &app_file,
+            "def init(*args, **kwargs):\n    snippet = args[0]\n    snippet.add_input(\"a\")\n    snippet.add_input(\"b\")\n    return snippet\n",
+        )
+        .unwrap();
+
+        directory_manager
+            .initialize(&relative_snippet_directory, &mut sequential_id_generator)
+            .unwrap();
+        let external_snippet_reload = external_snippet_manager
+            .reload_external_snippets_from_directory(
+                &directory_manager,
+                &vec![app_file.to_owned()],
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        // only the changed snippet is built again, and it keeps its uuid
+        assert_eq!(
+            external_snippet_reload.get_reloaded(),
+            &vec![external_snippet_uuid]
+        );
+        assert!(external_snippet_reload.get_removed().is_empty());
+        assert!(external_snippet_reload
+            .get_initialization_errors()
+            .is_empty());
+
+        let mut connector_names: Vec<String> = external_snippet_manager
+            .find_external_snippet(external_snippet_uuid)
+            .unwrap()
+            .create_pipeline_connectors_for_io_points(&mut sequential_id_generator)
+            .iter()
+            .map(|pipeline_connector| pipeline_connector.get_name())
+            .collect();
+        connector_names.sort();
+        assert_eq!(connector_names, vec!["a".to_string(), "b".to_string()]);
+
+        // the snippet breaks
+        std::fs::write(
+            &app_file,
+            "def init(*args, **kwargs):\n    raise ValueError(\"broken snippet\")\n",
+        )
+        .unwrap();
+
+        directory_manager
+            .initialize(&relative_snippet_directory, &mut sequential_id_generator)
+            .unwrap();
+        let external_snippet_reload = external_snippet_manager
+            .reload_external_snippets_from_directory(
+                &directory_manager,
+                &vec![app_file.to_owned()],
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        // it is removed, with why
+        assert!(external_snippet_reload.get_reloaded().is_empty());
+        assert!(
+            external_snippet_reload.get_removed()
+                == &vec![(external_snippet_uuid, package_path.to_owned())]
+        );
+        assert_eq!(external_snippet_reload.get_initialization_errors().len(), 1);
+        assert!(external_snippet_manager
+            .find_external_snippet(external_snippet_uuid)
+            .is_none());
+        assert!(external_snippet_manager
+            .find_external_snippet_from_package_path(&package_path)
+            .is_none());
+
+        // a reload that does not touch it keeps the error without reporting it again
+        directory_manager
+            .initialize(&relative_snippet_directory, &mut sequential_id_generator)
+            .unwrap();
+        let external_snippet_reload = external_snippet_manager
+            .reload_external_snippets_from_directory(
+                &directory_manager,
+                &Vec::new(),
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        assert!(external_snippet_reload.get_reloaded().is_empty());
+        assert!(external_snippet_reload.get_removed().is_empty());
+        assert!(external_snippet_reload
+            .get_initialization_errors()
+            .is_empty());
+        assert_eq!(
+            external_snippet_manager.get_initialization_errors().len(),
+            1
+        );
+
+        std::fs::remove_dir_all(snippets_directory).unwrap();
+    }
 }
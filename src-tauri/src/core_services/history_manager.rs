@@ -0,0 +1,1159 @@
+use crate::{
+    core_components::snippet_manager::{PipelineComponent, SnippetComponent, SnippetManager},
+    state_management::visual_snippet_component_manager::{
+        FrontGraphChanges, FrontMovedSnippetContent, FrontRemovedPipelineContent,
+        FrontUpdatedParameterContent, VisualSnippetComponentManager,
+    },
+    utils::sequential_id_generator::{SequentialIdGenerator, Uuid},
+};
+
+/// max number of operations that can be undone
+const HISTORY_LIMIT: usize = 100;
+
+/// undo and redo stacks of the graph edits of a project
+pub struct HistoryManager {
+    undo_stack: Vec<HistoryOperation>,
+    redo_stack: Vec<HistoryOperation>,
+}
+
+/// reversible edit of the snippet graph
+/// snippets and pipelines are kept as copies, so they can be restored with the same uuids
+pub enum HistoryOperation {
+    CreateSnippet {
+        snippet: SnippetComponent,
+    },
+    DeleteSnippet {
+        snippet: SnippetComponent,
+        // pipelines that were connected to the snippet
+        pipelines: Vec<PipelineComponent>,
+    },
+    // pasted or duplicated snippets, with the pipelines between them
+    CreateSnippets {
+        snippets: Vec<SnippetComponent>,
+        pipelines: Vec<PipelineComponent>,
+    },
+    // snippets replaced by the composite snippet created from them
+    CollapseSnippets {
+        snippets: Vec<SnippetComponent>,
+        // pipelines within the snippets, and crossing into them
+        pipelines: Vec<PipelineComponent>,
+        composite_snippet: SnippetComponent,
+        // pipelines crossing into the snippets, reconnected to the composite snippet
+        composite_pipelines: Vec<PipelineComponent>,
+    },
+    CreatePipeline {
+        pipeline: PipelineComponent,
+    },
+    DeletePipeline {
+        pipeline: PipelineComponent,
+    },
+    UpdateParameterValue {
+        parameter_uuid: Uuid,
+        old_value: String,
+        new_value: String,
+    },
+    MoveSnippet {
+        snippet_uuid: Uuid,
+        old_position: (f64, f64),
+        new_position: (f64, f64),
+    },
+}
+
+impl Default for HistoryManager {
+    fn default() -> Self {
+        return HistoryManager {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+        };
+    }
+}
+
+impl HistoryManager {
+    /// record an operation that was just applied
+    /// clears the redo stack, as the operations in it no longer apply
+    ///
+    /// # Arguments
+    /// * 'operation' - operation that was applied
+    pub fn record(&mut self, operation: HistoryOperation) {
+        self.redo_stack.clear();
+
+        // consecutive edits of the same parameter are undone as one
+        if let HistoryOperation::UpdateParameterValue {
+            parameter_uuid,
+            new_value,
+            ..
+        } = &operation
+        {
+            if let Some(HistoryOperation::UpdateParameterValue {
+                parameter_uuid: last_parameter_uuid,
+                new_value: last_new_value,
+                ..
+            }) = self.undo_stack.last_mut()
+            {
+                if last_parameter_uuid == parameter_uuid {
+                    *last_new_value = new_value.to_owned();
+                    return;
+                }
+            }
+        }
+
+        self.undo_stack.push(operation);
+
+        // drop the oldest operations
+        if self.undo_stack.len() > HISTORY_LIMIT {
+            let overflow = self.undo_stack.len() - HISTORY_LIMIT;
+            self.undo_stack.drain(0..overflow);
+        }
+    }
+
+    /// revert the last applied operation
+    /// returns the changes the front end needs to apply
+    pub fn undo(
+        &mut self,
+        snippet_manager: &mut SnippetManager,
+        visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<FrontGraphChanges, &'static str> {
+        let operation = match self.undo_stack.pop() {
+            Some(operation) => operation,
+            None => {
+                return Err("nothing to undo");
+            }
+        };
+
+        let front_graph_changes = match revert_operation(
+            &operation,
+            snippet_manager,
+            visual_snippet_component_manager,
+            sequential_id_generator,
+        ) {
+            Ok(result) => result,
+            Err(e) => {
+                // the graph was left unchanged, so the history still matches it
+                self.undo_stack.push(operation);
+                return Err(e);
+            }
+        };
+
+        self.redo_stack.push(operation);
+
+        return Ok(front_graph_changes);
+    }
+
+    /// reapply the last undone operation
+    /// returns the changes the front end needs to apply
+    pub fn redo(
+        &mut self,
+        snippet_manager: &mut SnippetManager,
+        visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<FrontGraphChanges, &'static str> {
+        let operation = match self.redo_stack.pop() {
+            Some(operation) => operation,
+            None => {
+                return Err("nothing to redo");
+            }
+        };
+
+        let front_graph_changes = match apply_operation(
+            &operation,
+            snippet_manager,
+            visual_snippet_component_manager,
+            sequential_id_generator,
+        ) {
+            Ok(result) => result,
+            Err(e) => {
+                // the graph was left unchanged, so the history still matches it
+                self.redo_stack.push(operation);
+                return Err(e);
+            }
+        };
+
+        self.undo_stack.push(operation);
+
+        return Ok(front_graph_changes);
+    }
+
+    /// forget all operations
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+/// apply operation to the snippet graph, as a whole or not at all
+/// returns the changes the front end needs to apply
+pub fn apply_operation(
+    operation: &HistoryOperation,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+) -> Result<FrontGraphChanges, &'static str> {
+    // apply to copies of the managers, so a failing step does not leave the graph half changed
+    let mut changed_snippet_manager = snippet_manager.clone();
+    let mut changed_visual_snippet_component_manager = visual_snippet_component_manager.clone();
+
+    let front_graph_changes = apply_operation_steps(
+        operation,
+        &mut changed_snippet_manager,
+        &mut changed_visual_snippet_component_manager,
+        sequential_id_generator,
+    )?;
+
+    *snippet_manager = changed_snippet_manager;
+    *visual_snippet_component_manager = changed_visual_snippet_component_manager;
+
+    return Ok(front_graph_changes);
+}
+
+/// apply each step of the operation to the snippet graph, stopping at the first failing step
+fn apply_operation_steps(
+    operation: &HistoryOperation,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+) -> Result<FrontGraphChanges, &'static str> {
+    let mut front_graph_changes = FrontGraphChanges::default();
+
+    match operation {
+        HistoryOperation::CreateSnippet { snippet } => {
+            add_snippet(
+                snippet,
+                snippet_manager,
+                visual_snippet_component_manager,
+                sequential_id_generator,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::DeleteSnippet { snippet, pipelines } => {
+            // pipelines go first, as they need the snippet's pipeline connectors
+            for pipeline in pipelines.iter() {
+                remove_pipeline(
+                    &pipeline.get_uuid(),
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    &mut front_graph_changes,
+                )?;
+            }
+
+            remove_snippet(
+                &snippet.get_uuid(),
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::CreateSnippets {
+            snippets,
+            pipelines,
+        } => {
+            // snippets go first, as the pipelines need their pipeline connectors
+            for snippet in snippets.iter() {
+                add_snippet(
+                    snippet,
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    sequential_id_generator,
+                    &mut front_graph_changes,
+                )?;
+            }
+
+            for pipeline in pipelines.iter() {
+                add_pipeline(
+                    pipeline,
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    sequential_id_generator,
+                    &mut front_graph_changes,
+                )?;
+            }
+        }
+        HistoryOperation::CollapseSnippets {
+            snippets,
+            pipelines,
+            composite_snippet,
+            composite_pipelines,
+        } => {
+            // pipelines go first, as they need the snippets' pipeline connectors
+            for pipeline in pipelines.iter() {
+                remove_pipeline(
+                    &pipeline.get_uuid(),
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    &mut front_graph_changes,
+                )?;
+            }
+
+            for snippet in snippets.iter() {
+                remove_snippet(
+                    &snippet.get_uuid(),
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    &mut front_graph_changes,
+                )?;
+            }
+
+            add_snippet(
+                composite_snippet,
+                snippet_manager,
+                visual_snippet_component_manager,
+                sequential_id_generator,
+                &mut front_graph_changes,
+            )?;
+
+            for pipeline in composite_pipelines.iter() {
+                add_pipeline(
+                    pipeline,
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    sequential_id_generator,
+                    &mut front_graph_changes,
+                )?;
+            }
+        }
+        HistoryOperation::CreatePipeline { pipeline } => {
+            add_pipeline(
+                pipeline,
+                snippet_manager,
+                visual_snippet_component_manager,
+                sequential_id_generator,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::DeletePipeline { pipeline } => {
+            remove_pipeline(
+                &pipeline.get_uuid(),
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::UpdateParameterValue {
+            parameter_uuid,
+            new_value,
+            ..
+        } => {
+            set_parameter_value(
+                parameter_uuid,
+                new_value,
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::MoveSnippet {
+            snippet_uuid,
+            new_position,
+            ..
+        } => {
+            move_snippet(
+                snippet_uuid,
+                *new_position,
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+        }
+    };
+
+    return Ok(front_graph_changes);
+}
+
+/// revert operation on the snippet graph, as a whole or not at all
+/// returns the changes the front end needs to apply
+pub fn revert_operation(
+    operation: &HistoryOperation,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+) -> Result<FrontGraphChanges, &'static str> {
+    // revert to copies of the managers, so a failing step does not leave the graph half changed
+    let mut changed_snippet_manager = snippet_manager.clone();
+    let mut changed_visual_snippet_component_manager = visual_snippet_component_manager.clone();
+
+    let front_graph_changes = revert_operation_steps(
+        operation,
+        &mut changed_snippet_manager,
+        &mut changed_visual_snippet_component_manager,
+        sequential_id_generator,
+    )?;
+
+    *snippet_manager = changed_snippet_manager;
+    *visual_snippet_component_manager = changed_visual_snippet_component_manager;
+
+    return Ok(front_graph_changes);
+}
+
+/// revert each step of the operation to the snippet graph, stopping at the first failing step
+fn revert_operation_steps(
+    operation: &HistoryOperation,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+) -> Result<FrontGraphChanges, &'static str> {
+    let mut front_graph_changes = FrontGraphChanges::default();
+
+    match operation {
+        HistoryOperation::CreateSnippet { snippet } => {
+            remove_snippet(
+                &snippet.get_uuid(),
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::DeleteSnippet { snippet, pipelines } => {
+            // snippet goes first, as the pipelines need its pipeline connectors
+            add_snippet(
+                snippet,
+                snippet_manager,
+                visual_snippet_component_manager,
+                sequential_id_generator,
+                &mut front_graph_changes,
+            )?;
+
+            for pipeline in pipelines.iter() {
+                add_pipeline(
+                    pipeline,
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    sequential_id_generator,
+                    &mut front_graph_changes,
+                )?;
+            }
+        }
+        HistoryOperation::CreateSnippets {
+            snippets,
+            pipelines,
+        } => {
+            // pipelines go first, as they need the snippets' pipeline connectors
+            for pipeline in pipelines.iter() {
+                remove_pipeline(
+                    &pipeline.get_uuid(),
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    &mut front_graph_changes,
+                )?;
+            }
+
+            for snippet in snippets.iter() {
+                remove_snippet(
+                    &snippet.get_uuid(),
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    &mut front_graph_changes,
+                )?;
+            }
+        }
+        HistoryOperation::CollapseSnippets {
+            snippets,
+            pipelines,
+            composite_snippet,
+            composite_pipelines,
+        } => {
+            // pipelines go first, as they need the composite snippet's pipeline connectors
+            for pipeline in composite_pipelines.iter() {
+                remove_pipeline(
+                    &pipeline.get_uuid(),
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    &mut front_graph_changes,
+                )?;
+            }
+
+            remove_snippet(
+                &composite_snippet.get_uuid(),
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+
+            for snippet in snippets.iter() {
+                add_snippet(
+                    snippet,
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    sequential_id_generator,
+                    &mut front_graph_changes,
+                )?;
+            }
+
+            for pipeline in pipelines.iter() {
+                add_pipeline(
+                    pipeline,
+                    snippet_manager,
+                    visual_snippet_component_manager,
+                    sequential_id_generator,
+                    &mut front_graph_changes,
+                )?;
+            }
+        }
+        HistoryOperation::CreatePipeline { pipeline } => {
+            remove_pipeline(
+                &pipeline.get_uuid(),
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::DeletePipeline { pipeline } => {
+            add_pipeline(
+                pipeline,
+                snippet_manager,
+                visual_snippet_component_manager,
+                sequential_id_generator,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::UpdateParameterValue {
+            parameter_uuid,
+            old_value,
+            ..
+        } => {
+            set_parameter_value(
+                parameter_uuid,
+                old_value,
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+        }
+        HistoryOperation::MoveSnippet {
+            snippet_uuid,
+            old_position,
+            ..
+        } => {
+            move_snippet(
+                snippet_uuid,
+                *old_position,
+                snippet_manager,
+                visual_snippet_component_manager,
+                &mut front_graph_changes,
+            )?;
+        }
+    };
+
+    return Ok(front_graph_changes);
+}
+
+/// restore snippet and add it to the front
+fn add_snippet(
+    snippet: &SnippetComponent,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+    front_graph_changes: &mut FrontGraphChanges,
+) -> Result<(), &'static str> {
+    let snippet_uuid = snippet_manager.restore_snippet(snippet.clone())?;
+
+    // can safely unwrap as we just restored the snippet above
+    let snippet = snippet_manager.find_snippet(&snippet_uuid).unwrap();
+
+    let front_snippet = snippet.get_snippet_to_front_snippet(
+        visual_snippet_component_manager,
+        sequential_id_generator,
+        &snippet_manager,
+    );
+
+    front_graph_changes.added_snippets.push(front_snippet);
+
+    return Ok(());
+}
+
+/// delete snippet and remove it from the front
+/// assumes no pipelines are connected to it
+fn remove_snippet(
+    snippet_uuid: &Uuid,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    front_graph_changes: &mut FrontGraphChanges,
+) -> Result<(), &'static str> {
+    let snippet = match snippet_manager.find_snippet(snippet_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("could not find snippet component from snippet uuid");
+        }
+    };
+
+    let snippet_front_uuid =
+        match visual_snippet_component_manager.find_snippet_front_uuid(snippet_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("could not find front snippet uuid from snippet uuid");
+            }
+        };
+
+    //delete pipeline connectors and parameters from front service
+    for pipeline_connector_uuid in snippet.get_pipeline_connector_uuids().iter() {
+        visual_snippet_component_manager
+            .delete_pipeline_connector_by_internal(pipeline_connector_uuid)?;
+    }
+
+    for parameter in snippet.get_parameters_as_copy().iter() {
+        visual_snippet_component_manager.delete_parameter_by_internal(&parameter.get_uuid())?;
+    }
+
+    visual_snippet_component_manager.delete_snippet_by_internal(snippet_uuid)?;
+
+    snippet_manager.delete_snippet(snippet_uuid)?;
+
+    front_graph_changes
+        .removed_snippets
+        .push(snippet_front_uuid);
+
+    return Ok(());
+}
+
+/// restore pipeline and add it to the front
+fn add_pipeline(
+    pipeline: &PipelineComponent,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+    front_graph_changes: &mut FrontGraphChanges,
+) -> Result<(), &'static str> {
+    let pipeline_uuid = snippet_manager.restore_pipeline(sequential_id_generator, pipeline)?;
+
+    // can safely unwrap as we just restored the pipeline above
+    let pipeline = snippet_manager.find_pipeline(&pipeline_uuid).unwrap();
+
+    let front_pipeline = pipeline.create_pipeline_as_front_content(
+        visual_snippet_component_manager,
+        sequential_id_generator,
+    )?;
+
+    front_graph_changes.added_pipelines.push(front_pipeline);
+
+    return Ok(());
+}
+
+/// delete pipeline and remove it from the front
+fn remove_pipeline(
+    pipeline_uuid: &Uuid,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    front_graph_changes: &mut FrontGraphChanges,
+) -> Result<(), &'static str> {
+    let pipeline = match snippet_manager.find_pipeline(pipeline_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("could not find pipeline from pipeline uuid");
+        }
+    };
+
+    let pipeline_front_uuid =
+        match visual_snippet_component_manager.find_pipeline_front_uuid(pipeline_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("could not find front pipeline uuid from pipeline uuid");
+            }
+        };
+
+    let from_front_uuid = match visual_snippet_component_manager
+        .find_pipeline_connector_front_uuid(&pipeline.get_from_pipeline_connector_uuid())
+    {
+        Some(result) => result,
+        None => {
+            return Err("could not find front pipeline connector uuid of from pipeline connector");
+        }
+    };
+
+    let to_front_uuid = match visual_snippet_component_manager
+        .find_pipeline_connector_front_uuid(&pipeline.get_to_pipeline_connector_uuid())
+    {
+        Some(result) => result,
+        None => {
+            return Err("could not find front pipeline connector uuid of to pipeline connector");
+        }
+    };
+
+    visual_snippet_component_manager.delete_pipeline_by_pipeline_front(&pipeline_front_uuid)?;
+
+    snippet_manager.delete_pipeline(pipeline_uuid)?;
+
+    front_graph_changes
+        .removed_pipelines
+        .push(FrontRemovedPipelineContent::new(
+            pipeline_front_uuid,
+            from_front_uuid,
+            to_front_uuid,
+        ));
+
+    return Ok(());
+}
+
+/// set the value of a parameter
+fn set_parameter_value(
+    parameter_uuid: &Uuid,
+    value: &String,
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    front_graph_changes: &mut FrontGraphChanges,
+) -> Result<(), &'static str> {
+    let parameter_front_uuid =
+        match visual_snippet_component_manager.find_parameter_front_uuid(parameter_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("could not find front parameter uuid from parameter uuid");
+            }
+        };
+
+    let parameter = match snippet_manager.find_parameter(parameter_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("could not find parameter in snippet manager");
+        }
+    };
+
+    // the value was already set on the parameter before
+    if let Err(_) = parameter.update_value(value.to_owned()) {
+        return Err("could not set value of parameter");
+    }
+
+    front_graph_changes
+        .updated_parameters
+        .push(FrontUpdatedParameterContent::new(
+            parameter_front_uuid,
+            parameter.get_front_value(),
+        ));
+
+    return Ok(());
+}
+
+/// set the position of a snippet
+fn move_snippet(
+    snippet_uuid: &Uuid,
+    position: (f64, f64),
+    snippet_manager: &mut SnippetManager,
+    visual_snippet_component_manager: &mut VisualSnippetComponentManager,
+    front_graph_changes: &mut FrontGraphChanges,
+) -> Result<(), &'static str> {
+    let snippet_front_uuid =
+        match visual_snippet_component_manager.find_snippet_front_uuid(snippet_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("could not find front snippet uuid from snippet uuid");
+            }
+        };
+
+    match snippet_manager.update_snippet_position(*snippet_uuid, position.0, position.1) {
+        Ok(_) => (),
+        Err(e) => {
+            println!("Could not update snippet positions: {}", e);
+            return Err("could not update snippet positions");
+        }
+    };
+
+    front_graph_changes
+        .moved_snippets
+        .push(FrontMovedSnippetContent::new(
+            snippet_front_uuid,
+            position.0,
+            position.1,
+        ));
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core_components::snippet_manager::{PipelineComponent, SnippetComponent, SnippetManager},
+        state_management::{
+            external_snippet_manager::{
+                ExternalSnippetManager, PackagePath, BUILTIN_CONSTANT_VALUE, BUILTIN_REROUTE_INPUT,
+                BUILTIN_REROUTE_OUTPUT,
+            },
+            test_support::{add_constant_into_reroute, create_builtin_external_snippet_manager},
+            visual_snippet_component_manager::VisualSnippetComponentManager,
+        },
+        utils::sequential_id_generator::{SequentialIdGenerator, Uuid},
+    };
+
+    use super::{apply_operation, HistoryManager, HistoryOperation};
+
+    /// snippet graph of a project, with the builtin snippets to build it from
+    struct TestGraph {
+        external_snippet_manager: ExternalSnippetManager,
+        snippet_manager: SnippetManager,
+        visual_snippet_component_manager: VisualSnippetComponentManager,
+        history_manager: HistoryManager,
+        sequential_id_generator: SequentialIdGenerator,
+    }
+
+    impl TestGraph {
+        fn new() -> Self {
+            let mut sequential_id_generator = SequentialIdGenerator::default();
+            let external_snippet_manager =
+                create_builtin_external_snippet_manager(&mut sequential_id_generator);
+
+            return TestGraph {
+                external_snippet_manager: external_snippet_manager,
+                snippet_manager: SnippetManager::default(),
+                visual_snippet_component_manager: VisualSnippetComponentManager::default(),
+                history_manager: HistoryManager::default(),
+                sequential_id_generator: sequential_id_generator,
+            };
+        }
+
+        /// create a copy of a new snippet of the builtin snippet, which is not in the graph
+        fn new_snippet_copy(&mut self, package_path: &str, x_position: f64) -> SnippetComponent {
+            let external_snippet = self
+                .external_snippet_manager
+                .find_external_snippet_from_package_path(&PackagePath::from(
+                    package_path.to_string(),
+                ))
+                .unwrap();
+
+            let mut snippet_manager = SnippetManager::default();
+            let snippet_uuid = snippet_manager.new_snippet(
+                &mut self.sequential_id_generator,
+                external_snippet,
+                x_position,
+                0.0,
+            );
+
+            return snippet_manager.find_snippet(&snippet_uuid).unwrap().clone();
+        }
+
+        /// create a copy of a new pipeline from the output to the input of the snippets,
+        /// which is not in the graph
+        fn new_pipeline_copy(
+            &mut self,
+            from_snippet: &SnippetComponent,
+            from_connector_name: &str,
+            to_snippet: &SnippetComponent,
+        ) -> PipelineComponent {
+            let mut snippet_manager = SnippetManager::default();
+            snippet_manager
+                .restore_snippet(from_snippet.clone())
+                .unwrap();
+            snippet_manager.restore_snippet(to_snippet.clone()).unwrap();
+
+            let pipeline_uuid = snippet_manager
+                .create_pipeline(
+                    &mut self.sequential_id_generator,
+                    find_pipeline_connector_uuid(from_snippet, from_connector_name, false),
+                    find_pipeline_connector_uuid(to_snippet, BUILTIN_REROUTE_INPUT, true),
+                )
+                .unwrap();
+
+            return snippet_manager
+                .find_pipeline(&pipeline_uuid)
+                .unwrap()
+                .clone();
+        }
+
+        /// create a constant snippet piped into a reroute snippet in the graph
+        ///
+        /// returns the (constant snippet, reroute snippet, pipeline) as created
+        fn create_constant_into_reroute(
+            &mut self,
+        ) -> (SnippetComponent, SnippetComponent, PipelineComponent) {
+            let mut snippet_manager = SnippetManager::default();
+            let (constant_uuid, reroute_uuid) = add_constant_into_reroute(
+                &mut snippet_manager,
+                &self.external_snippet_manager,
+                &mut self.sequential_id_generator,
+                "0",
+            );
+
+            let constant_snippet = snippet_manager
+                .find_snippet(&constant_uuid)
+                .unwrap()
+                .clone();
+            let reroute_snippet = snippet_manager.find_snippet(&reroute_uuid).unwrap().clone();
+            let pipeline = snippet_manager.get_pipelines_as_ref()[0].clone();
+
+            self.apply(HistoryOperation::CreateSnippets {
+                snippets: vec![constant_snippet.clone(), reroute_snippet.clone()],
+                pipelines: vec![pipeline.clone()],
+            });
+
+            return (constant_snippet, reroute_snippet, pipeline);
+        }
+
+        /// apply the operation and record it, as the edits of the project do
+        fn apply(&mut self, operation: HistoryOperation) {
+            apply_operation(
+                &operation,
+                &mut self.snippet_manager,
+                &mut self.visual_snippet_component_manager,
+                &mut self.sequential_id_generator,
+            )
+            .unwrap();
+
+            self.history_manager.record(operation);
+        }
+
+        fn undo(&mut self) -> Result<(), &'static str> {
+            return self
+                .history_manager
+                .undo(
+                    &mut self.snippet_manager,
+                    &mut self.visual_snippet_component_manager,
+                    &mut self.sequential_id_generator,
+                )
+                .map(|_| ());
+        }
+
+        fn redo(&mut self) -> Result<(), &'static str> {
+            return self
+                .history_manager
+                .redo(
+                    &mut self.snippet_manager,
+                    &mut self.visual_snippet_component_manager,
+                    &mut self.sequential_id_generator,
+                )
+                .map(|_| ());
+        }
+
+        /// description of the snippets, pipelines and parameter values in the graph,
+        /// and if they are drawn in the front, for comparing graphs
+        fn snapshot(&self) -> Vec<String> {
+            let mut snapshot = Vec::<String>::new();
+
+            for snippet in self.snippet_manager.get_snippets_as_ref() {
+                let parameter_values: Vec<String> = snippet
+                    .get_parameters_as_copy()
+                    .iter()
+                    .map(|parameter| parameter.get_storage().to_string())
+                    .collect();
+
+                snapshot.push(format!(
+                    "snippet {} at {:?} with {:?}, drawn {}",
+                    snippet.get_uuid(),
+                    snippet.get_position(),
+                    parameter_values,
+                    self.visual_snippet_component_manager
+                        .find_snippet_front_uuid(&snippet.get_uuid())
+                        .is_some()
+                ));
+            }
+
+            for pipeline in self.snippet_manager.get_pipelines_as_ref() {
+                snapshot.push(format!(
+                    "pipeline {} from {} to {}, drawn {}",
+                    pipeline.get_uuid(),
+                    pipeline.get_from_pipeline_connector_uuid(),
+                    pipeline.get_to_pipeline_connector_uuid(),
+                    self.visual_snippet_component_manager
+                        .find_pipeline_front_uuid(&pipeline.get_uuid())
+                        .is_some()
+                ));
+            }
+
+            snapshot.sort();
+
+            return snapshot;
+        }
+
+        /// apply the operation, then check undo, redo and undo again give the graph
+        /// before and after the operation
+        fn assert_round_trip(&mut self, operation: HistoryOperation) {
+            let snapshot_before = self.snapshot();
+
+            self.apply(operation);
+
+            let snapshot_after = self.snapshot();
+
+            assert!(snapshot_before != snapshot_after);
+
+            self.undo().unwrap();
+            assert_eq!(self.snapshot(), snapshot_before);
+
+            self.redo().unwrap();
+            assert_eq!(self.snapshot(), snapshot_after);
+
+            self.undo().unwrap();
+            assert_eq!(self.snapshot(), snapshot_before);
+        }
+    }
+
+    fn find_pipeline_connector_uuid(snippet: &SnippetComponent, name: &str, input: bool) -> Uuid {
+        return snippet
+            .find_pipeline_connector_from_name_and_input(name.to_string(), input)
+            .unwrap()
+            .get_uuid();
+    }
+
+    #[test]
+    fn test_create_snippet_round_trip() {
+        let mut test_graph = TestGraph::new();
+        let snippet = test_graph.new_snippet_copy("builtin.reroute", 0.0);
+
+        test_graph.assert_round_trip(HistoryOperation::CreateSnippet { snippet: snippet });
+    }
+
+    #[test]
+    fn test_delete_snippet_round_trip() {
+        let mut test_graph = TestGraph::new();
+        let (_, reroute_snippet, pipeline) = test_graph.create_constant_into_reroute();
+
+        test_graph.assert_round_trip(HistoryOperation::DeleteSnippet {
+            snippet: reroute_snippet,
+            pipelines: vec![pipeline],
+        });
+    }
+
+    #[test]
+    fn test_create_snippets_round_trip() {
+        let mut test_graph = TestGraph::new();
+        let constant_snippet = test_graph.new_snippet_copy("builtin.constant_integer", 0.0);
+        let reroute_snippet = test_graph.new_snippet_copy("builtin.reroute", 100.0);
+        let pipeline = test_graph.new_pipeline_copy(
+            &constant_snippet,
+            BUILTIN_CONSTANT_VALUE,
+            &reroute_snippet,
+        );
+
+        test_graph.assert_round_trip(HistoryOperation::CreateSnippets {
+            snippets: vec![constant_snippet, reroute_snippet],
+            pipelines: vec![pipeline],
+        });
+    }
+
+    #[test]
+    fn test_collapse_snippets_round_trip() {
+        let mut test_graph = TestGraph::new();
+        let (constant_snippet, reroute_snippet, pipeline) =
+            test_graph.create_constant_into_reroute();
+
+        // reroute outside of the collapsed snippets, fed from the collapsed snippets
+        let outside_snippet = test_graph.new_snippet_copy("builtin.reroute", 200.0);
+        let outside_pipeline = test_graph.new_pipeline_copy(
+            &reroute_snippet,
+            BUILTIN_REROUTE_OUTPUT,
+            &outside_snippet,
+        );
+
+        test_graph.apply(HistoryOperation::CreateSnippets {
+            snippets: vec![outside_snippet.clone()],
+            pipelines: vec![outside_pipeline.clone()],
+        });
+
+        // the composite snippet stands in for the collapsed snippets, and feeds the outside snippet
+        let composite_snippet = test_graph.new_snippet_copy("builtin.constant_integer", 0.0);
+        let composite_pipeline = test_graph.new_pipeline_copy(
+            &composite_snippet,
+            BUILTIN_CONSTANT_VALUE,
+            &outside_snippet,
+        );
+
+        test_graph.assert_round_trip(HistoryOperation::CollapseSnippets {
+            snippets: vec![constant_snippet, reroute_snippet],
+            pipelines: vec![pipeline, outside_pipeline],
+            composite_snippet: composite_snippet,
+            composite_pipelines: vec![composite_pipeline],
+        });
+    }
+
+    #[test]
+    fn test_create_pipeline_round_trip() {
+        let mut test_graph = TestGraph::new();
+        let (_, reroute_snippet, _) = test_graph.create_constant_into_reroute();
+
+        let outside_snippet = test_graph.new_snippet_copy("builtin.reroute", 200.0);
+
+        test_graph.apply(HistoryOperation::CreateSnippet {
+            snippet: outside_snippet.clone(),
+        });
+
+        let pipeline = test_graph.new_pipeline_copy(
+            &reroute_snippet,
+            BUILTIN_REROUTE_OUTPUT,
+            &outside_snippet,
+        );
+
+        test_graph.assert_round_trip(HistoryOperation::CreatePipeline { pipeline: pipeline });
+    }
+
+    #[test]
+    fn test_delete_pipeline_round_trip() {
+        let mut test_graph = TestGraph::new();
+        let (_, _, pipeline) = test_graph.create_constant_into_reroute();
+
+        test_graph.assert_round_trip(HistoryOperation::DeletePipeline { pipeline: pipeline });
+    }
+
+    #[test]
+    fn test_update_parameter_value_round_trip() {
+        let mut test_graph = TestGraph::new();
+        let (constant_snippet, _, _) = test_graph.create_constant_into_reroute();
+
+        let parameter_uuid = constant_snippet
+            .find_parameter_from_name(BUILTIN_CONSTANT_VALUE.to_string())
+            .unwrap()
+            .get_uuid();
+
+        test_graph.assert_round_trip(HistoryOperation::UpdateParameterValue {
+            parameter_uuid: parameter_uuid,
+            old_value: "0".to_string(),
+            new_value: "42".to_string(),
+        });
+    }
+
+    #[test]
+    fn test_move_snippet_round_trip() {
+        let mut test_graph = TestGraph::new();
+        let (constant_snippet, _, _) = test_graph.create_constant_into_reroute();
+
+        test_graph.assert_round_trip(HistoryOperation::MoveSnippet {
+            snippet_uuid: constant_snippet.get_uuid(),
+            old_position: (10.0, 20.0),
+            new_position: (50.0, 60.0),
+        });
+    }
+
+    #[test]
+    /// an operation failing part way leaves the graph and the history as they were
+    fn test_failing_operation_step() {
+        let mut test_graph = TestGraph::new();
+        let (constant_snippet, _, _) = test_graph.create_constant_into_reroute();
+
+        let snapshot_before = test_graph.snapshot();
+
+        // the second snippet is already in the graph, after the first was added
+        let new_snippet = test_graph.new_snippet_copy("builtin.reroute", 300.0);
+        let operation = HistoryOperation::CreateSnippets {
+            snippets: vec![new_snippet, constant_snippet.clone()],
+            pipelines: Vec::new(),
+        };
+
+        assert!(apply_operation(
+            &operation,
+            &mut test_graph.snippet_manager,
+            &mut test_graph.visual_snippet_component_manager,
+            &mut test_graph.sequential_id_generator,
+        )
+        .is_err());
+
+        assert_eq!(test_graph.snapshot(), snapshot_before);
+
+        // a move that can no longer be undone, as its snippet is gone
+        let moved_snippet = test_graph.new_snippet_copy("builtin.reroute", 400.0);
+
+        test_graph.apply(HistoryOperation::CreateSnippet {
+            snippet: moved_snippet.clone(),
+        });
+        test_graph.apply(HistoryOperation::MoveSnippet {
+            snippet_uuid: moved_snippet.get_uuid(),
+            old_position: (400.0, 0.0),
+            new_position: (500.0, 0.0),
+        });
+
+        test_graph
+            .snippet_manager
+            .delete_snippet(&moved_snippet.get_uuid())
+            .unwrap();
+
+        let snapshot_before = test_graph.snapshot();
+
+        assert!(test_graph.undo().is_err());
+        assert_eq!(test_graph.snapshot(), snapshot_before);
+
+        // the history is kept
+        assert_eq!(test_graph.history_manager.undo_stack.len(), 3);
+        assert_eq!(test_graph.history_manager.redo_stack.len(), 0);
+    }
+}
@@ -1,53 +1,123 @@
 use std::{
+    collections::HashMap,
     ffi::OsStr,
     io::{Read, Write},
     path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    core_components::snippet_manager::SnippetManager,
+    core_components::snippet_manager::{
+        PipelineComponent, PipelineConnectorComponent, SnippetComponent, SnippetManager,
+    },
     state_management::{
-        external_snippet_manager::{ExternalSnippetManager, PackagePath},
-        visual_snippet_component_manager::VisualSnippetComponentManager,
+        external_snippet_manager::{ExternalSnippet, ExternalSnippetManager, PackagePath},
+        visual_snippet_component_manager::{
+            FrontGraphChanges, FrontPipelineContent, FrontProjectContent, FrontSnippetContent,
+            VisualSnippetComponentManager,
+        },
     },
-    utils::sequential_id_generator::Uuid,
+    utils::sequential_id_generator::{SequentialIdGenerator, Uuid},
 };
 
-use super::concurrent_processes::get_projects_directory;
+use super::{
+    concurrent_processes::{
+        get_logs_directory, get_projects_directory, get_recovery_directory, get_runs_directory,
+    },
+    history_manager::{apply_operation, HistoryManager, HistoryOperation},
+    secret_store_service::{
+        get_project_secret_store_location_from_name, read_secret_store, remove_secret_store,
+        write_secret_store, SecretStore,
+    },
+    variable_profile_service::VariableProfiles,
+};
 
 // project manager
 pub struct ProjectManager {
     pub snippet_manager: SnippetManager,
     pub visual_component_manager: VisualSnippetComponentManager,
+    pub history_manager: HistoryManager,
+    pub variable_profiles: VariableProfiles,
+    // name of the project file, none if the project was never saved or opened
+    project_name: Option<String>,
+    // recovery id used while the project has no name
+    untitled_recovery_id: String,
+    // serialized plan as last saved or opened, and as last autosaved
+    saved_plan: Option<String>,
+    autosaved_plan: Option<String>,
+    // secrets as last saved or opened, which are not part of the plan
+    saved_secrets: SecretStore,
+}
+
+/// project recovered from an autosave, whose changes were never saved
+#[derive(Serialize, Deserialize)]
+pub struct RecoveryProject {
+    recovery_id: String,
+    project_name: Option<String>,
+    // seconds since unix epoch the recovery file was last written
+    modified_at: u64,
 }
 
 impl ProjectManager {
     /// create a new window session
     pub fn new() -> Self {
-        return ProjectManager {
-            snippet_manager: SnippetManager::default(),
-            visual_component_manager: VisualSnippetComponentManager::default(),
-        };
+        return ProjectManager::default();
     }
 }
 
 impl Default for ProjectManager {
     /// create a new window session
     fn default() -> Self {
+        // id from the creation time, so untitled recoveries from previous runs are not overwritten
+        let created_at = match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_nanos(),
+            Err(_) => 0,
+        };
+
         return ProjectManager {
             snippet_manager: SnippetManager::default(),
             visual_component_manager: VisualSnippetComponentManager::default(),
+            history_manager: HistoryManager::default(),
+            variable_profiles: VariableProfiles::default(),
+            project_name: None,
+            untitled_recovery_id: format!("untitled.{}", created_at),
+            // an empty project has nothing to recover
+            saved_plan: serialize_plan(&Plan::default()).ok(),
+            autosaved_plan: None,
+            saved_secrets: SecretStore::default(),
         };
     }
 }
 
-#[derive(Serialize, Deserialize, Default)]
+/// version of the project file format written by this version of the app
+pub const PROJECT_FORMAT_VERSION: u32 = 2;
+
+// field order is the key order in the project file, keep it stable so project files diff cleanly
+#[derive(Serialize, Deserialize)]
 pub struct Plan {
+    format_version: u32,
+    variable_profiles: VariableProfiles,
     actions: PlanActions,
 }
 
+// plan as written by the bincode project format, before the format was versioned
+#[derive(Serialize, Deserialize)]
+struct LegacyBincodePlan {
+    actions: PlanActions,
+}
+
+impl Default for Plan {
+    fn default() -> Self {
+        return Plan {
+            format_version: PROJECT_FORMAT_VERSION,
+            variable_profiles: VariableProfiles::default(),
+            actions: PlanActions::default(),
+        };
+    }
+}
+
 #[derive(Serialize, Deserialize, Default)]
 struct PlanActions {
     build_snippet_actions: Vec<BuildSnippetAction>,
@@ -83,15 +153,286 @@ struct BuildSnippetParameterAction {
     parameter_value: String,
 }
 
+/// reference in a project file that could not be resolved against the snippet directory
+/// a placeholder is created in its place, so nothing from the project file is dropped
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind")]
+pub enum UnresolvedProjectReference {
+    MissingSnippet {
+        package_path: PackagePath,
+    },
+    MissingPipelineConnector {
+        package_path: PackagePath,
+        connector_name: String,
+    },
+    MissingParameter {
+        package_path: PackagePath,
+        parameter_name: String,
+    },
+    // value no longer fits the type of the parameter, the parameter keeps its default value
+    InvalidParameterValue {
+        package_path: PackagePath,
+        parameter_name: String,
+        parameter_value: String,
+    },
+}
+
+/// remapping of a reference in a project file to a different snippet, connector or parameter
+/// connector and parameter remappings are matched against the package path after
+/// snippet remappings are applied
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind")]
+pub enum ProjectReferenceRemapping {
+    SnippetPackagePath {
+        from_package_path: PackagePath,
+        to_package_path: PackagePath,
+    },
+    PipelineConnectorName {
+        package_path: PackagePath,
+        from_connector_name: String,
+        to_connector_name: String,
+    },
+    ParameterName {
+        package_path: PackagePath,
+        from_parameter_name: String,
+        to_parameter_name: String,
+    },
+}
+
+/// snippet built from the plan of other snippets, saved in the snippet directory
+/// the inputs and outputs of the composite snippet are connectors of its inner snippets
+#[derive(Serialize, Deserialize)]
+pub struct CompositeSnippetDefinition {
+    plan: Plan,
+    inputs: Vec<CompositeSnippetConnector>,
+    outputs: Vec<CompositeSnippetConnector>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CompositeSnippetConnector {
+    // name of the connector on the composite snippet
+    name: String,
+    snippet_package_path: PackagePath,
+    snippet_original_uuid: Uuid,
+    connector_name: String,
+}
+
+/// composite snippet expanded into its inner snippets
+pub struct BuiltCompositeSnippet {
+    pub snippet_uuids: Vec<Uuid>,
+    // composite connector name -> pipeline connector uuid of the inner snippet
+    pub input_pipeline_connectors: HashMap<String, Uuid>,
+    pub output_pipeline_connectors: HashMap<String, Uuid>,
+}
+
 impl ProjectManager {
-    /// Save the current project session to a file at the specified project file path
+    /// Save the current project session to the project file of the project name
+    /// removing any recovery file of the project
     pub fn save_project(
         &mut self,
-        external_snippet_manager: &mut ExternalSnippetManager,
-        path: PathBuf,
+        external_snippet_manager: &ExternalSnippetManager,
+        project_name: String,
+    ) -> Result<(), String> {
+        let plan = self.create_plan(external_snippet_manager)?;
+        let secret_store = self.create_secret_store(external_snippet_manager)?;
+
+        let serialized_plan = serialize_plan(&plan)?;
+
+        write_serialized_plan_to_file(
+            &serialized_plan,
+            get_project_directory_location_from_name(project_name.to_owned()),
+        )?;
+
+        // secrets are kept away from the project file
+        write_secret_store(
+            &get_project_secret_store_location_from_name(&project_name),
+            &secret_store,
+        )?;
+
+        // changes are saved, the recovery file of the project is no longer needed
+        remove_recovery_file(&self.get_recovery_id());
+
+        self.project_name = Some(project_name);
+        self.saved_plan = Some(serialized_plan);
+        self.autosaved_plan = None;
+        self.saved_secrets = secret_store;
+
+        // as well as any recovery file of the project name it was saved as
+        remove_recovery_file(&self.get_recovery_id());
+
+        return Ok(());
+    }
+
+    /// Write the current project session to its recovery file, if it changed since it was last
+    /// saved or autosaved
+    pub fn autosave_project(
+        &mut self,
+        external_snippet_manager: &ExternalSnippetManager,
     ) -> Result<(), String> {
+        let plan = self.create_plan(external_snippet_manager)?;
+
+        let serialized_plan = serialize_plan(&plan)?;
+
+        // no unsaved changes, nothing to recover
+        if self.saved_plan.as_ref() == Some(&serialized_plan) {
+            if self.autosaved_plan.is_some() {
+                remove_recovery_file(&self.get_recovery_id());
+                self.autosaved_plan = None;
+            }
+
+            return Ok(());
+        }
+
+        // already autosaved
+        if self.autosaved_plan.as_ref() == Some(&serialized_plan) {
+            return Ok(());
+        }
+
+        write_serialized_plan_to_file(
+            &serialized_plan,
+            get_recovery_file_location(&self.get_recovery_id()),
+        )?;
+
+        self.autosaved_plan = Some(serialized_plan);
+
+        return Ok(());
+    }
+
+    /// get the directory path parameters of the project are relative to, the one of the project file
+    pub fn get_project_directory(&self) -> PathBuf {
+        let project_name = match &self.project_name {
+            Some(some) => some,
+            // untitled projects are not in a directory of their own yet
+            None => {
+                return get_projects_directory();
+            }
+        };
+
+        let project_location = get_project_directory_location_from_name(project_name.to_owned());
+
+        return match project_location.parent() {
+            Some(parent_dir) => parent_dir.to_path_buf(),
+            None => get_projects_directory(),
+        };
+    }
+
+    /// get the directory the run logs of the project are written to
+    pub fn get_logs_directory(&self) -> PathBuf {
+        return match &self.project_name {
+            Some(project_name) => get_project_logs_directory_location_from_name(project_name),
+            // logs of untitled projects are kept together until they are saved
+            None => get_logs_directory().join("untitled"),
+        };
+    }
+
+    /// get the directory the run records of the project are written to
+    pub fn get_runs_directory(&self) -> PathBuf {
+        return match &self.project_name {
+            Some(project_name) => get_project_runs_directory_location_from_name(project_name),
+            // runs of untitled projects are kept together until they are saved
+            None => get_runs_directory().join("untitled"),
+        };
+    }
+
+    /// get the name of the project file, none if the project was never saved or opened
+    pub fn get_project_name(&self) -> Option<String> {
+        return self.project_name.clone();
+    }
+
+    /// if the project changed since it was last saved or opened
+    pub fn has_unsaved_changes(&self, external_snippet_manager: &ExternalSnippetManager) -> bool {
+        let serialized_plan = match self
+            .create_plan(external_snippet_manager)
+            .and_then(|plan| serialize_plan(&plan))
+        {
+            Ok(some) => some,
+            // if it cannot be saved, it is not saved
+            Err(_) => {
+                return true;
+            }
+        };
+
+        if self.saved_plan.as_ref() != Some(&serialized_plan) {
+            return true;
+        }
+
+        return match self.create_secret_store(external_snippet_manager) {
+            Ok(secret_store) => secret_store != self.saved_secrets,
+            Err(_) => true,
+        };
+    }
+
+    /// remove the recovery file of the project, for when its changes are discarded
+    pub fn discard_recovery(&self) {
+        remove_recovery_file(&self.get_recovery_id());
+    }
+
+    /// get the id of the recovery file of this project
+    fn get_recovery_id(&self) -> String {
+        return match &self.project_name {
+            Some(project_name) => format!("project.{}", project_name),
+            None => self.untitled_recovery_id.to_owned(),
+        };
+    }
+
+    /// Create the plan of the current project session
+    fn create_plan(
+        &self,
+        external_snippet_manager: &ExternalSnippetManager,
+    ) -> Result<Plan, String> {
+        let mut plan = self.create_plan_from_snippets(
+            external_snippet_manager,
+            self.snippet_manager.get_snippets_as_ref(),
+        )?;
+
+        plan.variable_profiles = self.variable_profiles.clone();
+
+        return Ok(plan);
+    }
+
+    /// Create the store of the values of the secret parameters of the current project session,
+    /// referencing the parameters the way the plan does
+    fn create_secret_store(
+        &self,
+        external_snippet_manager: &ExternalSnippetManager,
+    ) -> Result<SecretStore, String> {
+        let mut secret_store = SecretStore::default();
+
+        let snippets = self.snippet_manager.get_snippets_as_ref();
+        let local_uuids = create_snippet_local_uuids(&snippets, external_snippet_manager)?;
+
+        for snippet in snippets.iter() {
+            for parameter in snippet.get_parameters_as_copy() {
+                let value = parameter.get_storage().to_string();
+
+                if !parameter.is_secret() || value.is_empty() {
+                    continue;
+                }
+
+                secret_store.add_secret(
+                    find_snippet_package_path(snippet, external_snippet_manager)?,
+                    local_uuids[&snippet.get_uuid()],
+                    parameter.get_name(),
+                    value,
+                );
+            }
+        }
+
+        return Ok(secret_store);
+    }
+
+    /// Create the plan of the snippets, with only the pipelines between them
+    fn create_plan_from_snippets(
+        &self,
+        external_snippet_manager: &ExternalSnippetManager,
+        snippets: Vec<&SnippetComponent>,
+    ) -> Result<Plan, String> {
         // get components
-        let snippet_manager = &mut self.snippet_manager;
+        let snippet_manager = &self.snippet_manager;
+
+        let snippet_uuids: Vec<Uuid> = snippets.iter().map(|snippet| snippet.get_uuid()).collect();
+        // the uuids of the snippets in the plan, which do not change with the uuids of this session
+        let local_uuids = create_snippet_local_uuids(&snippets, external_snippet_manager)?;
 
         // create plan
         let mut plan = Plan::default();
@@ -99,19 +440,9 @@ impl ProjectManager {
 
         // build snippets plan
         // build snippet actions
-        for snippet in snippet_manager.get_snippets_as_ref() {
-            // find it in external snippet manager
-            let external_snippet = match external_snippet_manager
-                .find_external_snippet(snippet.get_external_snippet_id())
-            {
-                None => {
-                    return Err(format!("Could not find snippet in external snippet manager in project build actions step"));
-                }
-                Some(external_snippet) => external_snippet,
-            };
-
+        for snippet in snippets.iter() {
             // get python path
-            let package_path = external_snippet.get_package_path();
+            let package_path = find_snippet_package_path(snippet, external_snippet_manager)?;
 
             // get positions
             let position = snippet.get_position();
@@ -121,7 +452,7 @@ impl ProjectManager {
             // create snippet action
             let snippet_action = BuildSnippetAction {
                 package_path: package_path,
-                original_uuid: snippet.get_uuid(),
+                original_uuid: local_uuids[&snippet.get_uuid()],
                 x_position: x_position,
                 y_position: y_position,
             };
@@ -131,7 +462,7 @@ impl ProjectManager {
         }
 
         // for each snippet
-        for snippet in snippet_manager.get_snippets_as_ref() {
+        for snippet in snippets.iter() {
             //  for each snippet connector
             for snippet_connector_uuid in snippet.get_pipeline_connector_uuids() {
                 //   for each pipeline find_pipeline_uuids_from_pipeline_connector
@@ -163,6 +494,11 @@ impl ProjectManager {
                         }
                     };
 
+                    // pipelines leaving the snippets are not part of the plan
+                    if !snippet_uuids.contains(&connecting_snippet_uuid) {
+                        continue;
+                    }
+
                     // get connecting snippet
                     let connecting_snippet =
                         match snippet_manager.find_snippet(&connecting_snippet_uuid) {
@@ -193,33 +529,21 @@ impl ProjectManager {
                         };
 
                     // get package paths
-                    let from_snippet_python_path = match external_snippet_manager
-                        .find_external_snippet(snippet.get_external_snippet_id())
-                    {
-                        None => {
-                            return Err(format!("Could not find snippet in external snippet manager in project build actions step"));
-                        }
-                        Some(external_snippet) => external_snippet.get_package_path(),
-                    };
+                    let from_snippet_python_path =
+                        find_snippet_package_path(snippet, external_snippet_manager)?;
 
-                    let to_snippet_python_path = match external_snippet_manager
-                        .find_external_snippet(connecting_snippet.get_external_snippet_id())
-                    {
-                        None => {
-                            return Err(format!("Could not find snippet in external snippet manager in project build actions step"));
-                        }
-                        Some(external_snippet) => external_snippet.get_package_path(),
-                    };
+                    let to_snippet_python_path =
+                        find_snippet_package_path(connecting_snippet, external_snippet_manager)?;
 
                     // create and add entry
                     plan.actions
                         .build_snippet_pipeline_actions
                         .push(BuildSnippetPipelineAction {
                             from_snippet_package_path: from_snippet_python_path,
-                            from_snippet_original_uuid: snippet.get_uuid(),
+                            from_snippet_original_uuid: local_uuids[&snippet.get_uuid()],
                             from_snippet_connector_name: from_pipeline_connector_name,
                             to_snippet_package_path: to_snippet_python_path,
-                            to_snippet_original_uuid: connecting_snippet.get_uuid(),
+                            to_snippet_original_uuid: local_uuids[&connecting_snippet_uuid],
                             to_snippet_connector_name: to_pipeline_connector_name,
                         });
                 }
@@ -228,152 +552,774 @@ impl ProjectManager {
 
         // add parameter values
         // for each snippet
-        for snippet in snippet_manager.get_snippets_as_ref() {
+        for snippet in snippets.iter() {
             // for each parameter
             for parameter in snippet.get_parameters_as_copy() {
                 let parameter_name = parameter.get_name();
 
-                // get inner value as string
-                let parameter_value = parameter.get_storage().to_string();
+                // get inner value as string, secrets are never written into the plan
+                let parameter_value = match parameter.is_secret() {
+                    true => String::new(),
+                    false => parameter.get_storage().to_string(),
+                };
 
                 // get package path
-                let snippet_package_path = match external_snippet_manager
-                    .find_external_snippet(snippet.get_external_snippet_id())
-                {
-                    None => {
-                        return Err(format!("Could not find snippet in external snippet manager in project build actions step"));
-                    }
-                    Some(external_snippet) => external_snippet.get_package_path(),
-                };
+                let snippet_package_path =
+                    find_snippet_package_path(snippet, external_snippet_manager)?;
 
                 // add entry to plan.build_snippet_parameter_actions
                 plan.actions
                     .build_snippet_parameter_actions
                     .push(BuildSnippetParameterAction {
                         snippet_package_path: snippet_package_path,
-                        snippet_original_uuid: snippet.get_uuid(),
+                        snippet_original_uuid: local_uuids[&snippet.get_uuid()],
                         parameter_name: parameter_name,
                         parameter_value: parameter_value,
                     });
             }
         }
 
-        // serialize plan
-        let serialized_plan = match bincode::serialize(&plan) {
-            Ok(some) => some,
+        // order actions so that saving the same project gives the same file
+        plan.sort_actions();
+
+        return Ok(plan);
+    }
+
+    /// Open the project from the project file of the project name
+    ///
+    /// # Arguments
+    /// * 'remappings' - remappings applied to the references in the project file before building
+    ///
+    /// returns the fully built front project content
+    pub fn open_project(
+        &mut self,
+        external_snippet_manager: &ExternalSnippetManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        project_name: String,
+        remappings: &Vec<ProjectReferenceRemapping>,
+    ) -> Result<FrontProjectContent, String> {
+        // read the plan from the project file
+        let mut plan = read_plan_from_file(get_project_directory_location_from_name(
+            project_name.to_owned(),
+        ))?;
+
+        // the project can still be opened without its secrets
+        match read_secret_store(&get_project_secret_store_location_from_name(&project_name)) {
+            Ok(secret_store) => plan.apply_secrets(&secret_store),
             Err(e) => {
-                return Err(format!("Unable to serialize plan: {}", e));
+                println!("{}", e);
             }
         };
 
-        // create necessary directories for file
-        if let Some(parent_dir) = path.parent() {
-            match std::fs::create_dir_all(parent_dir) {
-                Ok(()) => (),
-                Err(e) => {
-                    return Err(format!(
-                        "Unable to create necessary directories for project file {}: {}",
-                        path.to_string_lossy(),
-                        e
-                    ));
-                }
-            }
-        } else {
-            return Err("Invalid project file path".to_string());
+        let front_project_content = self.build_project_from_plan(
+            external_snippet_manager,
+            sequential_id_generator,
+            plan,
+            remappings,
+        )?;
+
+        // the project that was replaced was left on purpose, its changes do not need to be recovered
+        let replaced_recovery_id = self.get_recovery_id();
+
+        // the opened project is the saved state of the project
+        self.project_name = Some(project_name);
+        self.saved_plan = Some(serialize_plan(
+            &self.create_plan(external_snippet_manager)?,
+        )?);
+        self.autosaved_plan = None;
+        self.saved_secrets = self.create_secret_store(external_snippet_manager)?;
+
+        if replaced_recovery_id != self.get_recovery_id() {
+            remove_recovery_file(&replaced_recovery_id);
         }
 
-        // create file, truncate if exists
-        let mut file = match std::fs::File::create(path.to_owned()) {
-            Ok(some) => some,
-            Err(e) => {
-                return Err(format!(
-                    "Unable to create project file at {}: {}",
-                    path.to_string_lossy(),
-                    e
-                ));
-            }
+        return Ok(front_project_content);
+    }
+
+    /// Restore a project from its recovery file, the restored changes are unsaved
+    /// so the recovery file is kept until the project is saved
+    ///
+    /// # Arguments
+    /// * 'recovery_id' - id of the recovery file, as listed by get_recovery_projects
+    /// * 'remappings' - remappings applied to the references in the recovery file before building
+    ///
+    /// returns the fully built front project content
+    pub fn restore_recovery_project(
+        &mut self,
+        external_snippet_manager: &ExternalSnippetManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        recovery_id: String,
+        remappings: &Vec<ProjectReferenceRemapping>,
+    ) -> Result<FrontProjectContent, String> {
+        validate_recovery_id(&recovery_id)?;
+
+        let mut plan = read_plan_from_file(get_recovery_file_location(&recovery_id))?;
+
+        // the recovery file leaves the secrets empty, as the project file does
+        // untitled projects have no secrets store until they are saved
+        let secret_store = match recovery_id.strip_prefix("project.") {
+            Some(project_name) => match read_secret_store(
+                &get_project_secret_store_location_from_name(&project_name.to_string()),
+            ) {
+                Ok(some) => some,
+                // the project can still be restored without its secrets
+                Err(e) => {
+                    println!("{}", e);
+                    SecretStore::default()
+                }
+            },
+            None => SecretStore::default(),
         };
 
-        // export plan
-        match file.write_all(&serialized_plan) {
-            Ok(()) => (),
-            Err(e) => {
-                return Err(format!(
-                    "Unable to write serialized plan to file {}: {}",
-                    path.to_string_lossy(),
-                    e
-                ));
+        plan.apply_secrets(&secret_store);
+
+        let front_project_content = self.build_project_from_plan(
+            external_snippet_manager,
+            sequential_id_generator,
+            plan,
+            remappings,
+        )?;
+
+        // the project that was replaced was left on purpose, its changes do not need to be recovered
+        let replaced_recovery_id = self.get_recovery_id();
+
+        // continue autosaving to the same recovery file
+        match recovery_id.strip_prefix("project.") {
+            Some(project_name) => {
+                self.project_name = Some(project_name.to_string());
+            }
+            None => {
+                self.project_name = None;
+                self.untitled_recovery_id = recovery_id;
             }
         };
 
-        return Ok(());
+        self.saved_plan = None;
+        self.autosaved_plan = None;
+        self.saved_secrets = secret_store;
+
+        if replaced_recovery_id != self.get_recovery_id() {
+            remove_recovery_file(&replaced_recovery_id);
+        }
+
+        return Ok(front_project_content);
     }
 
-    /// Read the project from the project file path
+    /// Copy the snippets, their parameter values and the pipelines between them
+    /// into the clipboard format, which is the project file format of the selection
+    /// so it can be pasted into any project
     ///
-    /// returns the project build information
-    pub fn open_project(&mut self, path: PathBuf) -> Result<Plan, String> {
-        // create file, truncate if exists
-        let mut file = match std::fs::File::open(path.to_owned()) {
-            Ok(some) => some,
-            Err(e) => {
-                return Err(format!(
-                    "Unable to read project file at {}: {}",
-                    path.to_string_lossy(),
-                    e
-                ));
-            }
-        };
+    /// # Arguments
+    /// * 'snippet_uuids' - uuids of the snippets to copy
+    pub fn copy_snippets(
+        &self,
+        external_snippet_manager: &ExternalSnippetManager,
+        snippet_uuids: &Vec<Uuid>,
+    ) -> Result<String, String> {
+        let mut snippets = Vec::<&SnippetComponent>::with_capacity(snippet_uuids.len());
 
-        let mut unserialized_plan = Vec::<u8>::new();
+        for snippet_uuid in snippet_uuids.iter() {
+            match self.snippet_manager.find_snippet(snippet_uuid) {
+                Some(snippet) => snippets.push(snippet),
+                None => {
+                    return Err(format!("Could not find snippet {} to copy", snippet_uuid));
+                }
+            };
+        }
 
-        // export plan
-        match file.read_to_end(&mut unserialized_plan) {
-            Ok(_) => (),
-            Err(e) => {
-                return Err(format!(
-                    "Unable to read unserialized plan from the file {}: {}",
-                    path.to_string_lossy(),
-                    e
-                ));
-            }
+        let plan = self.create_plan_from_snippets(external_snippet_manager, snippets)?;
+
+        return serialize_plan(&plan);
+    }
+
+    /// Paste snippets copied with copy_snippets into the project, as new snippets
+    /// the paste can be undone as a whole
+    ///
+    /// # Arguments
+    /// * 'clipboard' - copied snippets in the clipboard format
+    /// * 'x_offset' - horizontal offset of the pasted snippets from the copied snippets
+    /// * 'y_offset' - vertical offset of the pasted snippets from the copied snippets
+    ///
+    /// returns the changes the front end needs to apply
+    pub fn paste_snippets(
+        &mut self,
+        external_snippet_manager: &ExternalSnippetManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        clipboard: &String,
+        x_offset: f64,
+        y_offset: f64,
+    ) -> Result<FrontGraphChanges, String> {
+        let mut plan = deserialize_plan(clipboard)?;
+
+        plan.offset_positions(x_offset, y_offset);
+
+        // build into a fresh snippet manager so a failure does not leave a partial paste
+        let mut pasted_snippet_manager = SnippetManager::default();
+        // missing references are shown as placeholders, there is nothing to remap in a paste
+        let mut unresolved_references = Vec::<UnresolvedProjectReference>::new();
+
+        let built_plan = build_plan_into_snippet_manager(
+            &plan,
+            external_snippet_manager,
+            sequential_id_generator,
+            &mut pasted_snippet_manager,
+            &mut unresolved_references,
+        )?;
+
+        // move the pasted snippets and pipelines into the project, keeping their uuids
+        let mut pipelines =
+            Vec::<PipelineComponent>::with_capacity(built_plan.pipeline_uuids.len());
+
+        for pipeline_uuid in built_plan.pipeline_uuids.iter() {
+            match pasted_snippet_manager.find_pipeline(pipeline_uuid) {
+                Some(pipeline) => pipelines.push(pipeline.clone()),
+                None => {
+                    return Err(format!("Critical logic error: Pipeline not found"));
+                }
+            };
+        }
+
+        let operation = HistoryOperation::CreateSnippets {
+            snippets: pasted_snippet_manager
+                .get_snippets_as_ref()
+                .into_iter()
+                .map(|snippet| snippet.clone())
+                .collect(),
+            pipelines: pipelines,
         };
 
-        // deserialize plan
-        let plan: Plan = match bincode::deserialize(&unserialized_plan) {
-            Ok(some) => some,
+        // applied as a whole, a failure leaves the project as it was
+        let front_graph_changes = match apply_operation(
+            &operation,
+            &mut self.snippet_manager,
+            &mut self.visual_component_manager,
+            sequential_id_generator,
+        ) {
+            Ok(result) => result,
             Err(e) => {
-                return Err(format!("Unable to serialize plan: {}", e));
+                return Err(format!("Could not paste snippets: {}", e));
             }
         };
 
-        // clear snippet manager and visual component manager
-        self.snippet_manager = SnippetManager::default();
-        self.visual_component_manager = VisualSnippetComponentManager::default();
+        self.history_manager.record(operation);
 
-        return Ok(plan);
+        return Ok(front_graph_changes);
     }
 
-    pub fn delete_project(&self, project_name: String) {
-        // get location of the project
-        let project_location = get_project_directory_location_from_name(project_name);
+    /// Create the composite snippet definition of the snippets
+    /// inputs not fed from within the snippets, and outputs not only used within the snippets,
+    /// become the connectors of the composite snippet
+    ///
+    /// # Arguments
+    /// * 'snippet_uuids' - uuids of the snippets in the composite snippet
+    pub fn create_composite_snippet_definition(
+        &self,
+        external_snippet_manager: &ExternalSnippetManager,
+        snippet_uuids: &Vec<Uuid>,
+    ) -> Result<CompositeSnippetDefinition, String> {
+        let snippet_manager = &self.snippet_manager;
 
-        // delete the project file itself
-        match std::fs::remove_file(project_location.clone()) {
-            Ok(()) => (),
-            Err(e) => {
-                println!(
-                    "Unable to delete project file at {}: {}",
-                    project_location.to_string_lossy(),
-                    e
-                );
+        let mut snippets = Vec::<&SnippetComponent>::with_capacity(snippet_uuids.len());
+
+        for snippet_uuid in snippet_uuids.iter() {
+            let snippet = match snippet_manager.find_snippet(snippet_uuid) {
+                Some(snippet) => snippet,
+                None => {
+                    return Err(format!(
+                        "Could not find snippet {} to add to the composite snippet",
+                        snippet_uuid
+                    ));
+                }
+            };
+
+            // placeholders have nothing to run
+            if snippet.has_missing_components() {
+                return Err(format!(
+                    "Snippet {} has missing snippets, connectors or parameters and cannot be part of a composite snippet",
+                    snippet.get_name()
+                ));
             }
+
+            snippets.push(snippet);
         }
 
-        // remove file part from directory
-        let mut project_location = match project_location.parent() {
-            Some(parent_dir) => parent_dir.to_path_buf(),
-            None => PathBuf::from("projects"),
+        if snippets.len() == 0 {
+            return Err("No snippets to create the composite snippet from".to_string());
+        }
+
+        let plan = self.create_plan_from_snippets(external_snippet_manager, snippets.clone())?;
+        let local_uuids = create_snippet_local_uuids(&snippets, external_snippet_manager)?;
+
+        let mut inputs = Vec::<CompositeSnippetConnector>::new();
+        let mut outputs = Vec::<CompositeSnippetConnector>::new();
+
+        // in the order of the plan, so the same snippets give the same composite connectors
+        for snippet_action in plan.actions.build_snippet_actions.iter() {
+            let snippet = match snippets
+                .iter()
+                .find(|snippet| local_uuids[&snippet.get_uuid()] == snippet_action.original_uuid)
+            {
+                Some(snippet) => snippet,
+                None => {
+                    return Err(format!("Critical logic error: Snippet not found"));
+                }
+            };
+
+            let pipeline_connector_uuids = snippet.get_pipeline_connector_uuids();
+
+            let mut pipeline_connectors: Vec<&PipelineConnectorComponent> =
+                pipeline_connector_uuids
+                    .iter()
+                    .filter_map(|uuid| snippet.find_pipeline_connector(*uuid))
+                    .collect();
+
+            pipeline_connectors.sort_by_key(|pipeline_connector| pipeline_connector.get_name());
+
+            for pipeline_connector in pipeline_connectors {
+                // snippets on the other end of the pipelines of the connector
+                let mut connected_snippet_uuids = Vec::<Uuid>::new();
+
+                for pipeline_uuid in snippet_manager
+                    .find_pipeline_uuids_from_pipeline_connector(&pipeline_connector.get_uuid())
+                {
+                    let pipeline = match snippet_manager.find_pipeline(&pipeline_uuid) {
+                        Some(pipeline) => pipeline,
+                        None => {
+                            return Err(format!("Critical logic error: Pipeline not found"));
+                        }
+                    };
+
+                    let other_pipeline_connector_uuid = if pipeline
+                        .get_from_pipeline_connector_uuid()
+                        == pipeline_connector.get_uuid()
+                    {
+                        pipeline.get_to_pipeline_connector_uuid()
+                    } else {
+                        pipeline.get_from_pipeline_connector_uuid()
+                    };
+
+                    match snippet_manager
+                        .find_snippet_uuid_from_pipeline_connector(&other_pipeline_connector_uuid)
+                    {
+                        Some(uuid) => connected_snippet_uuids.push(uuid),
+                        None => {
+                            return Err(format!("Critical logic error: Cannot find snippet Uuid from pipeline connector"));
+                        }
+                    };
+                }
+
+                let exposed = if pipeline_connector.get_input() {
+                    !connected_snippet_uuids
+                        .iter()
+                        .any(|uuid| snippet_uuids.contains(uuid))
+                } else {
+                    connected_snippet_uuids.len() == 0
+                        || connected_snippet_uuids
+                            .iter()
+                            .any(|uuid| !snippet_uuids.contains(uuid))
+                };
+
+                if !exposed {
+                    continue;
+                }
+
+                let composite_connectors = if pipeline_connector.get_input() {
+                    &mut inputs
+                } else {
+                    &mut outputs
+                };
+
+                let name = create_composite_connector_name(
+                    composite_connectors,
+                    snippet.get_name(),
+                    pipeline_connector.get_name(),
+                );
+
+                composite_connectors.push(CompositeSnippetConnector {
+                    name: name,
+                    snippet_package_path: snippet_action.package_path.to_owned(),
+                    snippet_original_uuid: snippet_action.original_uuid,
+                    connector_name: pipeline_connector.get_name(),
+                });
+            }
+        }
+
+        return Ok(CompositeSnippetDefinition {
+            plan: plan,
+            inputs: inputs,
+            outputs: outputs,
+        });
+    }
+
+    /// Replace the snippets with the composite snippet created from them,
+    /// reconnecting the pipelines crossing into the snippets to the composite snippet
+    /// the collapse can be undone as a whole
+    ///
+    /// # Arguments
+    /// * 'composite_snippet_definition' - definition created from the snippets
+    /// * 'composite_external_snippet' - external snippet of the composite snippet
+    /// * 'snippet_uuids' - uuids of the snippets in the composite snippet
+    ///
+    /// returns the changes the front end needs to apply
+    pub fn collapse_snippets_into_composite(
+        &mut self,
+        external_snippet_manager: &ExternalSnippetManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        composite_snippet_definition: &CompositeSnippetDefinition,
+        composite_external_snippet: &ExternalSnippet,
+        snippet_uuids: &Vec<Uuid>,
+    ) -> Result<FrontGraphChanges, String> {
+        // collapse in a copy of the snippet manager, so a failure does not leave a partial collapse
+        let mut collapsed_snippet_manager = self.snippet_manager.clone();
+
+        let mut snippets = Vec::<SnippetComponent>::with_capacity(snippet_uuids.len());
+        // pipelines within the snippets, and crossing into them
+        let mut pipelines = Vec::<PipelineComponent>::new();
+
+        for snippet_uuid in snippet_uuids.iter() {
+            match self.snippet_manager.find_snippet(snippet_uuid) {
+                Some(snippet) => snippets.push(snippet.clone()),
+                None => {
+                    return Err(format!(
+                        "Could not find snippet {} to add to the composite snippet",
+                        snippet_uuid
+                    ));
+                }
+            };
+
+            for pipeline_uuid in self
+                .snippet_manager
+                .find_pipeline_uuids_from_snippet(snippet_uuid)
+            {
+                if pipelines
+                    .iter()
+                    .any(|pipeline| pipeline.get_uuid() == pipeline_uuid)
+                {
+                    continue;
+                }
+
+                match self.snippet_manager.find_pipeline(&pipeline_uuid) {
+                    Some(pipeline) => pipelines.push(pipeline.clone()),
+                    None => {
+                        return Err(format!("Critical logic error: Pipeline not found"));
+                    }
+                };
+            }
+        }
+
+        // the composite snippet references the snippets by their uuids in its plan
+        let snippet_refs: Vec<&SnippetComponent> = snippets.iter().collect();
+        let local_uuids = create_snippet_local_uuids(&snippet_refs, external_snippet_manager)?;
+
+        for pipeline in pipelines.iter() {
+            match collapsed_snippet_manager.delete_pipeline(&pipeline.get_uuid()) {
+                Ok(()) => (),
+                Err(e) => {
+                    return Err(format!("Could not remove pipeline: {}", e));
+                }
+            };
+        }
+
+        for snippet in snippets.iter() {
+            match collapsed_snippet_manager.delete_snippet(&snippet.get_uuid()) {
+                Ok(()) => (),
+                Err(e) => {
+                    return Err(format!("Could not remove snippet: {}", e));
+                }
+            };
+        }
+
+        // place the composite snippet at the top left of the snippets
+        let x_position = snippets
+            .iter()
+            .map(|snippet| snippet.get_position().0)
+            .fold(f64::INFINITY, f64::min);
+        let y_position = snippets
+            .iter()
+            .map(|snippet| snippet.get_position().1)
+            .fold(f64::INFINITY, f64::min);
+
+        let composite_snippet_uuid = collapsed_snippet_manager.new_snippet(
+            sequential_id_generator,
+            composite_external_snippet,
+            x_position,
+            y_position,
+        );
+
+        // can safely unwrap as we just created the snippet above
+        let composite_snippet = collapsed_snippet_manager
+            .find_snippet(&composite_snippet_uuid)
+            .unwrap()
+            .clone();
+
+        let mut composite_pipelines = Vec::<PipelineComponent>::new();
+
+        for pipeline in pipelines.iter() {
+            let from_pipeline_connector_uuid = pipeline.get_from_pipeline_connector_uuid();
+            let to_pipeline_connector_uuid = pipeline.get_to_pipeline_connector_uuid();
+
+            // snippets of the pipeline ends, if they are one of the snippets
+            let from_snippet = snippets.iter().find(|snippet| {
+                snippet
+                    .find_pipeline_connector(from_pipeline_connector_uuid)
+                    .is_some()
+            });
+            let to_snippet = snippets.iter().find(|snippet| {
+                snippet
+                    .find_pipeline_connector(to_pipeline_connector_uuid)
+                    .is_some()
+            });
+
+            let (from_pipeline_connector_uuid, to_pipeline_connector_uuid) =
+                match (from_snippet, to_snippet) {
+                    // pipelines within the snippets are part of the composite snippet
+                    (Some(_), Some(_)) | (None, None) => {
+                        continue;
+                    }
+                    // pipeline into the composite snippet
+                    (None, Some(to_snippet)) => (
+                        from_pipeline_connector_uuid,
+                        find_composite_pipeline_connector_uuid(
+                            composite_snippet_definition,
+                            &composite_snippet,
+                            to_snippet,
+                            local_uuids[&to_snippet.get_uuid()],
+                            to_pipeline_connector_uuid,
+                            true,
+                        )?,
+                    ),
+                    // pipeline out of the composite snippet
+                    (Some(from_snippet), None) => (
+                        find_composite_pipeline_connector_uuid(
+                            composite_snippet_definition,
+                            &composite_snippet,
+                            from_snippet,
+                            local_uuids[&from_snippet.get_uuid()],
+                            from_pipeline_connector_uuid,
+                            false,
+                        )?,
+                        to_pipeline_connector_uuid,
+                    ),
+                };
+
+            let pipeline_uuid = match collapsed_snippet_manager.create_pipeline(
+                sequential_id_generator,
+                from_pipeline_connector_uuid,
+                to_pipeline_connector_uuid,
+            ) {
+                Ok(uuid) => uuid,
+                Err(e) => {
+                    return Err(format!(
+                        "Could not connect pipeline to the composite snippet: {}",
+                        e
+                    ));
+                }
+            };
+
+            // can safely unwrap as we just created the pipeline above
+            composite_pipelines.push(
+                collapsed_snippet_manager
+                    .find_pipeline(&pipeline_uuid)
+                    .unwrap()
+                    .clone(),
+            );
+        }
+
+        let operation = HistoryOperation::CollapseSnippets {
+            snippets: snippets,
+            pipelines: pipelines,
+            composite_snippet: composite_snippet,
+            composite_pipelines: composite_pipelines,
+        };
+
+        let front_graph_changes = match apply_operation(
+            &operation,
+            &mut self.snippet_manager,
+            &mut self.visual_component_manager,
+            sequential_id_generator,
+        ) {
+            Ok(result) => result,
+            Err(e) => {
+                return Err(format!("Could not create composite snippet: {}", e));
+            }
+        };
+
+        self.history_manager.record(operation);
+
+        return Ok(front_graph_changes);
+    }
+
+    /// Rebuild the snippet manager and visual component manager from the project plan
+    ///
+    /// the project is built all or nothing, on failure the current project is left untouched
+    /// snippets, connectors and parameters that cannot be found are built as placeholders
+    /// and reported in the unresolved references of the front project content
+    ///
+    /// returns the fully built front project content
+    fn build_project_from_plan(
+        &mut self,
+        external_snippet_manager: &ExternalSnippetManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        mut plan: Plan,
+        remappings: &Vec<ProjectReferenceRemapping>,
+    ) -> Result<FrontProjectContent, String> {
+        plan.apply_remappings(remappings);
+
+        let mut unresolved_references = Vec::<UnresolvedProjectReference>::new();
+
+        // build into fresh managers so a failure does not leave a partially built project
+        let mut snippet_manager = SnippetManager::default();
+        let mut visual_component_manager = VisualSnippetComponentManager::default();
+
+        let built_plan = build_plan_into_snippet_manager(
+            &plan,
+            external_snippet_manager,
+            sequential_id_generator,
+            &mut snippet_manager,
+            &mut unresolved_references,
+        )?;
+
+        // create front content for snippets, this registers the front connector ids
+        // which the front pipelines depend on
+        let mut front_snippets = Vec::<FrontSnippetContent>::new();
+
+        for snippet in snippet_manager.get_snippets_as_ref() {
+            front_snippets.push(snippet.get_snippet_to_front_snippet(
+                &mut visual_component_manager,
+                sequential_id_generator,
+                &snippet_manager,
+            ));
+        }
+
+        // create front content for pipelines
+        let mut front_pipelines = Vec::<FrontPipelineContent>::new();
+
+        for pipeline_uuid in built_plan.pipeline_uuids {
+            let pipeline = match snippet_manager.find_pipeline(&pipeline_uuid) {
+                Some(pipeline) => pipeline,
+                None => {
+                    return Err(format!("Critical logic error: Pipeline not found"));
+                }
+            };
+
+            match pipeline.create_pipeline_as_front_content(
+                &mut visual_component_manager,
+                sequential_id_generator,
+            ) {
+                Ok(front_pipeline) => front_pipelines.push(front_pipeline),
+                Err(e) => {
+                    return Err(format!("Could not create front pipeline: {}", e));
+                }
+            };
+        }
+
+        // project was fully built, replace the current project
+        self.snippet_manager = snippet_manager;
+        self.visual_component_manager = visual_component_manager;
+        self.variable_profiles = plan.variable_profiles;
+
+        // edits of the previous project can no longer be undone
+        self.history_manager.clear();
+
+        return Ok(FrontProjectContent::new(
+            front_snippets,
+            front_pipelines,
+            unresolved_references,
+        ));
+    }
+
+    /// Create the front content of the current project session, for drawing it again
+    /// such as when switching to its tab. The front uuids of the previous drawing are replaced
+    pub fn create_front_project_content(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<FrontProjectContent, String> {
+        let mut visual_component_manager = VisualSnippetComponentManager::default();
+
+        // create front content for snippets first, as the front pipelines depend on their connectors
+        let mut front_snippets = Vec::<FrontSnippetContent>::new();
+
+        for snippet in self.snippet_manager.get_snippets_as_ref() {
+            front_snippets.push(snippet.get_snippet_to_front_snippet(
+                &mut visual_component_manager,
+                sequential_id_generator,
+                &self.snippet_manager,
+            ));
+        }
+
+        let mut front_pipelines = Vec::<FrontPipelineContent>::new();
+
+        for pipeline in self.snippet_manager.get_pipelines_as_ref() {
+            match pipeline.create_pipeline_as_front_content(
+                &mut visual_component_manager,
+                sequential_id_generator,
+            ) {
+                Ok(front_pipeline) => front_pipelines.push(front_pipeline),
+                Err(e) => {
+                    return Err(format!("Could not create front pipeline: {}", e));
+                }
+            };
+        }
+
+        self.visual_component_manager = visual_component_manager;
+
+        // missing components are already placeholders
+        return Ok(FrontProjectContent::new(
+            front_snippets,
+            front_pipelines,
+            Vec::new(),
+        ));
+    }
+
+    pub fn delete_project(&self, project_name: String) {
+        // get location of the project
+        let project_location = get_project_directory_location_from_name(project_name.to_owned());
+
+        // delete the project file itself
+        match std::fs::remove_file(project_location.clone()) {
+            Ok(()) => (),
+            Err(e) => {
+                println!(
+                    "Unable to delete project file at {}: {}",
+                    project_location.to_string_lossy(),
+                    e
+                );
+            }
+        }
+
+        // delete the run logs of the project
+        let project_logs_location = get_project_logs_directory_location_from_name(&project_name);
+
+        if project_logs_location.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&project_logs_location) {
+                println!(
+                    "Unable to delete project logs at {}: {}",
+                    project_logs_location.to_string_lossy(),
+                    e
+                );
+            }
+        }
+
+        // delete the secrets of the project
+        remove_secret_store(&get_project_secret_store_location_from_name(&project_name));
+
+        // delete the run records of the project
+        let project_runs_location = get_project_runs_directory_location_from_name(&project_name);
+
+        if project_runs_location.exists() {
+            if let Err(e) = std::fs::remove_dir_all(&project_runs_location) {
+                println!(
+                    "Unable to delete project runs at {}: {}",
+                    project_runs_location.to_string_lossy(),
+                    e
+                );
+            }
+        }
+
+        // remove file part from directory
+        let mut project_location = match project_location.parent() {
+            Some(parent_dir) => parent_dir.to_path_buf(),
+            None => PathBuf::from("projects"),
         };
 
         // delete all empty folders until the projects folder (and not including the projects folder)
@@ -428,10 +1374,6 @@ impl ProjectManager {
             };
         }
     }
-
-    pub fn get_default_plan(&self) -> Plan {
-        return Plan::default();
-    }
 }
 
 /// Get the directory path of project given it's name
@@ -453,3 +1395,2199 @@ pub fn get_project_directory_location_from_name(project_name: String) -> PathBuf
 
     return project_path;
 }
+
+/// Get the directory path of the logs of the project given it's name,
+/// which lies next to the project file
+pub fn get_project_logs_directory_location_from_name(project_name: &String) -> PathBuf {
+    let mut project_logs_path = get_project_directory_location_from_name(project_name.to_owned());
+
+    project_logs_path.set_extension("logs");
+
+    return project_logs_path;
+}
+
+/// get the location of the directory of the run records of the project, next to the project file
+pub fn get_project_runs_directory_location_from_name(project_name: &String) -> PathBuf {
+    let mut project_runs_path = get_project_directory_location_from_name(project_name.to_owned());
+
+    project_runs_path.set_extension("runs");
+
+    return project_runs_path;
+}
+
+impl Plan {
+    /// fill in the values of the secret parameters, which the project file leaves empty
+    fn apply_secrets(&mut self, secret_store: &SecretStore) {
+        for action in self.actions.build_snippet_parameter_actions.iter_mut() {
+            if let Some(value) = secret_store.find_secret(
+                &action.snippet_package_path,
+                action.snippet_original_uuid,
+                &action.parameter_name,
+            ) {
+                action.parameter_value = value.to_owned();
+            }
+        }
+    }
+
+    /// apply remappings to the references in the plan, in order
+    fn apply_remappings(&mut self, remappings: &Vec<ProjectReferenceRemapping>) {
+        for remapping in remappings.iter() {
+            match remapping {
+                ProjectReferenceRemapping::SnippetPackagePath {
+                    from_package_path,
+                    to_package_path,
+                } => {
+                    for action in self.actions.build_snippet_actions.iter_mut() {
+                        if action.package_path == *from_package_path {
+                            action.package_path = to_package_path.to_owned();
+                        }
+                    }
+
+                    for action in self.actions.build_snippet_pipeline_actions.iter_mut() {
+                        if action.from_snippet_package_path == *from_package_path {
+                            action.from_snippet_package_path = to_package_path.to_owned();
+                        }
+
+                        if action.to_snippet_package_path == *from_package_path {
+                            action.to_snippet_package_path = to_package_path.to_owned();
+                        }
+                    }
+
+                    for action in self.actions.build_snippet_parameter_actions.iter_mut() {
+                        if action.snippet_package_path == *from_package_path {
+                            action.snippet_package_path = to_package_path.to_owned();
+                        }
+                    }
+                }
+                ProjectReferenceRemapping::PipelineConnectorName {
+                    package_path,
+                    from_connector_name,
+                    to_connector_name,
+                } => {
+                    for action in self.actions.build_snippet_pipeline_actions.iter_mut() {
+                        if action.from_snippet_package_path == *package_path
+                            && action.from_snippet_connector_name == *from_connector_name
+                        {
+                            action.from_snippet_connector_name = to_connector_name.to_owned();
+                        }
+
+                        if action.to_snippet_package_path == *package_path
+                            && action.to_snippet_connector_name == *from_connector_name
+                        {
+                            action.to_snippet_connector_name = to_connector_name.to_owned();
+                        }
+                    }
+                }
+                ProjectReferenceRemapping::ParameterName {
+                    package_path,
+                    from_parameter_name,
+                    to_parameter_name,
+                } => {
+                    for action in self.actions.build_snippet_parameter_actions.iter_mut() {
+                        if action.snippet_package_path == *package_path
+                            && action.parameter_name == *from_parameter_name
+                        {
+                            action.parameter_name = to_parameter_name.to_owned();
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// move the position of all snippets in the plan
+    fn offset_positions(&mut self, x_offset: f64, y_offset: f64) {
+        for action in self.actions.build_snippet_actions.iter_mut() {
+            action.x_position += x_offset;
+            action.y_position += y_offset;
+        }
+    }
+
+    /// sort the actions into a deterministic order
+    fn sort_actions(&mut self) {
+        self.actions.build_snippet_actions.sort_by(|a, b| {
+            return a
+                .package_path
+                .to_string()
+                .cmp(&b.package_path.to_string())
+                .then(a.original_uuid.cmp(&b.original_uuid));
+        });
+
+        self.actions.build_snippet_pipeline_actions.sort_by(|a, b| {
+            return a
+                .from_snippet_original_uuid
+                .cmp(&b.from_snippet_original_uuid)
+                .then(
+                    a.from_snippet_connector_name
+                        .cmp(&b.from_snippet_connector_name),
+                )
+                .then(a.to_snippet_original_uuid.cmp(&b.to_snippet_original_uuid))
+                .then(
+                    a.to_snippet_connector_name
+                        .cmp(&b.to_snippet_connector_name),
+                );
+        });
+
+        self.actions
+            .build_snippet_parameter_actions
+            .sort_by(|a, b| {
+                return a
+                    .snippet_original_uuid
+                    .cmp(&b.snippet_original_uuid)
+                    .then(a.parameter_name.cmp(&b.parameter_name));
+            });
+    }
+}
+
+/// Serialize the plan into the project file format
+fn serialize_plan(plan: &Plan) -> Result<String, String> {
+    return match serde_json::to_string_pretty(plan) {
+        Ok(some) => Ok(some),
+        Err(e) => Err(format!("Unable to serialize plan: {}", e)),
+    };
+}
+
+/// Deserialize a plan in the project file format, such as copied snippets
+fn deserialize_plan(serialized_plan: &String) -> Result<Plan, String> {
+    let versioned_plan: serde_json::Value = match serde_json::from_str(serialized_plan) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!(
+                "Unable to read plan, it is not a valid plan: {}",
+                e
+            ));
+        }
+    };
+
+    // upgrade plan to the current format
+    let migrated_plan = migrate_plan(versioned_plan)?;
+
+    return match serde_json::from_value(migrated_plan) {
+        Ok(some) => Ok(some),
+        Err(e) => Err(format!("Unable to read plan: {}", e)),
+    };
+}
+
+impl std::fmt::Display for UnresolvedProjectReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return match self {
+            UnresolvedProjectReference::MissingSnippet { package_path } => {
+                write!(f, "Snippet {} could not be found", package_path.to_string())
+            }
+            UnresolvedProjectReference::MissingPipelineConnector {
+                package_path,
+                connector_name,
+            } => write!(
+                f,
+                "Connector {} could not be found on snippet {}",
+                connector_name, package_path.to_string()
+            ),
+            UnresolvedProjectReference::MissingParameter {
+                package_path,
+                parameter_name,
+            } => write!(
+                f,
+                "Parameter {} could not be found on snippet {}",
+                parameter_name, package_path.to_string()
+            ),
+            UnresolvedProjectReference::InvalidParameterValue {
+                package_path,
+                parameter_name,
+                parameter_value,
+            } => write!(
+                f,
+                "Value {} of parameter {} on snippet {} is not valid for its type, the default value is used instead",
+                parameter_value, parameter_name, package_path.to_string()
+            ),
+        };
+    }
+}
+
+impl CompositeSnippetDefinition {
+    /// get the names of the inputs of the composite snippet
+    pub fn get_input_names(&self) -> Vec<String> {
+        return self
+            .inputs
+            .iter()
+            .map(|connector| connector.name.to_owned())
+            .collect();
+    }
+
+    /// get the names of the outputs of the composite snippet
+    pub fn get_output_names(&self) -> Vec<String> {
+        return self
+            .outputs
+            .iter()
+            .map(|connector| connector.name.to_owned())
+            .collect();
+    }
+}
+
+/// Create a name for a composite connector that is not used by the other composite connectors,
+/// prefixing the snippet name when the connector name is already used
+fn create_composite_connector_name(
+    composite_connectors: &Vec<CompositeSnippetConnector>,
+    snippet_name: String,
+    connector_name: String,
+) -> String {
+    let is_used = |name: &String| -> bool {
+        return composite_connectors
+            .iter()
+            .any(|composite_connector| composite_connector.name.eq(name));
+    };
+
+    if !is_used(&connector_name) {
+        return connector_name;
+    }
+
+    let base_name = format!("{}_{}", snippet_name, connector_name);
+    let mut name = base_name.to_owned();
+    let mut i = 2;
+
+    while is_used(&name) {
+        name = format!("{}_{}", base_name, i);
+        i += 1;
+    }
+
+    return name;
+}
+
+/// Find the pipeline connector of the composite snippet which the pipeline connector
+/// of one of its inner snippets is exposed as
+fn find_composite_pipeline_connector_uuid(
+    composite_snippet_definition: &CompositeSnippetDefinition,
+    composite_snippet: &SnippetComponent,
+    snippet: &SnippetComponent,
+    snippet_local_uuid: Uuid,
+    pipeline_connector_uuid: Uuid,
+    input: bool,
+) -> Result<Uuid, String> {
+    let connector_name = match snippet.find_pipeline_connector(pipeline_connector_uuid) {
+        Some(pipeline_connector) => pipeline_connector.get_name(),
+        None => {
+            return Err(format!(
+                "Critical logic error: Pipeline connector not found"
+            ));
+        }
+    };
+
+    let composite_connectors = if input {
+        &composite_snippet_definition.inputs
+    } else {
+        &composite_snippet_definition.outputs
+    };
+
+    let composite_connector = match composite_connectors.iter().find(|composite_connector| {
+        composite_connector.snippet_original_uuid == snippet_local_uuid
+            && composite_connector.connector_name.eq(&connector_name)
+    }) {
+        Some(composite_connector) => composite_connector,
+        None => {
+            return Err(format!(
+                "Connector {} of snippet {} is not a connector of the composite snippet",
+                connector_name,
+                snippet.get_name()
+            ));
+        }
+    };
+
+    return match composite_snippet
+        .find_pipeline_connector_from_name_and_input(composite_connector.name.to_owned(), input)
+    {
+        Some(pipeline_connector) => Ok(pipeline_connector.get_uuid()),
+        None => Err(format!(
+            "Composite snippet has no connector {}",
+            composite_connector.name
+        )),
+    };
+}
+
+/// Write the composite snippet definition into the composite snippet file of the snippet directory
+pub fn write_composite_snippet_definition(
+    composite_snippet_definition: &CompositeSnippetDefinition,
+    path: PathBuf,
+) -> Result<(), String> {
+    let serialized_composite_snippet_definition =
+        match serde_json::to_string_pretty(composite_snippet_definition) {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(format!(
+                    "Unable to serialize composite snippet definition: {}",
+                    e
+                ));
+            }
+        };
+
+    return write_serialized_plan_to_file(&serialized_composite_snippet_definition, path);
+}
+
+/// Read the composite snippet definition from the composite snippet file
+pub fn read_composite_snippet_definition(
+    path: PathBuf,
+) -> Result<CompositeSnippetDefinition, String> {
+    let serialized_composite_snippet_definition = match std::fs::read_to_string(path.to_owned()) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!(
+                "Unable to read composite snippet file at {}: {}",
+                path.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    let mut composite_snippet_definition: serde_json::Value = match serde_json::from_str(
+        &serialized_composite_snippet_definition,
+    ) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!(
+                    "Unable to read composite snippet file {}, it is not a valid composite snippet file: {}",
+                    path.to_string_lossy(),
+                    e
+                ));
+        }
+    };
+
+    // upgrade the plan of the composite snippet to the current format
+    if let Some(plan) = composite_snippet_definition.get_mut("plan") {
+        *plan = migrate_plan(plan.take())?;
+    }
+
+    return match serde_json::from_value(composite_snippet_definition) {
+        Ok(some) => Ok(some),
+        Err(e) => Err(format!(
+            "Unable to read composite snippet file {}: {}",
+            path.to_string_lossy(),
+            e
+        )),
+    };
+}
+
+/// Build the inner snippets of the composite snippet into the snippet manager
+/// unlike projects, composite snippets cannot be built with placeholders, as they are only built to be ran
+pub fn build_composite_snippet_into_snippet_manager(
+    composite_snippet_definition: &CompositeSnippetDefinition,
+    external_snippet_manager: &ExternalSnippetManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+    snippet_manager: &mut SnippetManager,
+) -> Result<BuiltCompositeSnippet, String> {
+    let mut unresolved_references = Vec::<UnresolvedProjectReference>::new();
+
+    let built_plan = build_plan_into_snippet_manager(
+        &composite_snippet_definition.plan,
+        external_snippet_manager,
+        sequential_id_generator,
+        snippet_manager,
+        &mut unresolved_references,
+    )?;
+
+    if unresolved_references.len() > 0 {
+        return Err(
+            "Composite snippet has snippets, connectors or parameters that could not be found"
+                .to_string(),
+        );
+    }
+
+    let input_pipeline_connectors = find_composite_inner_pipeline_connector_uuids(
+        &composite_snippet_definition.inputs,
+        &built_plan,
+        snippet_manager,
+        true,
+    )?;
+    let output_pipeline_connectors = find_composite_inner_pipeline_connector_uuids(
+        &composite_snippet_definition.outputs,
+        &built_plan,
+        snippet_manager,
+        false,
+    )?;
+
+    return Ok(BuiltCompositeSnippet {
+        snippet_uuids: built_plan
+            .original_to_snippet_uuid
+            .values()
+            .copied()
+            .collect(),
+        input_pipeline_connectors: input_pipeline_connectors,
+        output_pipeline_connectors: output_pipeline_connectors,
+    });
+}
+
+/// Find the pipeline connectors of the built inner snippets the composite connectors are exposing
+///
+/// returns the mapping of composite connector name to inner pipeline connector uuid
+fn find_composite_inner_pipeline_connector_uuids(
+    composite_connectors: &Vec<CompositeSnippetConnector>,
+    built_plan: &BuiltPlan,
+    snippet_manager: &SnippetManager,
+    input: bool,
+) -> Result<HashMap<String, Uuid>, String> {
+    let mut pipeline_connector_uuids = HashMap::<String, Uuid>::new();
+
+    for composite_connector in composite_connectors.iter() {
+        let snippet_uuid = match built_plan.original_to_snippet_uuid.get(&(
+            composite_connector.snippet_package_path.to_owned(),
+            composite_connector.snippet_original_uuid,
+        )) {
+            Some(uuid) => uuid.to_owned(),
+            None => {
+                return Err(format!(
+                    "Composite connector {} references snippet {} which is not part of the composite snippet",
+                    composite_connector.name,
+                    composite_connector.snippet_package_path.to_string()
+                ));
+            }
+        };
+
+        let snippet = match snippet_manager.find_snippet(&snippet_uuid) {
+            Some(snippet) => snippet,
+            None => {
+                return Err(format!("Critical logic error: Snippet not found"));
+            }
+        };
+
+        match snippet.find_pipeline_connector_from_name_and_input(
+            composite_connector.connector_name.to_owned(),
+            input,
+        ) {
+            Some(pipeline_connector) => {
+                pipeline_connector_uuids.insert(
+                    composite_connector.name.to_owned(),
+                    pipeline_connector.get_uuid(),
+                );
+            }
+            None => {
+                return Err(format!(
+                    "Composite connector {} references connector {} which could not be found on snippet {}",
+                    composite_connector.name,
+                    composite_connector.connector_name,
+                    composite_connector.snippet_package_path.to_string()
+                ));
+            }
+        };
+    }
+
+    return Ok(pipeline_connector_uuids);
+}
+
+/// Write the serialized plan to the file path, creating any necessary directories
+fn write_serialized_plan_to_file(serialized_plan: &String, path: PathBuf) -> Result<(), String> {
+    // create necessary directories for file
+    if let Some(parent_dir) = path.parent() {
+        match std::fs::create_dir_all(parent_dir) {
+            Ok(()) => (),
+            Err(e) => {
+                return Err(format!(
+                    "Unable to create necessary directories for project file {}: {}",
+                    path.to_string_lossy(),
+                    e
+                ));
+            }
+        }
+    } else {
+        return Err("Invalid project file path".to_string());
+    }
+
+    // create file, truncate if exists
+    let mut file = match std::fs::File::create(path.to_owned()) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!(
+                "Unable to create project file at {}: {}",
+                path.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    // export plan
+    match file.write_all(serialized_plan.as_bytes()) {
+        Ok(()) => (),
+        Err(e) => {
+            return Err(format!(
+                "Unable to write serialized plan to file {}: {}",
+                path.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    return Ok(());
+}
+
+/// Read and deserialize the plan from the project file path
+fn read_plan_from_file(path: PathBuf) -> Result<Plan, String> {
+    let mut file = match std::fs::File::open(path.to_owned()) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!(
+                "Unable to read project file at {}: {}",
+                path.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    let mut unserialized_plan = Vec::<u8>::new();
+
+    match file.read_to_end(&mut unserialized_plan) {
+        Ok(_) => (),
+        Err(e) => {
+            return Err(format!(
+                "Unable to read unserialized plan from the file {}: {}",
+                path.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    // project files are json, anything that is not is the legacy bincode format
+    // a legacy file can start with the same byte as a json object, so json is tried first
+    let versioned_plan: serde_json::Value = match serde_json::from_slice(&unserialized_plan) {
+        Ok(some) => some,
+        Err(json_error) => {
+            let legacy_plan: LegacyBincodePlan = match bincode::deserialize(&unserialized_plan) {
+                Ok(some) => some,
+                Err(_) => {
+                    return Err(format!(
+                        "Unable to read project file {}, it is not a valid project file: {}",
+                        path.to_string_lossy(),
+                        json_error
+                    ));
+                }
+            };
+
+            // legacy plans are the unversioned plan structure
+            match serde_json::to_value(legacy_plan) {
+                Ok(some) => some,
+                Err(e) => {
+                    return Err(format!("Unable to convert legacy project file: {}", e));
+                }
+            }
+        }
+    };
+
+    // upgrade plan to the current format
+    let migrated_plan = migrate_plan(versioned_plan)?;
+
+    // deserialize plan
+    let plan: Plan = match serde_json::from_value(migrated_plan) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!(
+                "Unable to read project file {}: {}",
+                path.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    return Ok(plan);
+}
+
+/// snippets and pipelines built from a plan
+struct BuiltPlan {
+    // (package path, original uuid) -> new snippet uuid
+    original_to_snippet_uuid: HashMap<(PackagePath, Uuid), Uuid>,
+    pipeline_uuids: Vec<Uuid>,
+}
+
+/// Build the snippets, parameter values and pipelines of the plan into the snippet manager
+/// snippets, connectors and parameters that cannot be found are built as placeholders
+/// and added to the unresolved references
+///
+/// returns the uuids of the built snippets and pipelines
+fn build_plan_into_snippet_manager(
+    plan: &Plan,
+    external_snippet_manager: &ExternalSnippetManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+    snippet_manager: &mut SnippetManager,
+    unresolved_references: &mut Vec<UnresolvedProjectReference>,
+) -> Result<BuiltPlan, String> {
+    // (package path, original uuid) -> new snippet uuid
+    let mut original_to_snippet_uuid: HashMap<(PackagePath, Uuid), Uuid> = HashMap::new();
+
+    // build snippets
+    for snippet_action in plan.actions.build_snippet_actions.iter() {
+        let snippet_uuid = match external_snippet_manager
+            .find_external_snippet_from_package_path(&snippet_action.package_path)
+        {
+            Some(external_snippet) => snippet_manager.new_snippet(
+                sequential_id_generator,
+                external_snippet,
+                snippet_action.x_position,
+                snippet_action.y_position,
+            ),
+            None => {
+                // keep the snippet as a placeholder
+                let unresolved_reference = UnresolvedProjectReference::MissingSnippet {
+                    package_path: snippet_action.package_path.to_owned(),
+                };
+
+                if !unresolved_references.contains(&unresolved_reference) {
+                    unresolved_references.push(unresolved_reference);
+                }
+
+                snippet_manager.new_missing_snippet(
+                    sequential_id_generator,
+                    snippet_action.package_path.to_owned(),
+                    snippet_action.x_position,
+                    snippet_action.y_position,
+                )
+            }
+        };
+
+        original_to_snippet_uuid.insert(
+            (
+                snippet_action.package_path.to_owned(),
+                snippet_action.original_uuid,
+            ),
+            snippet_uuid,
+        );
+    }
+
+    // set parameter values
+    for parameter_action in plan.actions.build_snippet_parameter_actions.iter() {
+        let snippet_uuid = match original_to_snippet_uuid.get(&(
+            parameter_action.snippet_package_path.to_owned(),
+            parameter_action.snippet_original_uuid,
+        )) {
+            Some(uuid) => uuid.to_owned(),
+            None => {
+                return Err(format!(
+                    "Parameter {} references snippet {} which is not part of the project",
+                    parameter_action.parameter_name,
+                    parameter_action.snippet_package_path.to_string()
+                ));
+            }
+        };
+
+        let snippet = match snippet_manager.find_snippet(&snippet_uuid) {
+            Some(snippet) => snippet,
+            None => {
+                return Err(format!("Critical logic error: Snippet not found"));
+            }
+        };
+
+        let parameter_uuid =
+            match snippet.find_parameter_from_name(parameter_action.parameter_name.to_owned()) {
+                Some(parameter) => parameter.get_uuid(),
+                None => {
+                    // placeholder snippets are already reported as a whole
+                    if snippet.get_missing_package_path().is_none() {
+                        unresolved_references.push(UnresolvedProjectReference::MissingParameter {
+                            package_path: parameter_action.snippet_package_path.to_owned(),
+                            parameter_name: parameter_action.parameter_name.to_owned(),
+                        });
+                    }
+
+                    // keep the parameter and its value as a placeholder
+                    match snippet_manager.add_missing_parameter(
+                        sequential_id_generator,
+                        snippet_uuid,
+                        parameter_action.parameter_name.to_owned(),
+                        parameter_action.parameter_value.to_owned(),
+                    ) {
+                        Ok(_) => (),
+                        Err(e) => {
+                            return Err(format!("Could not create missing parameter: {}", e));
+                        }
+                    };
+
+                    continue;
+                }
+            };
+
+        let parameter = match snippet_manager.find_parameter(&parameter_uuid) {
+            Some(parameter) => parameter,
+            None => {
+                return Err(format!("Critical logic error: Parameter not found"));
+            }
+        };
+
+        match parameter.update_value(parameter_action.parameter_value.to_owned()) {
+            Ok(()) => (),
+            // parameters saved before they were typed were empty until set
+            Err(_) if parameter_action.parameter_value.is_empty() => (),
+            Err(_) => {
+                unresolved_references.push(UnresolvedProjectReference::InvalidParameterValue {
+                    package_path: parameter_action.snippet_package_path.to_owned(),
+                    parameter_name: parameter_action.parameter_name.to_owned(),
+                    parameter_value: parameter_action.parameter_value.to_owned(),
+                });
+            }
+        };
+    }
+
+    // build pipelines
+    let mut pipeline_uuids = Vec::<Uuid>::new();
+
+    for pipeline_action in plan.actions.build_snippet_pipeline_actions.iter() {
+        let from_pipeline_connector_uuid = find_pipeline_connector_uuid_from_plan_reference(
+            snippet_manager,
+            sequential_id_generator,
+            &original_to_snippet_uuid,
+            unresolved_references,
+            &pipeline_action.from_snippet_package_path,
+            pipeline_action.from_snippet_original_uuid,
+            &pipeline_action.from_snippet_connector_name,
+            false,
+        )?;
+
+        let to_pipeline_connector_uuid = find_pipeline_connector_uuid_from_plan_reference(
+            snippet_manager,
+            sequential_id_generator,
+            &original_to_snippet_uuid,
+            unresolved_references,
+            &pipeline_action.to_snippet_package_path,
+            pipeline_action.to_snippet_original_uuid,
+            &pipeline_action.to_snippet_connector_name,
+            true,
+        )?;
+
+        let pipeline_uuid = match snippet_manager.create_pipeline(
+            sequential_id_generator,
+            from_pipeline_connector_uuid,
+            to_pipeline_connector_uuid,
+        ) {
+            Ok(uuid) => uuid,
+            Err(e) => {
+                return Err(format!(
+                    "Could not create pipeline from {} to {}: {}",
+                    pipeline_action.from_snippet_connector_name,
+                    pipeline_action.to_snippet_connector_name,
+                    e
+                ));
+            }
+        };
+
+        pipeline_uuids.push(pipeline_uuid);
+    }
+
+    return Ok(BuiltPlan {
+        original_to_snippet_uuid: original_to_snippet_uuid,
+        pipeline_uuids: pipeline_uuids,
+    });
+}
+
+/// migration that upgrades a plan from the format version of its index to the next format version
+type PlanMigration = fn(serde_json::Value) -> Result<serde_json::Value, String>;
+
+/// chain of plan migrations, the migration at index i upgrades a plan from version i to version i + 1
+/// the length of this list must always equal PROJECT_FORMAT_VERSION
+const PLAN_MIGRATIONS: [PlanMigration; PROJECT_FORMAT_VERSION as usize] =
+    [migrate_plan_v0_to_v1, migrate_plan_v1_to_v2];
+
+/// Upgrade the plan step by step from its format version to the current format version
+fn migrate_plan(mut plan: serde_json::Value) -> Result<serde_json::Value, String> {
+    // plans without a format version predate versioning
+    let format_version = match plan.get("format_version") {
+        Some(version) => match version.as_u64() {
+            Some(version) => version,
+            None => {
+                return Err("Project file has an invalid format version".to_string());
+            }
+        },
+        None => 0,
+    };
+
+    // refuse files written by newer versions of the app, we cannot know what they contain
+    if format_version > PROJECT_FORMAT_VERSION as u64 {
+        return Err(format!(
+            "Project file was saved by a newer version of the app (project format version {}, this version supports up to {}), please update the app to open it",
+            format_version, PROJECT_FORMAT_VERSION
+        ));
+    }
+
+    for migration in PLAN_MIGRATIONS.iter().skip(format_version as usize) {
+        plan = migration(plan)?;
+    }
+
+    return Ok(plan);
+}
+
+/// version 0 is the unversioned plan, version 1 adds the format version
+fn migrate_plan_v0_to_v1(mut plan: serde_json::Value) -> Result<serde_json::Value, String> {
+    let plan_object = match plan.as_object_mut() {
+        Some(some) => some,
+        None => {
+            return Err("Project file is not a valid plan".to_string());
+        }
+    };
+
+    plan_object.insert("format_version".to_string(), serde_json::Value::from(1));
+
+    return Ok(plan);
+}
+
+/// version 2 adds the variable profiles of the project, which older projects have none of
+fn migrate_plan_v1_to_v2(mut plan: serde_json::Value) -> Result<serde_json::Value, String> {
+    let plan_object = match plan.as_object_mut() {
+        Some(some) => some,
+        None => {
+            return Err("Project file is not a valid plan".to_string());
+        }
+    };
+
+    plan_object.insert("format_version".to_string(), serde_json::Value::from(2));
+    plan_object.insert(
+        "variable_profiles".to_string(),
+        serde_json::Value::Object(serde_json::Map::new()),
+    );
+
+    return Ok(plan);
+}
+
+/// Find the pipeline connector uuid of a rebuilt snippet from the reference stored in the plan
+/// if the connector does not exist on the snippet, a placeholder connector is created
+///
+/// # Arguments
+/// * 'input' - if the referenced connector is the input end of the pipeline
+fn find_pipeline_connector_uuid_from_plan_reference(
+    snippet_manager: &mut SnippetManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+    original_to_snippet_uuid: &HashMap<(PackagePath, Uuid), Uuid>,
+    unresolved_references: &mut Vec<UnresolvedProjectReference>,
+    package_path: &PackagePath,
+    original_uuid: Uuid,
+    connector_name: &String,
+    input: bool,
+) -> Result<Uuid, String> {
+    let snippet_uuid = match original_to_snippet_uuid.get(&(package_path.to_owned(), original_uuid))
+    {
+        Some(uuid) => uuid.to_owned(),
+        None => {
+            return Err(format!(
+                "Pipeline references snippet {} which is not part of the project",
+                package_path.to_string()
+            ));
+        }
+    };
+
+    let snippet = match snippet_manager.find_snippet(&snippet_uuid) {
+        Some(snippet) => snippet,
+        None => {
+            return Err(format!("Critical logic error: Snippet not found"));
+        }
+    };
+
+    // placeholder connectors are found by name as well, so a missing connector is only created once
+    if let Some(pipeline_connector) =
+        snippet.find_pipeline_connector_from_name(connector_name.to_owned())
+    {
+        return Ok(pipeline_connector.get_uuid());
+    }
+
+    // placeholder snippets are already reported as a whole
+    if snippet.get_missing_package_path().is_none() {
+        unresolved_references.push(UnresolvedProjectReference::MissingPipelineConnector {
+            package_path: package_path.to_owned(),
+            connector_name: connector_name.to_owned(),
+        });
+    }
+
+    return match snippet_manager.add_missing_pipeline_connector(
+        sequential_id_generator,
+        snippet_uuid,
+        connector_name,
+        input,
+    ) {
+        Ok(uuid) => Ok(uuid),
+        Err(e) => Err(format!(
+            "Could not create missing pipeline connector: {}",
+            e
+        )),
+    };
+}
+
+/// Get the package path of a snippet, placeholder snippets keep the package path they were saved with
+fn find_snippet_package_path(
+    snippet: &SnippetComponent,
+    external_snippet_manager: &ExternalSnippetManager,
+) -> Result<PackagePath, String> {
+    if let Some(package_path) = snippet.get_missing_package_path() {
+        return Ok(package_path);
+    }
+
+    return match external_snippet_manager.find_external_snippet(snippet.get_external_snippet_id()) {
+        Some(external_snippet) => Ok(external_snippet.get_package_path()),
+        None => Err(format!(
+            "Could not find snippet in external snippet manager in project build actions step"
+        )),
+    };
+}
+
+/// Number the snippets in the order of their package path, position and parameter values,
+/// so the plan of the same snippets gets the same uuids whatever their uuids in this session
+///
+/// returns the local uuid of each snippet, by its uuid in this session
+fn create_snippet_local_uuids(
+    snippets: &Vec<&SnippetComponent>,
+    external_snippet_manager: &ExternalSnippetManager,
+) -> Result<HashMap<Uuid, Uuid>, String> {
+    // (package path, x position, y position, parameter values, uuid) of the snippets
+    let mut snippet_keys =
+        Vec::<(String, f64, f64, Vec<(String, String)>, Uuid)>::with_capacity(snippets.len());
+
+    for snippet in snippets.iter() {
+        let package_path = find_snippet_package_path(snippet, external_snippet_manager)?;
+        let position = snippet.get_position();
+
+        // secrets are not in the plan, so they do not order it either
+        let mut parameter_values: Vec<(String, String)> = snippet
+            .get_parameters_as_copy()
+            .iter()
+            .map(|parameter| match parameter.is_secret() {
+                true => (parameter.get_name(), String::new()),
+                false => (parameter.get_name(), parameter.get_storage().to_string()),
+            })
+            .collect();
+        parameter_values.sort();
+
+        snippet_keys.push((
+            package_path.to_string(),
+            position.0,
+            position.1,
+            parameter_values,
+            snippet.get_uuid(),
+        ));
+    }
+
+    // the uuid of this session only orders snippets that are the same in every other way
+    snippet_keys.sort_by(|a, b| {
+        return a
+            .0
+            .cmp(&b.0)
+            .then(a.1.total_cmp(&b.1))
+            .then(a.2.total_cmp(&b.2))
+            .then(a.3.cmp(&b.3))
+            .then(a.4.cmp(&b.4));
+    });
+
+    return Ok(snippet_keys
+        .iter()
+        .enumerate()
+        .map(|(i, snippet_key)| (snippet_key.4, i as Uuid + 1))
+        .collect());
+}
+
+/// Get the location of the recovery file from its recovery id
+fn get_recovery_file_location(recovery_id: &String) -> PathBuf {
+    return get_recovery_directory().join(format!("{}.recovery", recovery_id));
+}
+
+/// Recovery ids come from the front end, make sure they stay inside the recovery directory
+fn validate_recovery_id(recovery_id: &String) -> Result<(), String> {
+    if recovery_id.is_empty()
+        || recovery_id.contains('/')
+        || recovery_id.contains('\\')
+        || recovery_id.contains("..")
+    {
+        return Err(format!("Invalid recovery id {}", recovery_id));
+    }
+
+    return Ok(());
+}
+
+/// Remove the recovery file of the recovery id, if it exists
+fn remove_recovery_file(recovery_id: &String) {
+    let recovery_location = get_recovery_file_location(recovery_id);
+
+    if !recovery_location.exists() {
+        return;
+    }
+
+    if let Err(e) = std::fs::remove_file(&recovery_location) {
+        println!(
+            "Unable to delete recovery file at {}: {}",
+            recovery_location.to_string_lossy(),
+            e
+        );
+    }
+}
+
+/// Discard the recovery file of the recovery id
+pub fn discard_recovery_project(recovery_id: String) -> Result<(), String> {
+    validate_recovery_id(&recovery_id)?;
+
+    remove_recovery_file(&recovery_id);
+
+    return Ok(());
+}
+
+/// Get the projects that have recovery files with changes newer than their saved project file
+pub fn get_recovery_projects() -> Result<Vec<RecoveryProject>, String> {
+    let recovery_directory = get_recovery_directory();
+
+    // nothing was ever autosaved
+    if !recovery_directory.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = match std::fs::read_dir(&recovery_directory) {
+        Ok(entries) => entries,
+        Err(e) => {
+            return Err(format!(
+                "Unable to read recovery directory {}: {}",
+                recovery_directory.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    let mut recovery_projects = Vec::<RecoveryProject>::new();
+
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(_) => continue,
+        };
+
+        if path.extension() != Some(OsStr::new("recovery")) {
+            continue;
+        }
+
+        let recovery_id = match path.file_stem() {
+            Some(stem) => stem.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        let recovery_modified_at = match std::fs::metadata(&path).and_then(|m| m.modified()) {
+            Ok(modified_at) => modified_at,
+            Err(_) => continue,
+        };
+
+        let project_name = recovery_id
+            .strip_prefix("project.")
+            .map(|project_name| project_name.to_string());
+
+        // only offer recoveries newer than the saved project
+        if let Some(project_name) = &project_name {
+            let project_location =
+                get_project_directory_location_from_name(project_name.to_owned());
+
+            if let Ok(project_modified_at) =
+                std::fs::metadata(&project_location).and_then(|m| m.modified())
+            {
+                if project_modified_at >= recovery_modified_at {
+                    continue;
+                }
+            }
+        }
+
+        let modified_at = match recovery_modified_at.duration_since(UNIX_EPOCH) {
+            Ok(duration) => duration.as_secs(),
+            Err(_) => 0,
+        };
+
+        recovery_projects.push(RecoveryProject {
+            recovery_id: recovery_id,
+            project_name: project_name,
+            modified_at: modified_at,
+        });
+    }
+
+    // newest first
+    recovery_projects.sort_by(|a, b| b.modified_at.cmp(&a.modified_at));
+
+    return Ok(recovery_projects);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        core_components::snippet_manager::SnippetManager,
+        core_services::{
+            directory_manager::DirectoryManager,
+            secret_store_service::{
+                get_project_secret_store_location_from_name, read_secret_store,
+            },
+        },
+        state_management::{
+            external_snippet_manager::{
+                ExternalSnippetManager, PackagePath, BUILTIN_CONSTANT_VALUE, BUILTIN_REROUTE_INPUT,
+                BUILTIN_REROUTE_OUTPUT,
+            },
+            test_support::{add_constant_into_reroute, create_builtin_external_snippet_manager},
+        },
+        utils::sequential_id_generator::{SequentialIdGenerator, Uuid},
+    };
+
+    use super::{
+        discard_recovery_project, get_project_directory_location_from_name,
+        get_recovery_file_location, get_recovery_projects, migrate_plan, read_plan_from_file,
+        serialize_plan, validate_recovery_id, write_serialized_plan_to_file, BuildSnippetAction,
+        BuildSnippetParameterAction, BuildSnippetPipelineAction, LegacyBincodePlan, Plan,
+        PlanActions, ProjectManager, PROJECT_FORMAT_VERSION,
+    };
+
+    #[test]
+    /// saving a project and opening it again gives back the same snippets, pipelines,
+    /// parameter values and positions
+    fn test_save_and_open_project() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+        let project_name = format!("test_save_and_open_project_{}", std::process::id());
+
+        let mut project_manager = ProjectManager::new();
+        add_constant_into_reroute(
+            &mut project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            "42",
+        );
+
+        assert!(project_manager.has_unsaved_changes(&external_snippet_manager));
+
+        project_manager
+            .save_project(&external_snippet_manager, project_name.to_owned())
+            .unwrap();
+
+        assert!(!project_manager.has_unsaved_changes(&external_snippet_manager));
+
+        let mut opened_project_manager = ProjectManager::new();
+        opened_project_manager
+            .open_project(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                project_name.to_owned(),
+                &Vec::new(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            opened_project_manager.get_project_name(),
+            Some(project_name.to_owned())
+        );
+        assert!(!opened_project_manager.has_unsaved_changes(&external_snippet_manager));
+
+        let snippet_manager = &opened_project_manager.snippet_manager;
+        let snippets = snippet_manager.get_snippets_as_ref();
+
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippet_manager.get_pipelines_as_ref().len(), 1);
+
+        let constant_snippet = snippets
+            .iter()
+            .find(|snippet| snippet.get_name() == "constant_integer")
+            .unwrap();
+        let reroute_snippet = snippets
+            .iter()
+            .find(|snippet| snippet.get_name() == "reroute")
+            .unwrap();
+
+        assert_eq!(constant_snippet.get_position(), (10.0, 20.0));
+        assert_eq!(reroute_snippet.get_position(), (110.0, 40.0));
+
+        assert_eq!(
+            constant_snippet
+                .find_parameter_from_name(BUILTIN_CONSTANT_VALUE.to_string())
+                .unwrap()
+                .get_storage()
+                .to_string(),
+            "42"
+        );
+
+        // the pipeline goes from the constant value into the reroute input
+        let pipeline = snippet_manager.get_pipelines_as_ref()[0];
+
+        assert_eq!(
+            constant_snippet
+                .find_pipeline_connector(pipeline.get_from_pipeline_connector_uuid())
+                .unwrap()
+                .get_name(),
+            BUILTIN_CONSTANT_VALUE
+        );
+        assert_eq!(
+            reroute_snippet
+                .find_pipeline_connector(pipeline.get_to_pipeline_connector_uuid())
+                .unwrap()
+                .get_name(),
+            BUILTIN_REROUTE_INPUT
+        );
+        assert!(reroute_snippet
+            .find_pipeline_connector_from_name_and_input(BUILTIN_REROUTE_OUTPUT.to_string(), false)
+            .is_some());
+
+        project_manager.delete_project(project_name);
+    }
+
+    #[test]
+    /// saving an opened project without changing it writes the same project file,
+    /// even though its snippets got new uuids when it was opened
+    fn test_save_opened_project_gives_the_same_file() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+        let project_name = format!("test_save_opened_project_{}", std::process::id());
+
+        // the pairs are at the same positions, only the constant values tell them apart
+        let mut project_manager = ProjectManager::new();
+        for value in ["42", "7"] {
+            add_constant_into_reroute(
+                &mut project_manager.snippet_manager,
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                value,
+            );
+        }
+
+        project_manager
+            .save_project(&external_snippet_manager, project_name.to_owned())
+            .unwrap();
+
+        let project_location = get_project_directory_location_from_name(project_name.to_owned());
+        let saved_project = std::fs::read(&project_location).unwrap();
+
+        let mut opened_project_manager = ProjectManager::new();
+        opened_project_manager
+            .open_project(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                project_name.to_owned(),
+                &Vec::new(),
+            )
+            .unwrap();
+        opened_project_manager
+            .save_project(&external_snippet_manager, project_name.to_owned())
+            .unwrap();
+
+        assert_eq!(std::fs::read(&project_location).unwrap(), saved_project);
+
+        project_manager.delete_project(project_name);
+    }
+
+    /// plan of a constant integer snippet piped into a reroute snippet, with the actions
+    /// in the order given by reversed
+    fn create_constant_into_reroute_plan(reversed: bool) -> Plan {
+        let constant_package_path = PackagePath::from("builtin.constant_integer".to_string());
+        let reroute_package_path = PackagePath::from("builtin.reroute".to_string());
+
+        let mut plan = Plan::default();
+
+        plan.actions.build_snippet_actions = vec![
+            BuildSnippetAction {
+                package_path: reroute_package_path.to_owned(),
+                original_uuid: 2,
+                x_position: 110.0,
+                y_position: 40.0,
+            },
+            BuildSnippetAction {
+                package_path: constant_package_path.to_owned(),
+                original_uuid: 1,
+                x_position: 10.0,
+                y_position: 20.0,
+            },
+            BuildSnippetAction {
+                package_path: constant_package_path.to_owned(),
+                original_uuid: 3,
+                x_position: 10.0,
+                y_position: 80.0,
+            },
+        ];
+
+        plan.actions.build_snippet_pipeline_actions = vec![
+            BuildSnippetPipelineAction {
+                from_snippet_package_path: constant_package_path.to_owned(),
+                from_snippet_original_uuid: 3,
+                from_snippet_connector_name: BUILTIN_CONSTANT_VALUE.to_string(),
+                to_snippet_package_path: reroute_package_path.to_owned(),
+                to_snippet_original_uuid: 2,
+                to_snippet_connector_name: BUILTIN_REROUTE_INPUT.to_string(),
+            },
+            BuildSnippetPipelineAction {
+                from_snippet_package_path: constant_package_path.to_owned(),
+                from_snippet_original_uuid: 1,
+                from_snippet_connector_name: BUILTIN_CONSTANT_VALUE.to_string(),
+                to_snippet_package_path: reroute_package_path.to_owned(),
+                to_snippet_original_uuid: 2,
+                to_snippet_connector_name: BUILTIN_REROUTE_INPUT.to_string(),
+            },
+        ];
+
+        plan.actions.build_snippet_parameter_actions = vec![
+            BuildSnippetParameterAction {
+                snippet_package_path: constant_package_path.to_owned(),
+                snippet_original_uuid: 3,
+                parameter_name: BUILTIN_CONSTANT_VALUE.to_string(),
+                parameter_value: "7".to_string(),
+            },
+            BuildSnippetParameterAction {
+                snippet_package_path: constant_package_path.to_owned(),
+                snippet_original_uuid: 1,
+                parameter_name: BUILTIN_CONSTANT_VALUE.to_string(),
+                parameter_value: "42".to_string(),
+            },
+        ];
+
+        if reversed {
+            plan.actions.build_snippet_actions.reverse();
+            plan.actions.build_snippet_pipeline_actions.reverse();
+            plan.actions.build_snippet_parameter_actions.reverse();
+        }
+
+        return plan;
+    }
+
+    #[test]
+    /// a plan written as json reads back the same
+    fn test_json_plan_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "snippet_test_builder_json_plan_{}.project",
+            std::process::id()
+        ));
+
+        let plan = create_constant_into_reroute_plan(false);
+        let serialized_plan = serialize_plan(&plan).unwrap();
+
+        write_serialized_plan_to_file(&serialized_plan, path.to_owned()).unwrap();
+
+        let read_plan = read_plan_from_file(path.to_owned()).unwrap();
+
+        assert_eq!(read_plan.format_version, PROJECT_FORMAT_VERSION);
+        assert_eq!(read_plan.actions.build_snippet_actions.len(), 3);
+        assert_eq!(read_plan.actions.build_snippet_pipeline_actions.len(), 2);
+        assert_eq!(read_plan.actions.build_snippet_parameter_actions.len(), 2);
+        assert_eq!(serialize_plan(&read_plan).unwrap(), serialized_plan);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    /// the same actions in any order are sorted into the same plan
+    fn test_sort_actions() {
+        let mut plan = create_constant_into_reroute_plan(false);
+        let mut reversed_plan = create_constant_into_reroute_plan(true);
+
+        assert!(serialize_plan(&plan).unwrap() != serialize_plan(&reversed_plan).unwrap());
+
+        plan.sort_actions();
+        reversed_plan.sort_actions();
+
+        assert_eq!(
+            serialize_plan(&plan).unwrap(),
+            serialize_plan(&reversed_plan).unwrap()
+        );
+
+        // snippets by package path, then original uuid
+        let snippet_order: Vec<Uuid> = plan
+            .actions
+            .build_snippet_actions
+            .iter()
+            .map(|action| action.original_uuid)
+            .collect();
+
+        assert_eq!(snippet_order, vec![1, 3, 2]);
+
+        // pipelines and parameters by the original uuid of their snippet
+        assert_eq!(
+            plan.actions.build_snippet_pipeline_actions[0].from_snippet_original_uuid,
+            1
+        );
+        assert_eq!(
+            plan.actions.build_snippet_parameter_actions[0].snippet_original_uuid,
+            1
+        );
+    }
+
+    #[test]
+    /// project files written in the legacy bincode format are still opened, even the ones
+    /// starting with the same byte as a json object
+    fn test_read_legacy_bincode_plan() {
+        let path = std::env::temp_dir().join(format!(
+            "snippet_test_builder_legacy_plan_{}.project",
+            std::process::id()
+        ));
+
+        // 123 snippets makes the length prefix of the bincode file start with '{'
+        let mut actions = PlanActions::default();
+
+        for i in 0..123 {
+            actions.build_snippet_actions.push(BuildSnippetAction {
+                package_path: PackagePath::from("builtin.reroute".to_string()),
+                original_uuid: i,
+                x_position: i as f64,
+                y_position: 0.0,
+            });
+        }
+
+        let serialized_plan = bincode::serialize(&LegacyBincodePlan { actions: actions }).unwrap();
+
+        assert_eq!(serialized_plan[0], b'{');
+
+        std::fs::write(&path, &serialized_plan).unwrap();
+
+        let read_plan = read_plan_from_file(path.to_owned()).unwrap();
+
+        assert_eq!(read_plan.format_version, PROJECT_FORMAT_VERSION);
+        assert_eq!(read_plan.actions.build_snippet_actions.len(), 123);
+        assert_eq!(
+            read_plan.actions.build_snippet_actions[122].original_uuid,
+            122
+        );
+        assert_eq!(
+            read_plan.actions.build_snippet_actions[122].x_position,
+            122.0
+        );
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    /// unversioned plans are upgraded through every migration to the current format
+    fn test_migrate_plan_from_unversioned() {
+        let plan = serde_json::json!({
+            "actions": {
+                "build_snippet_actions": [],
+                "build_snippet_pipeline_actions": [],
+                "build_snippet_parameter_actions": []
+            }
+        });
+
+        let migrated_plan = migrate_plan(plan).unwrap();
+
+        assert_eq!(
+            migrated_plan["format_version"],
+            serde_json::Value::from(PROJECT_FORMAT_VERSION)
+        );
+        assert_eq!(migrated_plan["variable_profiles"], serde_json::json!({}));
+
+        let plan: Plan = serde_json::from_value(migrated_plan).unwrap();
+
+        assert_eq!(plan.format_version, PROJECT_FORMAT_VERSION);
+    }
+
+    #[test]
+    /// version 1 plans only gain the variable profiles, keeping their actions
+    fn test_migrate_plan_from_v1() {
+        let plan = serde_json::json!({
+            "format_version": 1,
+            "actions": {
+                "build_snippet_actions": [{
+                    "package_path": { "path": "builtin.reroute" },
+                    "original_uuid": 4,
+                    "x_position": 1.0,
+                    "y_position": 2.0
+                }],
+                "build_snippet_pipeline_actions": [],
+                "build_snippet_parameter_actions": []
+            }
+        });
+
+        let migrated_plan = migrate_plan(plan.clone()).unwrap();
+
+        assert_eq!(migrated_plan["format_version"], serde_json::Value::from(2));
+        assert_eq!(migrated_plan["variable_profiles"], serde_json::json!({}));
+        assert_eq!(migrated_plan["actions"], plan["actions"]);
+
+        // current plans are left as they are
+        assert_eq!(migrate_plan(migrated_plan.clone()).unwrap(), migrated_plan);
+    }
+
+    #[test]
+    /// plans of a newer format, or without a valid format version, are refused
+    fn test_migrate_plan_invalid_format_version() {
+        let newer_plan = serde_json::json!({
+            "format_version": PROJECT_FORMAT_VERSION + 1,
+            "actions": {}
+        });
+
+        assert!(migrate_plan(newer_plan)
+            .unwrap_err()
+            .contains("newer version of the app"));
+
+        let text_version_plan = serde_json::json!({
+            "format_version": "2",
+            "actions": {}
+        });
+
+        assert!(migrate_plan(text_version_plan).is_err());
+
+        let negative_version_plan = serde_json::json!({
+            "format_version": -1,
+            "actions": {}
+        });
+
+        assert!(migrate_plan(negative_version_plan).is_err());
+
+        // without a format version the plan is unversioned, which must be an object
+        assert!(migrate_plan(serde_json::json!([])).is_err());
+    }
+
+    #[test]
+    /// unsaved changes are autosaved into a recovery file, which is listed until it is discarded
+    fn test_autosave_and_discard_recovery() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+
+        let mut project_manager = ProjectManager::new();
+        let recovery_id = project_manager.get_recovery_id();
+        let recovery_location = get_recovery_file_location(&recovery_id);
+
+        // nothing to recover in an empty project
+        project_manager
+            .autosave_project(&external_snippet_manager)
+            .unwrap();
+
+        assert!(!recovery_location.exists());
+
+        add_constant_into_reroute(
+            &mut project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            "42",
+        );
+
+        project_manager
+            .autosave_project(&external_snippet_manager)
+            .unwrap();
+
+        assert!(recovery_location.exists());
+
+        let recovery_project = get_recovery_projects()
+            .unwrap()
+            .into_iter()
+            .find(|recovery_project| recovery_project.recovery_id == recovery_id)
+            .unwrap();
+
+        assert!(recovery_project.project_name.is_none());
+
+        // the recovery file is the plan of the project
+        let recovered_plan = read_plan_from_file(recovery_location.to_owned()).unwrap();
+
+        assert_eq!(recovered_plan.actions.build_snippet_actions.len(), 2);
+
+        discard_recovery_project(recovery_id.to_owned()).unwrap();
+
+        assert!(!recovery_location.exists());
+        assert!(get_recovery_projects()
+            .unwrap()
+            .iter()
+            .all(|recovery_project| recovery_project.recovery_id != recovery_id));
+    }
+
+    #[test]
+    /// recovery ids come from the front end, and must not leave the recovery directory
+    fn test_validate_recovery_id() {
+        assert!(validate_recovery_id(&"untitled.1234".to_string()).is_ok());
+        assert!(validate_recovery_id(&"project.folder.name".to_string()).is_ok());
+
+        assert!(validate_recovery_id(&String::new()).is_err());
+        assert!(validate_recovery_id(&"../untitled.1234".to_string()).is_err());
+        assert!(validate_recovery_id(&"project..name".to_string()).is_err());
+        assert!(validate_recovery_id(&"folder/untitled".to_string()).is_err());
+        assert!(validate_recovery_id(&"folder\\untitled".to_string()).is_err());
+
+        assert!(discard_recovery_project("../projects".to_string()).is_err());
+    }
+
+    #[test]
+    /// opening a project in place of an untitled project removes the recovery file of the
+    /// untitled project
+    fn test_open_project_removes_replaced_recovery() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+        let project_name = format!(
+            "test_open_project_removes_replaced_recovery_{}",
+            std::process::id()
+        );
+
+        // save an empty project to open
+        let mut saved_project_manager = ProjectManager::new();
+        saved_project_manager
+            .save_project(&external_snippet_manager, project_name.to_owned())
+            .unwrap();
+
+        let mut project_manager = ProjectManager::new();
+        add_constant_into_reroute(
+            &mut project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            "42",
+        );
+        project_manager
+            .autosave_project(&external_snippet_manager)
+            .unwrap();
+
+        let untitled_recovery_location =
+            get_recovery_file_location(&project_manager.get_recovery_id());
+
+        assert!(untitled_recovery_location.exists());
+
+        project_manager
+            .open_project(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                project_name.to_owned(),
+                &Vec::new(),
+            )
+            .unwrap();
+
+        assert!(!untitled_recovery_location.exists());
+        assert_eq!(
+            project_manager.snippet_manager.get_snippets_as_ref().len(),
+            0
+        );
+
+        saved_project_manager.delete_project(project_name);
+    }
+
+    #[test]
+    /// restoring the recovery file of a saved project gives back the secrets of the project,
+    /// which the recovery file leaves out, so saving it again keeps them
+    fn test_restore_recovery_project_keeps_secrets() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let project_name = format!(
+            "test_restore_recovery_project_keeps_secrets_{}",
+            std::process::id()
+        );
+
+        // snippet with a secret parameter
+        let snippets_directory = std::env::temp_dir().join(format!("{}_snippets", project_name));
+        let root_directory = snippets_directory.join("root");
+        let app_file = root_directory.join("main").join("secret").join("app.py");
+
+        std::fs::create_dir_all(app_file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &app_file,
+            "def init(*args, **kwargs):\n    snippet = args[0]\n    snippet.add_parameter(\"token\", \"Secret\")\n    return snippet\n",
+        )
+        .unwrap();
+
+        let mut directory_manager = DirectoryManager::default();
+        directory_manager
+            .initialize(
+                &root_directory.to_string_lossy().to_string(),
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let mut external_snippet_manager = ExternalSnippetManager::default();
+        external_snippet_manager
+            .create_external_snippets_from_directory(
+                &directory_manager,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let package_path = PackagePath::from("main.secret".to_string());
+        let external_snippet = external_snippet_manager
+            .find_external_snippet_from_package_path(&package_path)
+            .unwrap();
+
+        let mut project_manager = ProjectManager::new();
+        let snippet_uuid = project_manager.snippet_manager.new_snippet(
+            &mut sequential_id_generator,
+            external_snippet,
+            0.0,
+            0.0,
+        );
+        let parameter_uuid = project_manager
+            .snippet_manager
+            .find_snippet(&snippet_uuid)
+            .unwrap()
+            .find_parameter_from_name("token".to_string())
+            .unwrap()
+            .get_uuid();
+        project_manager
+            .snippet_manager
+            .find_parameter(&parameter_uuid)
+            .unwrap()
+            .update_value("hunter2".to_string())
+            .unwrap();
+
+        project_manager
+            .save_project(&external_snippet_manager, project_name.to_owned())
+            .unwrap();
+
+        // an unsaved change, after the snippet with the secret
+        project_manager.snippet_manager.new_snippet(
+            &mut sequential_id_generator,
+            external_snippet,
+            100.0,
+            0.0,
+        );
+        project_manager
+            .autosave_project(&external_snippet_manager)
+            .unwrap();
+
+        let mut restored_project_manager = ProjectManager::new();
+        restored_project_manager
+            .restore_recovery_project(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                format!("project.{}", project_name),
+                &Vec::new(),
+            )
+            .unwrap();
+
+        restored_project_manager
+            .save_project(&external_snippet_manager, project_name.to_owned())
+            .unwrap();
+
+        let secret_store =
+            read_secret_store(&get_project_secret_store_location_from_name(&project_name)).unwrap();
+
+        assert_eq!(
+            secret_store.find_secret(&package_path, 1, &"token".to_string()),
+            Some(&"hunter2".to_string())
+        );
+
+        restored_project_manager.delete_project(project_name);
+        std::fs::remove_dir_all(&snippets_directory).unwrap();
+    }
+
+    #[test]
+    /// pasting copied snippets into another project gives new snippets, with the pipelines
+    /// between them and their parameter values, which is undone as a whole
+    fn test_copy_and_paste_snippets() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+
+        let mut project_manager = ProjectManager::new();
+        let (constant_uuid, reroute_uuid) = add_constant_into_reroute(
+            &mut project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            "42",
+        );
+
+        let clipboard = project_manager
+            .copy_snippets(
+                &external_snippet_manager,
+                &vec![constant_uuid, reroute_uuid],
+            )
+            .unwrap();
+
+        let mut pasted_project_manager = ProjectManager::new();
+        let front_graph_changes = pasted_project_manager
+            .paste_snippets(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                &clipboard,
+                5.0,
+                10.0,
+            )
+            .unwrap();
+
+        assert_eq!(front_graph_changes.added_snippets.len(), 2);
+        assert_eq!(front_graph_changes.added_pipelines.len(), 1);
+
+        let snippet_manager = &pasted_project_manager.snippet_manager;
+        let snippets = snippet_manager.get_snippets_as_ref();
+
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippet_manager.get_pipelines_as_ref().len(), 1);
+
+        // pasted snippets are new snippets
+        assert!(snippets.iter().all(
+            |snippet| snippet.get_uuid() != constant_uuid && snippet.get_uuid() != reroute_uuid
+        ));
+
+        let constant_snippet = snippets
+            .iter()
+            .find(|snippet| snippet.get_name() == "constant_integer")
+            .unwrap();
+
+        assert_eq!(constant_snippet.get_position(), (15.0, 30.0));
+        assert_eq!(
+            constant_snippet
+                .find_parameter_from_name(BUILTIN_CONSTANT_VALUE.to_string())
+                .unwrap()
+                .get_storage()
+                .to_string(),
+            "42"
+        );
+
+        // the copied snippets are left as they are
+        assert_eq!(
+            project_manager.snippet_manager.get_snippets_as_ref().len(),
+            2
+        );
+
+        pasted_project_manager
+            .history_manager
+            .undo(
+                &mut pasted_project_manager.snippet_manager,
+                &mut pasted_project_manager.visual_component_manager,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        assert_eq!(
+            pasted_project_manager
+                .snippet_manager
+                .get_snippets_as_ref()
+                .len(),
+            0
+        );
+        assert_eq!(
+            pasted_project_manager
+                .snippet_manager
+                .get_pipelines_as_ref()
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    /// pipelines to snippets that were not copied are not pasted
+    fn test_paste_drops_external_pipelines() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+
+        let mut project_manager = ProjectManager::new();
+        let (_, reroute_uuid) = add_constant_into_reroute(
+            &mut project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            "42",
+        );
+
+        let clipboard = project_manager
+            .copy_snippets(&external_snippet_manager, &vec![reroute_uuid])
+            .unwrap();
+
+        let front_graph_changes = project_manager
+            .paste_snippets(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                &clipboard,
+                0.0,
+                50.0,
+            )
+            .unwrap();
+
+        assert_eq!(front_graph_changes.added_snippets.len(), 1);
+        assert_eq!(front_graph_changes.added_pipelines.len(), 0);
+
+        assert_eq!(
+            project_manager.snippet_manager.get_snippets_as_ref().len(),
+            3
+        );
+        assert_eq!(
+            project_manager.snippet_manager.get_pipelines_as_ref().len(),
+            1
+        );
+    }
+
+    #[test]
+    /// duplicating snippets pastes their copy into the same project, next to them
+    fn test_duplicate_snippets() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+
+        let mut project_manager = ProjectManager::new();
+        let (constant_uuid, reroute_uuid) = add_constant_into_reroute(
+            &mut project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            "42",
+        );
+
+        let clipboard = project_manager
+            .copy_snippets(
+                &external_snippet_manager,
+                &vec![constant_uuid, reroute_uuid],
+            )
+            .unwrap();
+
+        project_manager
+            .paste_snippets(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                &clipboard,
+                20.0,
+                20.0,
+            )
+            .unwrap();
+
+        let snippet_manager = &project_manager.snippet_manager;
+
+        assert_eq!(snippet_manager.get_snippets_as_ref().len(), 4);
+        assert_eq!(snippet_manager.get_pipelines_as_ref().len(), 2);
+
+        // the duplicates are connected to each other, not to the snippets they were copied from
+        let duplicate_reroute_snippet = snippet_manager
+            .get_snippets_as_ref()
+            .into_iter()
+            .find(|snippet| snippet.get_name() == "reroute" && snippet.get_uuid() != reroute_uuid)
+            .unwrap();
+
+        assert_eq!(duplicate_reroute_snippet.get_position(), (130.0, 60.0));
+
+        let duplicate_pipeline_uuids =
+            snippet_manager.find_pipeline_uuids_from_snippet(&duplicate_reroute_snippet.get_uuid());
+
+        assert_eq!(duplicate_pipeline_uuids.len(), 1);
+
+        let duplicate_pipeline = snippet_manager
+            .find_pipeline(&duplicate_pipeline_uuids[0])
+            .unwrap();
+        let from_snippet_uuid = snippet_manager
+            .find_snippet_uuid_from_pipeline_connector(
+                &duplicate_pipeline.get_from_pipeline_connector_uuid(),
+            )
+            .unwrap();
+
+        assert!(from_snippet_uuid != constant_uuid);
+    }
+
+    /// find the uuid of the snippet on the other end of the only pipeline of the connector
+    fn find_connected_snippet_uuid(
+        snippet_manager: &SnippetManager,
+        snippet_uuid: Uuid,
+        connector_name: &str,
+        input: bool,
+    ) -> Uuid {
+        let pipeline_connector_uuid = snippet_manager
+            .find_snippet(&snippet_uuid)
+            .unwrap()
+            .find_pipeline_connector_from_name_and_input(connector_name.to_string(), input)
+            .unwrap()
+            .get_uuid();
+
+        let pipeline_uuids =
+            snippet_manager.find_pipeline_uuids_from_pipeline_connector(&pipeline_connector_uuid);
+
+        assert_eq!(pipeline_uuids.len(), 1);
+
+        let pipeline = snippet_manager.find_pipeline(&pipeline_uuids[0]).unwrap();
+
+        let other_pipeline_connector_uuid = if input {
+            pipeline.get_from_pipeline_connector_uuid()
+        } else {
+            pipeline.get_to_pipeline_connector_uuid()
+        };
+
+        return snippet_manager
+            .find_snippet_uuid_from_pipeline_connector(&other_pipeline_connector_uuid)
+            .unwrap();
+    }
+
+    #[test]
+    /// collapsed snippets are replaced by the composite snippet, with the pipelines crossing
+    /// into them reconnected to it, including composite snippets collapsed into another one
+    fn test_collapse_snippets_into_composite() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let mut external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+
+        // constant -> reroute -> last reroute
+        let mut project_manager = ProjectManager::new();
+        let (constant_uuid, reroute_uuid) = add_constant_into_reroute(
+            &mut project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            "42",
+        );
+
+        let reroute_external_snippet = external_snippet_manager
+            .find_external_snippet_from_package_path(&PackagePath::from(
+                "builtin.reroute".to_string(),
+            ))
+            .unwrap();
+        let last_reroute_uuid = project_manager.snippet_manager.new_snippet(
+            &mut sequential_id_generator,
+            reroute_external_snippet,
+            210.0,
+            40.0,
+        );
+        let from_pipeline_connector_uuid = project_manager
+            .snippet_manager
+            .find_snippet(&reroute_uuid)
+            .unwrap()
+            .find_pipeline_connector_from_name_and_input(BUILTIN_REROUTE_OUTPUT.to_string(), false)
+            .unwrap()
+            .get_uuid();
+        let to_pipeline_connector_uuid = project_manager
+            .snippet_manager
+            .find_snippet(&last_reroute_uuid)
+            .unwrap()
+            .find_pipeline_connector_from_name_and_input(BUILTIN_REROUTE_INPUT.to_string(), true)
+            .unwrap()
+            .get_uuid();
+        project_manager
+            .snippet_manager
+            .create_pipeline(
+                &mut sequential_id_generator,
+                from_pipeline_connector_uuid,
+                to_pipeline_connector_uuid,
+            )
+            .unwrap();
+
+        // draw the project, as the collapse is applied to the front as well
+        project_manager
+            .create_front_project_content(&mut sequential_id_generator)
+            .unwrap();
+
+        // collapse the middle reroute, with a pipeline into and out of it
+        let inner_definition = project_manager
+            .create_composite_snippet_definition(&external_snippet_manager, &vec![reroute_uuid])
+            .unwrap();
+
+        assert_eq!(
+            inner_definition.get_input_names(),
+            vec!["input".to_string()]
+        );
+        assert_eq!(
+            inner_definition.get_output_names(),
+            vec!["output".to_string()]
+        );
+
+        let directory_uuid = sequential_id_generator.get_id();
+        let inner_external_snippet_uuid = external_snippet_manager
+            .create_snippet_from_composite_snippet_definition(
+                &inner_definition,
+                "inner".to_string(),
+                PackagePath::from("composites.inner".to_string()),
+                directory_uuid,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let front_graph_changes = project_manager
+            .collapse_snippets_into_composite(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                &inner_definition,
+                external_snippet_manager
+                    .find_external_snippet(inner_external_snippet_uuid)
+                    .unwrap(),
+                &vec![reroute_uuid],
+            )
+            .unwrap();
+
+        assert_eq!(front_graph_changes.removed_snippets.len(), 1);
+        assert_eq!(front_graph_changes.added_snippets.len(), 1);
+        assert_eq!(front_graph_changes.removed_pipelines.len(), 2);
+        assert_eq!(front_graph_changes.added_pipelines.len(), 2);
+
+        let snippet_manager = &project_manager.snippet_manager;
+
+        assert_eq!(snippet_manager.get_snippets_as_ref().len(), 3);
+        assert_eq!(snippet_manager.get_pipelines_as_ref().len(), 2);
+        assert!(snippet_manager.find_snippet(&reroute_uuid).is_none());
+
+        let inner_uuid = snippet_manager
+            .get_snippets_as_ref()
+            .into_iter()
+            .find(|snippet| snippet.get_name() == "inner")
+            .unwrap()
+            .get_uuid();
+
+        // placed where the collapsed snippet was, and rewired into and out of it
+        assert_eq!(
+            snippet_manager
+                .find_snippet(&inner_uuid)
+                .unwrap()
+                .get_position(),
+            (110.0, 40.0)
+        );
+        assert_eq!(
+            find_connected_snippet_uuid(snippet_manager, inner_uuid, "input", true),
+            constant_uuid
+        );
+        assert_eq!(
+            find_connected_snippet_uuid(snippet_manager, inner_uuid, "output", false),
+            last_reroute_uuid
+        );
+
+        // collapse the constant and the composite snippet into another composite snippet
+        let outer_definition = project_manager
+            .create_composite_snippet_definition(
+                &external_snippet_manager,
+                &vec![constant_uuid, inner_uuid],
+            )
+            .unwrap();
+
+        assert_eq!(outer_definition.get_input_names().len(), 0);
+        assert_eq!(
+            outer_definition.get_output_names(),
+            vec!["output".to_string()]
+        );
+
+        let directory_uuid = sequential_id_generator.get_id();
+        let outer_external_snippet_uuid = external_snippet_manager
+            .create_snippet_from_composite_snippet_definition(
+                &outer_definition,
+                "outer".to_string(),
+                PackagePath::from("composites.outer".to_string()),
+                directory_uuid,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        project_manager
+            .collapse_snippets_into_composite(
+                &external_snippet_manager,
+                &mut sequential_id_generator,
+                &outer_definition,
+                external_snippet_manager
+                    .find_external_snippet(outer_external_snippet_uuid)
+                    .unwrap(),
+                &vec![constant_uuid, inner_uuid],
+            )
+            .unwrap();
+
+        let snippet_manager = &project_manager.snippet_manager;
+
+        assert_eq!(snippet_manager.get_snippets_as_ref().len(), 2);
+        assert_eq!(snippet_manager.get_pipelines_as_ref().len(), 1);
+
+        let outer_uuid = snippet_manager
+            .get_snippets_as_ref()
+            .into_iter()
+            .find(|snippet| snippet.get_name() == "outer")
+            .unwrap()
+            .get_uuid();
+
+        assert_eq!(
+            snippet_manager
+                .find_snippet(&outer_uuid)
+                .unwrap()
+                .get_position(),
+            (10.0, 20.0)
+        );
+        assert_eq!(
+            find_connected_snippet_uuid(snippet_manager, outer_uuid, "output", false),
+            last_reroute_uuid
+        );
+
+        // undoing both collapses gives back the snippets and pipelines they were made from
+        for _ in 0..2 {
+            project_manager
+                .history_manager
+                .undo(
+                    &mut project_manager.snippet_manager,
+                    &mut project_manager.visual_component_manager,
+                    &mut sequential_id_generator,
+                )
+                .unwrap();
+        }
+
+        let snippet_manager = &project_manager.snippet_manager;
+
+        assert_eq!(snippet_manager.get_snippets_as_ref().len(), 3);
+        assert_eq!(snippet_manager.get_pipelines_as_ref().len(), 2);
+        assert_eq!(
+            find_connected_snippet_uuid(snippet_manager, reroute_uuid, BUILTIN_REROUTE_INPUT, true),
+            constant_uuid
+        );
+        assert_eq!(
+            find_connected_snippet_uuid(
+                snippet_manager,
+                reroute_uuid,
+                BUILTIN_REROUTE_OUTPUT,
+                false
+            ),
+            last_reroute_uuid
+        );
+    }
+}
@@ -0,0 +1,675 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsStr,
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::utils::sequential_id_generator::Uuid;
+
+use super::{runtime_logging_service::LogEntry, secret_store_service::redact_secrets};
+
+// extension of the run record files
+const RUN_RECORD_EXTENSION: &str = "run";
+
+// max number of characters of the preview of an output value
+pub const OUTPUT_PREVIEW_MAX_LENGTH: usize = 200;
+
+/// Record of a run of a project, persisted once the run is done
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunRecord {
+    run_id: String,
+    // milliseconds since unix epoch
+    started_at: u64,
+    ended_at: u64,
+    // hash of the snippets and pipelines that were ran, regardless of their ids and positions
+    graph_hash: String,
+    status: RunStatus,
+    parameter_values: Vec<RunParameterValue>,
+    // snippets in the order they were built
+    snippets: Vec<SnippetRunRecord>,
+    logs: Vec<LogEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum RunStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+pub struct RunParameterValue {
+    snippet_name: String,
+    parameter_name: String,
+    value: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SnippetRunRecord {
+    // uuid of the snippet in the runner, only meaningful while the run is recorded
+    #[serde(skip)]
+    snippet_uuid: Uuid,
+    // visual snippet, only meaningful for the window session the run was done in
+    snippet_id: Uuid,
+    snippet_name: String,
+    status: SnippetRunStatus,
+    output_previews: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum SnippetRunStatus {
+    // the run stopped before reaching the snippet
+    NotRun,
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+//struct for the json serialization of a run in the run history
+#[derive(Serialize)]
+pub struct FrontRunSummary {
+    run_id: String,
+    started_at: u64,
+    ended_at: u64,
+    status: RunStatus,
+    graph_hash: String,
+}
+
+//struct for the json serialization of the comparison of two runs
+#[derive(Serialize)]
+pub struct FrontRunComparison {
+    left: FrontRunSummary,
+    right: FrontRunSummary,
+    graph_changed: bool,
+    parameter_changes: Vec<FrontParameterChange>,
+    // snippets whose status or outputs differ, none if the snippet is not in the run
+    snippet_changes: Vec<FrontSnippetRunChange>,
+}
+
+#[derive(Serialize)]
+pub struct FrontParameterChange {
+    snippet_name: String,
+    parameter_name: String,
+    left_value: Option<String>,
+    right_value: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct FrontSnippetRunChange {
+    snippet_name: String,
+    left_status: Option<SnippetRunStatus>,
+    right_status: Option<SnippetRunStatus>,
+    changed_outputs: Vec<String>,
+}
+
+impl RunRecord {
+    /// create the record of a run that is about to start, with none of its snippets ran yet
+    pub fn new(
+        graph_hash: String,
+        parameter_values: Vec<RunParameterValue>,
+        snippets: Vec<SnippetRunRecord>,
+    ) -> Self {
+        let started_at = get_timestamp_millis();
+
+        return RunRecord {
+            run_id: started_at.to_string(),
+            started_at: started_at,
+            ended_at: started_at,
+            graph_hash: graph_hash,
+            status: RunStatus::Running,
+            parameter_values: parameter_values,
+            snippets: snippets,
+            logs: Vec::new(),
+        };
+    }
+
+    /// record the outcome of a snippet of the run
+    pub fn set_snippet_result(
+        &mut self,
+        snippet_uuid: Uuid,
+        status: SnippetRunStatus,
+        output_previews: BTreeMap<String, String>,
+    ) {
+        if let Some(snippet_record) = self
+            .snippets
+            .iter_mut()
+            .find(|snippet_record| snippet_record.snippet_uuid == snippet_uuid)
+        {
+            snippet_record.status = status;
+            snippet_record.output_previews = output_previews;
+        }
+    }
+
+    /// record the end of the run, with the logs of its stream
+    pub fn finish(&mut self, status: RunStatus, logs: Vec<LogEntry>) {
+        self.ended_at = get_timestamp_millis();
+        self.status = status;
+        self.logs = logs;
+    }
+
+    /// mask the secrets of the run in the output previews, the logs are masked as they are appended
+    pub fn redact_secrets(&mut self, secrets: &Vec<String>) {
+        for snippet_record in self.snippets.iter_mut() {
+            for output_preview in snippet_record.output_previews.values_mut() {
+                *output_preview = redact_secrets(output_preview, secrets);
+            }
+        }
+    }
+
+    fn get_summary(&self) -> FrontRunSummary {
+        return FrontRunSummary {
+            run_id: self.run_id.to_owned(),
+            started_at: self.started_at,
+            ended_at: self.ended_at,
+            status: self.status,
+            graph_hash: self.graph_hash.to_owned(),
+        };
+    }
+
+    /// key of each snippet of the run, as snippet ids change each time the project is opened,
+    /// snippets are matched by name, and by their order among snippets of the same name
+    fn get_keyed_snippets(&self) -> Vec<((String, usize), &SnippetRunRecord)> {
+        let mut occurrences = HashMap::<&String, usize>::new();
+
+        return self
+            .snippets
+            .iter()
+            .map(|snippet_record| {
+                let occurrence = occurrences.entry(&snippet_record.snippet_name).or_insert(0);
+                let key = (snippet_record.snippet_name.to_owned(), *occurrence);
+                *occurrence += 1;
+
+                return (key, snippet_record);
+            })
+            .collect();
+    }
+}
+
+impl RunParameterValue {
+    pub fn new(snippet_name: String, parameter_name: String, value: String) -> Self {
+        return RunParameterValue {
+            snippet_name: snippet_name,
+            parameter_name: parameter_name,
+            value: value,
+        };
+    }
+}
+
+impl SnippetRunRecord {
+    pub fn new(snippet_uuid: Uuid, snippet_id: Uuid, snippet_name: String) -> Self {
+        return SnippetRunRecord {
+            snippet_uuid: snippet_uuid,
+            snippet_id: snippet_id,
+            snippet_name: snippet_name,
+            status: SnippetRunStatus::NotRun,
+            output_previews: BTreeMap::new(),
+        };
+    }
+}
+
+fn get_timestamp_millis() -> u64 {
+    return match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_millis() as u64,
+        Err(_) => 0,
+    };
+}
+
+/// Hash the canonical description of a graph, which is the same for the same graph on any run
+pub fn hash_graph_description(graph_description: &String) -> String {
+    // fnv-1a, so the hash does not change between versions of the application
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for byte in graph_description.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    return format!("{:016x}", hash);
+}
+
+/// Truncate the preview of an output value
+pub fn truncate_output_preview(preview: String) -> String {
+    if preview.chars().count() <= OUTPUT_PREVIEW_MAX_LENGTH {
+        return preview;
+    }
+
+    let mut truncated_preview: String = preview.chars().take(OUTPUT_PREVIEW_MAX_LENGTH).collect();
+    truncated_preview.push_str("...");
+
+    return truncated_preview;
+}
+
+/// Get the location of the run record file from its run id
+fn get_run_record_location(runs_directory: &PathBuf, run_id: &String) -> Result<PathBuf, String> {
+    let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+
+    // run ids come from the front end, make sure they stay inside the runs directory
+    // they are the start time, followed by a counter for runs started in the same millisecond
+    let is_valid = match run_id.split_once('-') {
+        Some((started_at, counter)) => is_number(started_at) && is_number(counter),
+        None => is_number(run_id),
+    };
+
+    if !is_valid {
+        return Err(format!("Invalid run id {}", run_id));
+    }
+
+    return Ok(runs_directory.join(format!("{}.{}", run_id, RUN_RECORD_EXTENSION)));
+}
+
+/// Write the run record to the runs directory, creating the directory if necessary
+///
+/// the run id of a run started in the same millisecond as an already recorded run
+/// is suffixed by a counter, so no record is overwritten
+pub fn write_run_record(
+    runs_directory: &PathBuf,
+    run_record: &mut RunRecord,
+) -> Result<(), String> {
+    if let Err(e) = std::fs::create_dir_all(runs_directory) {
+        return Err(format!(
+            "Unable to create runs directory {}: {}",
+            runs_directory.to_string_lossy(),
+            e
+        ));
+    }
+
+    let mut counter: u32 = 0;
+
+    loop {
+        run_record.run_id = match counter {
+            0 => run_record.started_at.to_string(),
+            _ => format!("{}-{}", run_record.started_at, counter),
+        };
+
+        let run_record_location = get_run_record_location(runs_directory, &run_record.run_id)?;
+
+        let mut file = match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&run_record_location)
+        {
+            Ok(some) => some,
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                counter += 1;
+                continue;
+            }
+            Err(e) => {
+                return Err(format!(
+                    "Unable to create run record at {}: {}",
+                    run_record_location.to_string_lossy(),
+                    e
+                ));
+            }
+        };
+
+        let serialized_run_record = match serde_json::to_string(run_record) {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(format!("Unable to serialize run record: {}", e));
+            }
+        };
+
+        if let Err(e) = file.write_all(serialized_run_record.as_bytes()) {
+            return Err(format!(
+                "Unable to write run record to {}: {}",
+                run_record_location.to_string_lossy(),
+                e
+            ));
+        }
+
+        return Ok(());
+    }
+}
+
+/// Read the run record of the run id from the runs directory
+pub fn read_run_record(runs_directory: &PathBuf, run_id: &String) -> Result<RunRecord, String> {
+    let run_record_location = get_run_record_location(runs_directory, run_id)?;
+
+    let serialized_run_record = match std::fs::read_to_string(&run_record_location) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!("Unable to read run {}: {}", run_id, e));
+        }
+    };
+
+    return match serde_json::from_str::<RunRecord>(&serialized_run_record) {
+        Ok(some) => Ok(some),
+        Err(e) => Err(format!("Unable to deserialize run {}: {}", run_id, e)),
+    };
+}
+
+/// Get the runs of the runs directory, most recent first
+pub fn get_run_summaries(runs_directory: &PathBuf) -> Result<Vec<FrontRunSummary>, String> {
+    let entries = match std::fs::read_dir(runs_directory) {
+        Ok(entries) => entries,
+        // nothing was ever ran
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut run_summaries = Vec::<FrontRunSummary>::new();
+
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(_) => continue,
+        };
+
+        if path.extension() != Some(OsStr::new(RUN_RECORD_EXTENSION)) {
+            continue;
+        }
+
+        let run_id = match path.file_stem() {
+            Some(stem) => stem.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        // skip records that can no longer be read, rather than hiding every run
+        match read_run_record(runs_directory, &run_id) {
+            Ok(run_record) => run_summaries.push(run_record.get_summary()),
+            Err(e) => println!("{}", e),
+        };
+    }
+
+    run_summaries.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+
+    return Ok(run_summaries);
+}
+
+/// Compare two runs, giving what changed from the left run to the right run
+pub fn compare_run_records(left: &RunRecord, right: &RunRecord) -> FrontRunComparison {
+    // parameters
+    let left_parameters: BTreeMap<(&String, &String), &String> = left
+        .parameter_values
+        .iter()
+        .map(|p| ((&p.snippet_name, &p.parameter_name), &p.value))
+        .collect();
+    let right_parameters: BTreeMap<(&String, &String), &String> = right
+        .parameter_values
+        .iter()
+        .map(|p| ((&p.snippet_name, &p.parameter_name), &p.value))
+        .collect();
+
+    let mut parameter_keys: Vec<&(&String, &String)> = left_parameters
+        .keys()
+        .chain(right_parameters.keys())
+        .collect();
+    parameter_keys.sort();
+    parameter_keys.dedup();
+
+    let mut parameter_changes = Vec::<FrontParameterChange>::new();
+
+    for key in parameter_keys {
+        let left_value = left_parameters.get(key);
+        let right_value = right_parameters.get(key);
+
+        if left_value == right_value {
+            continue;
+        }
+
+        parameter_changes.push(FrontParameterChange {
+            snippet_name: key.0.to_owned(),
+            parameter_name: key.1.to_owned(),
+            left_value: left_value.map(|value| value.to_string()),
+            right_value: right_value.map(|value| value.to_string()),
+        });
+    }
+
+    // snippets
+    let left_snippets: BTreeMap<(String, usize), &SnippetRunRecord> =
+        left.get_keyed_snippets().into_iter().collect();
+    let right_snippets: BTreeMap<(String, usize), &SnippetRunRecord> =
+        right.get_keyed_snippets().into_iter().collect();
+
+    let mut snippet_keys: Vec<&(String, usize)> =
+        left_snippets.keys().chain(right_snippets.keys()).collect();
+    snippet_keys.sort();
+    snippet_keys.dedup();
+
+    let mut snippet_changes = Vec::<FrontSnippetRunChange>::new();
+
+    for key in snippet_keys {
+        let left_snippet = left_snippets.get(key);
+        let right_snippet = right_snippets.get(key);
+
+        let left_status = left_snippet.map(|snippet_record| snippet_record.status);
+        let right_status = right_snippet.map(|snippet_record| snippet_record.status);
+
+        // outputs whose preview differs, or that only one of the runs gave
+        let mut changed_outputs = Vec::<String>::new();
+
+        if let (Some(left_snippet), Some(right_snippet)) = (left_snippet, right_snippet) {
+            let mut output_names: Vec<&String> = left_snippet
+                .output_previews
+                .keys()
+                .chain(right_snippet.output_previews.keys())
+                .collect();
+            output_names.sort();
+            output_names.dedup();
+
+            for output_name in output_names {
+                if left_snippet.output_previews.get(output_name)
+                    != right_snippet.output_previews.get(output_name)
+                {
+                    changed_outputs.push(output_name.to_owned());
+                }
+            }
+        }
+
+        if left_status == right_status && changed_outputs.len() == 0 {
+            continue;
+        }
+
+        snippet_changes.push(FrontSnippetRunChange {
+            snippet_name: key.0.to_owned(),
+            left_status: left_status,
+            right_status: right_status,
+            changed_outputs: changed_outputs,
+        });
+    }
+
+    return FrontRunComparison {
+        left: left.get_summary(),
+        right: right.get_summary(),
+        graph_changed: left.graph_hash != right.graph_hash,
+        parameter_changes: parameter_changes,
+        snippet_changes: snippet_changes,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use crate::core_services::secret_store_service::SECRET_MASK;
+
+    use super::{
+        compare_run_records, get_run_summaries, read_run_record, truncate_output_preview,
+        write_run_record, RunParameterValue, RunRecord, RunStatus, SnippetRunRecord,
+        SnippetRunStatus, OUTPUT_PREVIEW_MAX_LENGTH,
+    };
+
+    /// record of a run of a snippet giving the output, with the parameter value
+    fn create_run_record(run_id: &str, parameter_value: &str, output_preview: &str) -> RunRecord {
+        let mut run_record = RunRecord::new(
+            "graph".to_string(),
+            vec![RunParameterValue::new(
+                "snippet".to_string(),
+                "parameter".to_string(),
+                parameter_value.to_string(),
+            )],
+            vec![SnippetRunRecord::new(1, 10, "snippet".to_string())],
+        );
+
+        // run ids are the start time, which can be the same for runs created right after another
+        run_record.run_id = run_id.to_string();
+        run_record.started_at = run_id.parse().unwrap();
+
+        run_record.set_snippet_result(
+            1,
+            SnippetRunStatus::Succeeded,
+            BTreeMap::from([("output".to_string(), output_preview.to_string())]),
+        );
+        run_record.finish(RunStatus::Succeeded, Vec::new());
+
+        return run_record;
+    }
+
+    fn get_test_runs_directory(name: &str) -> PathBuf {
+        return std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+    }
+
+    #[test]
+    fn test_write_and_read_run_records() {
+        let runs_directory = get_test_runs_directory("test_write_and_read_run_records");
+
+        // nothing was ever ran
+        assert_eq!(get_run_summaries(&runs_directory).unwrap().len(), 0);
+
+        let mut first_run_record = create_run_record("1000", "a", "1");
+        let mut second_run_record = create_run_record("2000", "b", "2");
+        write_run_record(&runs_directory, &mut first_run_record).unwrap();
+        write_run_record(&runs_directory, &mut second_run_record).unwrap();
+
+        let read_record = read_run_record(&runs_directory, &"1000".to_string()).unwrap();
+        assert!(read_record.status == RunStatus::Succeeded);
+        assert!(read_record.parameter_values == first_run_record.parameter_values);
+        assert_eq!(read_record.snippets.len(), 1);
+        assert_eq!(read_record.snippets[0].snippet_id, 10);
+        assert!(read_record.snippets[0].status == SnippetRunStatus::Succeeded);
+        assert_eq!(read_record.snippets[0].output_previews["output"], "1");
+
+        // records that cannot be read and other files are left out of the summaries
+        std::fs::write(runs_directory.join("3000.run"), "not a run record").unwrap();
+        std::fs::write(runs_directory.join("notes.txt"), "").unwrap();
+
+        let run_ids: Vec<String> = get_run_summaries(&runs_directory)
+            .unwrap()
+            .into_iter()
+            .map(|run_summary| run_summary.run_id)
+            .collect();
+        assert_eq!(run_ids, vec!["2000".to_string(), "1000".to_string()]);
+
+        // run ids cannot leave the runs directory
+        assert!(read_run_record(&runs_directory, &"../1000".to_string()).is_err());
+        assert!(read_run_record(&runs_directory, &"1000-../1".to_string()).is_err());
+        assert!(read_run_record(&runs_directory, &"1000-".to_string()).is_err());
+        assert!(read_run_record(&runs_directory, &"4000".to_string()).is_err());
+
+        std::fs::remove_dir_all(&runs_directory).unwrap();
+    }
+
+    #[test]
+    /// runs started in the same millisecond are all recorded, under their own run id
+    fn test_write_run_records_started_at_the_same_time() {
+        let runs_directory =
+            get_test_runs_directory("test_write_run_records_started_at_the_same_time");
+
+        let mut run_ids = Vec::<String>::new();
+
+        for parameter_value in ["a", "b", "c"] {
+            let mut run_record = create_run_record("1000", parameter_value, "1");
+            write_run_record(&runs_directory, &mut run_record).unwrap();
+
+            run_ids.push(run_record.run_id);
+        }
+
+        assert_eq!(
+            run_ids,
+            vec![
+                "1000".to_string(),
+                "1000-1".to_string(),
+                "1000-2".to_string()
+            ]
+        );
+
+        for (run_id, parameter_value) in run_ids.iter().zip(["a", "b", "c"]) {
+            let read_record = read_run_record(&runs_directory, run_id).unwrap();
+
+            assert_eq!(read_record.run_id, *run_id);
+            assert_eq!(read_record.parameter_values[0].value, parameter_value);
+        }
+
+        assert_eq!(get_run_summaries(&runs_directory).unwrap().len(), 3);
+
+        std::fs::remove_dir_all(&runs_directory).unwrap();
+    }
+
+    #[test]
+    fn test_compare_run_records() {
+        let left = create_run_record("1000", "a", "1");
+        let mut right = create_run_record("2000", "b", "2");
+
+        // a snippet only the right run has, with a snippet of the same name as the first
+        right
+            .snippets
+            .push(SnippetRunRecord::new(2, 20, "snippet".to_string()));
+        right.graph_hash = "other graph".to_string();
+
+        let comparison = compare_run_records(&left, &right);
+
+        assert!(comparison.graph_changed);
+
+        assert_eq!(comparison.parameter_changes.len(), 1);
+        assert_eq!(comparison.parameter_changes[0].parameter_name, "parameter");
+        assert_eq!(
+            comparison.parameter_changes[0].left_value,
+            Some("a".to_string())
+        );
+        assert_eq!(
+            comparison.parameter_changes[0].right_value,
+            Some("b".to_string())
+        );
+
+        // snippets of the same name are matched by their order
+        assert_eq!(comparison.snippet_changes.len(), 2);
+        assert_eq!(
+            comparison.snippet_changes[0].changed_outputs,
+            vec!["output".to_string()]
+        );
+        assert!(comparison.snippet_changes[0].left_status == Some(SnippetRunStatus::Succeeded));
+        assert!(comparison.snippet_changes[1].left_status.is_none());
+        assert!(comparison.snippet_changes[1].right_status == Some(SnippetRunStatus::NotRun));
+
+        // the same run has no changes
+        let comparison = compare_run_records(&left, &left);
+
+        assert!(!comparison.graph_changed);
+        assert!(comparison.parameter_changes.is_empty());
+        assert!(comparison.snippet_changes.is_empty());
+    }
+
+    #[test]
+    fn test_truncate_output_preview() {
+        let preview = "a".repeat(OUTPUT_PREVIEW_MAX_LENGTH);
+        assert_eq!(truncate_output_preview(preview.clone()), preview);
+
+        let truncated_preview = truncate_output_preview("a".repeat(OUTPUT_PREVIEW_MAX_LENGTH + 1));
+        assert_eq!(truncated_preview, format!("{}...", preview));
+
+        // truncated by characters, not bytes
+        let preview = "é".repeat(OUTPUT_PREVIEW_MAX_LENGTH + 1);
+        let truncated_preview = truncate_output_preview(preview);
+        assert_eq!(
+            truncated_preview,
+            format!("{}...", "é".repeat(OUTPUT_PREVIEW_MAX_LENGTH))
+        );
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let mut run_record = create_run_record("1000", "a", "token=hunter2");
+
+        run_record.redact_secrets(&vec!["hunter2".to_string()]);
+
+        assert_eq!(
+            run_record.snippets[0].output_previews["output"],
+            format!("token={}", SECRET_MASK)
+        );
+    }
+}
@@ -1,29 +1,124 @@
 // Create correct log location for purpose
 // buffer, then append to log location
 
-use std::{borrow::BorrowMut, sync::{Arc, Mutex}};
+use std::{borrow::BorrowMut, collections::{BTreeMap, HashSet, VecDeque}, ffi::OsStr, fs::File, io::{BufRead, BufReader, Write}, path::PathBuf, sync::{Arc, Mutex}, time::{Duration, SystemTime, UNIX_EPOCH}};
 
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString};
 use tauri::Manager;
 
-use crate::utils::sequential_id_generator::Uuid;
+use crate::utils::sequential_id_generator::{SequentialIdGenerator, Uuid};
 
-static BASE_LOG_PATH: &str = "/logging";
+use super::secret_store_service::redact_secrets;
 
-//TODO global list of taken streaming numbers, can be added to and taken away from
-//Held in global tauri state
-/*pub struct LoggingStreamManager (
+pub struct LoggingStreamManager (
     Arc::<Mutex::<LoggingStreamCoordinator>>
 );
 
+// max number of closed streams whose logs are kept for late subscribers
+const MAX_CLOSED_STREAMS: usize = 16;
+
+// max number of logs of a stream kept in memory, older logs are only kept in the log file
+const MAX_STREAM_LOGS: usize = 10000;
+
+// size a log file can grow to before the stream continues in the next log file
+const MAX_LOG_FILE_SIZE: u64 = 1024 * 1024;
+
+// retention policy of the log files of a log directory,
+// logs of the most recent streams are kept as long as they are not too old
+const MAX_RETAINED_LOG_STREAMS: usize = 20;
+const LOG_RETENTION: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+// extension of the json lines log files
+const LOG_FILE_EXTENSION: &str = "jsonl";
+
 struct LoggingStreamCoordinator {
-    active_streams: HashSet<u32> 
+    // streams still being appended to
+    active_streams: HashSet<Uuid>,
+    // logs of the active and recently closed streams, by stream id
+    stream_logs: BTreeMap<Uuid, StreamLogs>
+}
+
+struct StreamLogs {
+    // none if the stream belongs to the whole application rather than a window session
+    window_session_uuid: Option<Uuid>,
+    logs: VecDeque<LogEntry>,
+    // number of logs dropped from the front of the logs, the index of the first kept log
+    dropped_logs: usize,
+    // none if the log file could not be created
+    log_file: Option<LogFileWriter>
+}
+
+/// Writes the entries of a stream as json lines, continuing in a new file once the file is full
+struct LogFileWriter {
+    log_directory: PathBuf,
+    // log id shared by all the files of the stream
+    log_id: String,
+    part: usize,
+    file: File,
+    file_size: u64
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Display, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "UPPERCASE")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warning,
+    Error
+}
+
+/// Entry of a log stream, as written to the log file
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LogEntry {
+    // milliseconds since unix epoch
+    timestamp: u64,
+    level: LogLevel,
+    // visual snippet the log came from, none for logs of the run itself
+    snippet_id: Option<Uuid>,
+    snippet_name: Option<String>,
+    message: String,
+    // structured key value fields of the log
+    #[serde(default)]
+    fields: BTreeMap<String, String>
+}
+
+//struct for the json serialization of a past log of a log directory
+#[derive(Serialize)]
+pub struct FrontPastLog {
+    log_id: String,
+    // milliseconds since unix epoch the stream started
+    started_at: u64,
+    // total size of the log files of the stream in bytes
+    size: u64
 }
 
 pub struct LoggingStreamInstance {
     stream_i: u32,
-    file: std::fs::File,
-    file_path: PathBuf,
-    logging_streams: Arc<Mutex<LoggingStreamCoordinator>>
+    // none for a stream nobody listens to, which is only kept and written to its log file
+    app_handle: Option<tauri::AppHandle>,
+    logging_stream_coordinator: Arc<Mutex<LoggingStreamCoordinator>>,
+    // values of the secret parameters of the run, masked wherever they appear in a log
+    redacted_secrets: Vec<String>
+}
+
+//struct for the json serialization of a log of a stream
+#[derive(Serialize, Clone)]
+pub struct FrontLogEntry {
+    // position of the log in the stream
+    index: usize,
+    // none signals the stream was closed
+    entry: Option<LogEntry>
+}
+
+//struct for the json serialization of the logs of a stream so far
+#[derive(Serialize)]
+pub struct FrontLogStream {
+    logs: Vec<LogEntry>,
+    // index of the first log returned, older logs were dropped
+    first_index: usize,
+    closed: bool
 }
 
 impl Default for LoggingStreamManager {
@@ -34,81 +129,100 @@ impl Default for LoggingStreamManager {
 
 impl Default for LoggingStreamCoordinator {
     fn default() -> Self {
-        Self { active_streams: Default::default() }
+        Self { active_streams: Default::default(), stream_logs: Default::default() }
     }
 }
 
 impl LoggingStreamManager {
-    pub fn create_new_stream(&mut self) -> Result<LoggingStreamInstance, String> {
+    /// Create a new stream with a unique stream id, for the window session.
+    /// returns error if stream could not be created successfully
+    /// The stream is also written to a log file in the log directory
+    pub fn create_new_stream(&mut self, app_handle: tauri::AppHandle, sequential_id_generator: &mut SequentialIdGenerator, window_session_uuid: Uuid, log_directory: PathBuf) -> Result<LoggingStreamInstance, String> {
+        return self.create_stream(Some(app_handle), sequential_id_generator, Some(window_session_uuid), log_directory);
+    }
+
+    /// Create a new stream which belongs to no window session, for work the application does on its own.
+    /// It is not closed with any window session, so it should be closed once done to be pruned with the closed streams
+    pub fn create_application_stream(&mut self, app_handle: tauri::AppHandle, sequential_id_generator: &mut SequentialIdGenerator, log_directory: PathBuf) -> Result<LoggingStreamInstance, String> {
+        return self.create_stream(Some(app_handle), sequential_id_generator, None, log_directory);
+    }
+
+    /// Create a new stream which is not emitted to the front end, for testing without an app
+    #[cfg(test)]
+    pub fn create_detached_stream(&mut self, sequential_id_generator: &mut SequentialIdGenerator, window_session_uuid: Uuid, log_directory: PathBuf) -> Result<LoggingStreamInstance, String> {
+        return self.create_stream(None, sequential_id_generator, Some(window_session_uuid), log_directory);
+    }
+
+    fn create_stream(&mut self, app_handle: Option<tauri::AppHandle>, sequential_id_generator: &mut SequentialIdGenerator, window_session_uuid: Option<Uuid>, log_directory: PathBuf) -> Result<LoggingStreamInstance, String> {
         // get logging stream coordinator
         let mut logging_stream_coordinator_lock = self.0.lock().unwrap();
         let logging_stream_coordinator = logging_stream_coordinator_lock.borrow_mut();
 
-        // impose limit until we implement multiple processes on the front end
-        if logging_stream_coordinator.active_streams.len() > 1 {
-            return Err("Due to pending parallel workflow processing, parallel log streaming will not be allowed".to_string());
-        }
-
-        // Start from 1, finding first stream index that is not already taken
-        let range = Range::<u32> {start: 1, end: u32::max_value()};
+        let stream_i = sequential_id_generator.get_id();
 
-        let mut stream_i: u32 = 0;
+        // create log stream instance
+        let logging_stream_instance = LoggingStreamInstance::new(Arc::clone(&self.0), stream_i, app_handle)?; 
 
-        // Log stream will always start at 1, so 0 will be an invalid stream id 
-        for i in range.into_iter() {
-            // Once we find an available one
-            if !logging_stream_coordinator.active_streams.contains(&i) {
-                stream_i = i;
+        // logs are still streamed when they cannot be written to a file
+        let log_file = match LogFileWriter::new(log_directory, stream_i) {
+            Ok(some) => Some(some),
+            Err(e) => {
+                println!("{}", e);
+                None
             }
-        }
-
-        // All streams are taken, panic as this is an unforseen case
-        // Later implementations can wait for one to become available
-        if stream_i == 0 {
-            panic!("All active streams are taken");
-        }
-
-        // create log file
-        let (file, file_path) = Self::initialize_file_service(stream_i)?;
-
-        // create log stream instance
-        let logging_stream_instance = LoggingStreamInstance::new(Arc::clone(&self.0), stream_i, file, file_path)?; 
+        };
 
         // Add stream id to active streams after successful creation 
         logging_stream_coordinator.active_streams.insert(stream_i);
+        logging_stream_coordinator.stream_logs.insert(stream_i, StreamLogs { window_session_uuid: window_session_uuid, logs: VecDeque::new(), dropped_logs: 0, log_file: log_file });
 
         return Ok(logging_stream_instance);
-
     }
 
-    /// initalize log file
-    fn initialize_file_service(stream_i: u32) -> Result<(std::fs::File, PathBuf), String>{
-        //get current working directory
-        let current_working_directory = match env::current_dir() {
-            Ok(result) => result.as_path().to_owned(),
-            Err(e) => {
-                return Err(e.to_string());
+    /// Get the logs of the stream so far, so a subscriber can catch up on what was emitted before it listened.
+    /// Logs emitted afterwards have an index of at least the first index plus the number of logs returned
+    pub fn subscribe_to_stream(&self, stream_i: Uuid) -> Result<FrontLogStream, String> {
+        // get logging stream coordinator
+        let logging_stream_coordinator = self.0.lock().unwrap();
+
+        let stream_logs = match logging_stream_coordinator.stream_logs.get(&stream_i) {
+            Some(some) => some,
+            None => {
+                return Err(format!("Log stream {} could not be found", stream_i));
             }
         };
-        // get log file path
-        let log_file_name = stream_i.to_string() + ".log";
-        let log_file_path = current_working_directory.join(BASE_LOG_PATH).join(log_file_name);
-
-        // Get rid of existing file if exists
-        // this scenario can occur if the program crashed in the middle of an operation 
-        if log_file_path.exists() {
-            std::fs::remove_file(log_file_path.to_owned());
-        }
 
-        // Create new empty file in append mode, get fd, store
-        let file = match std::fs::OpenOptions::new().append(true).open(log_file_path.to_owned()) {
-            Ok(file) => file,
-            Err(e) => {
-                return Err(format!("Was not able to create log file for stream {}:{}", stream_i, e.to_string()));
-            },
-        };
+        return Ok(FrontLogStream {
+            logs: stream_logs.logs.iter().cloned().collect(),
+            first_index: stream_logs.dropped_logs,
+            closed: !logging_stream_coordinator.active_streams.contains(&stream_i)
+        });
+    }
+
+    /// Close the streams of the window session and forget their logs.
+    /// An instance still appending to a closed stream emits to a window that no longer listens
+    pub fn close_window_session_streams(&mut self, window_session_uuid: Uuid) {
+        // get logging stream coordinator
+        let mut logging_stream_coordinator_lock = self.0.lock().unwrap();
+        let logging_stream_coordinator = logging_stream_coordinator_lock.borrow_mut();
+
+        let stream_ids: Vec<Uuid> = logging_stream_coordinator.stream_logs
+            .iter()
+            .filter(|(_, stream_logs)| stream_logs.window_session_uuid == Some(window_session_uuid))
+            .map(|(stream_i, _)| *stream_i)
+            .collect();
 
-        return Ok((file, log_file_path));
+        for stream_i in stream_ids {
+            logging_stream_coordinator.done_with_stream(stream_i);
+            logging_stream_coordinator.stream_logs.remove(&stream_i);
+        }
+    }
+}
+
+impl StreamLogs {
+    /// index the next log of the stream gets
+    fn next_index(&self) -> usize {
+        return self.dropped_logs + self.logs.len();
     }
 }
 
@@ -117,19 +231,65 @@ impl LoggingStreamCoordinator {
     pub fn done_with_stream(&mut self, stream_i: u32) {
         // remove stream from active streams
         self.active_streams.remove(&stream_i);
+
+        // nothing is written to the log file of a closed stream anymore
+        if let Some(stream_logs) = self.stream_logs.get_mut(&stream_i) {
+            stream_logs.log_file = None;
+        }
+
+        // forget the logs of the oldest closed streams
+        let closed_stream_ids: Vec<Uuid> = self.stream_logs
+            .keys()
+            .filter(|stream_i| !self.active_streams.contains(stream_i))
+            .copied()
+            .collect();
+
+        if closed_stream_ids.len() > MAX_CLOSED_STREAMS {
+            for stream_i in closed_stream_ids[..closed_stream_ids.len() - MAX_CLOSED_STREAMS].iter() {
+                self.stream_logs.remove(stream_i);
+            }
+        }
+    }
+
+    /// keep the log of an active stream, writing its entry to the log file
+    /// returns the index of the log in the stream, or none if the stream was closed
+    fn push_log(&mut self, stream_i: u32, log_entry: &LogEntry) -> Option<usize> {
+        if !self.active_streams.contains(&stream_i) {
+            return None;
+        }
+
+        let stream_logs = self.stream_logs.get_mut(&stream_i)?;
+        stream_logs.logs.push_back(log_entry.clone());
+
+        // forget the oldest logs, the indices of the kept logs stay the same
+        if stream_logs.logs.len() > MAX_STREAM_LOGS {
+            stream_logs.logs.pop_front();
+            stream_logs.dropped_logs += 1;
+        }
+
+        if let Some(log_file) = &mut stream_logs.log_file {
+            if let Err(e) = log_file.write_entry(log_entry) {
+                println!("{}", e);
+
+                // stop writing to the log file rather than failing on each entry
+                stream_logs.log_file = None;
+            }
+        }
+
+        return Some(stream_logs.next_index() - 1);
     }
 }
 
 // As long as the runtime service is alive, the file descriptor will be held
 impl LoggingStreamInstance {
     /// Creates a new runtime logging service instance 
-    pub fn new(logging_streams: Arc<Mutex<LoggingStreamCoordinator>>, stream_i: u32, file: std::fs::File, file_path: PathBuf) -> Result<Self, String> {
+    fn new(logging_streams: Arc<Mutex<LoggingStreamCoordinator>>, stream_i: u32, app_handle: Option<tauri::AppHandle>) -> Result<Self, String> {
         // create new runtime logging service
         let service = LoggingStreamInstance {
             stream_i: stream_i,
-            file: file,
-            file_path: file_path,
-            logging_streams: logging_streams
+            app_handle: app_handle,
+            logging_stream_coordinator: logging_streams,
+            redacted_secrets: Vec::new()
         };
 
         return Ok(service);
@@ -139,173 +299,680 @@ impl LoggingStreamInstance {
         return self.stream_i;
     }
 
+    /// Get another instance appending to the same stream, for logging from other threads.
+    /// Only the original instance should close the log
+    pub fn get_shared_instance(&self) -> LoggingStreamInstance {
+        return LoggingStreamInstance {
+            stream_i: self.stream_i,
+            app_handle: self.app_handle.clone(),
+            logging_stream_coordinator: Arc::clone(&self.logging_stream_coordinator),
+            redacted_secrets: self.redacted_secrets.clone()
+        };
+    }
+
+    /// Set the secrets masked in the logs appended from now on, including by shared instances created after
+    pub fn set_redacted_secrets(&mut self, secrets: Vec<String>) {
+        self.redacted_secrets = secrets;
+    }
+
+    /// Get the logs of the stream kept in memory, at most the last MAX_STREAM_LOGS appended
+    pub fn get_logs(&self) -> Vec<LogEntry> {
+        // get logging stream coordinator
+        let logging_stream_coordinator = self.logging_stream_coordinator.lock().unwrap();
+
+        match logging_stream_coordinator.stream_logs.get(&self.stream_i) {
+            Some(stream_logs) => return stream_logs.logs.iter().cloned().collect(),
+            None => return Vec::new()
+        };
+    }
 
-    // append log to file
+    /// append info log of the run itself
     pub fn append_log(&mut self, log: String) {
-        // append new line
-        let contents = log + "\n"; 
-        // append contents to file
-        self.file.write(contents.as_bytes());
+        self.append_leveled_log(LogLevel::Info, log);
+    }
+
+    /// append log of the run itself
+    pub fn append_leveled_log(&mut self, level: LogLevel, log: String) {
+        self.append_entry(LogEntry::new(level, None, log, BTreeMap::new()));
+    }
+
+    /// append log of a snippet, with its structured fields
+    pub fn append_snippet_log(&mut self, level: LogLevel, snippet_uuid: Uuid, snippet_name: String, message: String, fields: BTreeMap<String, String>) {
+        self.append_entry(LogEntry::new(level, Some((snippet_uuid, snippet_name)), message, fields));
     }
-}
 
-impl Drop for LoggingStreamInstance {
-    /// overwrite existing drop method to remove itself from the logging stream coordinator, and to remove the existing file
-    fn drop(&mut self) {
-        {
+    fn append_entry(&mut self, mut log_entry: LogEntry) {
+        // secrets never reach the stream, the log file or the front
+        log_entry.redact(&self.redacted_secrets);
+
+        let index = {
+            // get logging stream coordinator
+            let mut logging_stream_coordinator_lock = self.logging_stream_coordinator.lock().unwrap();
+            let logging_stream_coordinator = logging_stream_coordinator_lock.borrow_mut();
+
+            match logging_stream_coordinator.push_log(self.stream_i, &log_entry) {
+                Some(some) => some,
+                // stream was closed, no one is listening anymore
+                None => return
+            }
+        };
+
+        // emit log
+        self.emit(FrontLogEntry { index: index, entry: Some(log_entry) });
+    }
+
+    /// Close the log
+    /// returns the app handle, none for a detached stream
+    pub fn close_log(self) -> Option<tauri::AppHandle> {
+        let index = {
             // get logging stream coordinator
-            let mut logging_stream_coordinator_lock = self.logging_streams.lock().unwrap();
+            let mut logging_stream_coordinator_lock = self.logging_stream_coordinator.lock().unwrap();
             let logging_stream_coordinator = logging_stream_coordinator_lock.borrow_mut();
 
-            // remove stream from stream coordinator
+            let index = match logging_stream_coordinator.stream_logs.get(&self.stream_i) {
+                Some(stream_logs) => stream_logs.next_index(),
+                None => 0
+            };
+
+            // close stream from stream coordinator
             logging_stream_coordinator.done_with_stream(self.stream_i);
 
-            // get file path
-            let file_path = self.file_path.to_owned();
+            index
+        };
 
-            // attempt to remove file
-            // if the file was moved, this will cause the program to crash
-            // ignore error
-            std::fs::remove_file(file_path);
-        }
+        // an entry without a log will be a closing signal
+        self.emit(FrontLogEntry { index: index, entry: None });
 
-        // drop self
-        drop(self);
+        return self.app_handle;
     }
-}*/
 
-pub struct LoggingStreamManager (
-    Arc::<Mutex::<LoggingStreamCoordinator>>
-);
+    fn emit(&self, log_entry: FrontLogEntry) {
+        // emit name
+        let event_name = format!("log_{}", self.stream_i);
+        // emit log
+        if let Some(app_handle) = &self.app_handle {
+            app_handle.emit_all(&event_name, log_entry).unwrap();
+        }
+    }
+}
 
-//TODO add uuids
-// OR remove uuids from other things like this where it does not matter
+impl LogEntry {
+    pub fn new(level: LogLevel, snippet: Option<(Uuid, String)>, message: String, fields: BTreeMap<String, String>) -> Self {
+        let (snippet_id, snippet_name) = match snippet {
+            Some((snippet_id, snippet_name)) => (Some(snippet_id), Some(snippet_name)),
+            None => (None, None),
+        };
 
-struct LoggingStreamCoordinator {
-    active_stream: Option<u32> 
-}
+        return LogEntry {
+            timestamp: get_timestamp_millis(),
+            level: level,
+            snippet_id: snippet_id,
+            snippet_name: snippet_name,
+            message: message,
+            fields: fields,
+        };
+    }
 
-pub struct LoggingStreamInstance {
-    stream_i: u32,
-    app_handle: tauri::AppHandle,
-    logging_stream_coordinator: Arc<Mutex<LoggingStreamCoordinator>>
-}
+    /// mask the secrets in the message and the values of the fields
+    fn redact(&mut self, secrets: &Vec<String>) {
+        if secrets.is_empty() {
+            return;
+        }
 
-impl Default for LoggingStreamManager {
-    fn default() -> Self {
-        return LoggingStreamManager(Arc::new(Mutex::new(LoggingStreamCoordinator::default())));
+        self.message = redact_secrets(&self.message, secrets);
+
+        for value in self.fields.values_mut() {
+            *value = redact_secrets(value, secrets);
+        }
     }
 }
 
-impl Default for LoggingStreamCoordinator {
-    fn default() -> Self {
-        Self { active_stream: Default::default() }
+impl LogFileWriter {
+    /// create the first log file of the stream, pruning the old logs of the log directory
+    fn new(log_directory: PathBuf, stream_i: u32) -> Result<Self, String> {
+        if let Err(e) = std::fs::create_dir_all(&log_directory) {
+            return Err(format!(
+                "Unable to create log directory {}: {}",
+                log_directory.to_string_lossy(),
+                e
+            ));
+        }
+
+        prune_past_logs(&log_directory);
+
+        // logs sort by the time their stream started
+        let log_id = format!("{}_{}", get_timestamp_millis(), stream_i);
+        let file = create_log_file(&log_directory, &log_id, 0)?;
+
+        return Ok(LogFileWriter {
+            log_directory: log_directory,
+            log_id: log_id,
+            part: 0,
+            file: file,
+            file_size: 0,
+        });
     }
-}
 
-impl LoggingStreamManager {
-    /// Create a new stream.
-    /// returns error if stream could not be created successfully
-    pub fn create_new_stream(&mut self, app_handle: tauri::AppHandle, window_session_uuid: Uuid) -> Result<LoggingStreamInstance, String> {
-        // get logging stream coordinator
-        let mut logging_stream_coordinator_lock = self.0.lock().unwrap();
-        let logging_stream_coordinator = logging_stream_coordinator_lock.borrow_mut();
+    fn write_entry(&mut self, log_entry: &LogEntry) -> Result<(), String> {
+        let mut line = match serde_json::to_string(log_entry) {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(format!("Unable to serialize log entry: {}", e));
+            }
+        };
+        line.push('\n');
 
-        // impose limit until we implement multiple processes on the front end
-        if let Some(_) = logging_stream_coordinator.active_stream {
-            return Err("Due to pending parallel workflow processing, parallel log streaming will not be allowed".to_string());
+        // rotate to the next file once the current one is full
+        if self.file_size > 0 && self.file_size + line.len() as u64 > MAX_LOG_FILE_SIZE {
+            self.part += 1;
+            self.file = create_log_file(&self.log_directory, &self.log_id, self.part)?;
+            self.file_size = 0;
         }
 
-        let stream_i = window_session_uuid; 
-
-        // create log stream instance
-        let logging_stream_instance = LoggingStreamInstance::new(Arc::clone(&self.0), stream_i, app_handle)?; 
+        if let Err(e) = self.file.write_all(line.as_bytes()) {
+            return Err(format!("Unable to write to log {}: {}", self.log_id, e));
+        }
 
-        // Add stream id to active streams after successful creation 
-        logging_stream_coordinator.active_stream = Some(stream_i);
+        self.file_size += line.len() as u64;
 
-        return Ok(logging_stream_instance);
+        return Ok(());
     }
 }
 
-impl LoggingStreamCoordinator {
-    // Remove stream, irregardless if stream already exists or not
-    pub fn done_with_stream(&mut self) {
-        // remove stream from active stream
-        self.active_stream = None;
-    }
+fn get_timestamp_millis() -> u64 {
+    return match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_millis() as u64,
+        Err(_) => 0,
+    };
 }
 
-// As long as the runtime service is alive, the file descriptor will be held
-impl LoggingStreamInstance {
-    /// Creates a new runtime logging service instance 
-    fn new(logging_streams: Arc<Mutex<LoggingStreamCoordinator>>, stream_i: u32, app_handle: tauri::AppHandle) -> Result<Self, String> {
-        // create new runtime logging service
-        let service = LoggingStreamInstance {
-            stream_i: stream_i,
-            app_handle: app_handle,
-            logging_stream_coordinator: logging_streams
+/// Get the location of a log file from its log id and part
+fn get_log_file_location(log_directory: &PathBuf, log_id: &String, part: usize) -> PathBuf {
+    return log_directory.join(format!("{}.{}.{}", log_id, part, LOG_FILE_EXTENSION));
+}
+
+fn create_log_file(log_directory: &PathBuf, log_id: &String, part: usize) -> Result<File, String> {
+    let log_file_location = get_log_file_location(log_directory, log_id, part);
+
+    return match File::create(&log_file_location) {
+        Ok(some) => Ok(some),
+        Err(e) => Err(format!(
+            "Unable to create log file {}: {}",
+            log_file_location.to_string_lossy(),
+            e
+        )),
+    };
+}
+
+/// Get the log files of the log directory, grouped by log id
+/// returns the log ids with their started at time and log files, in the order their streams started
+fn get_log_files(log_directory: &PathBuf) -> Vec<(String, u64, Vec<PathBuf>)> {
+    let entries = match std::fs::read_dir(log_directory) {
+        Ok(entries) => entries,
+        // nothing was ever logged
+        Err(_) => return Vec::new(),
+    };
+
+    let mut log_files = BTreeMap::<(u64, String), Vec<PathBuf>>::new();
+
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(_) => continue,
         };
 
-        return Ok(service);
+        if path.extension() != Some(OsStr::new(LOG_FILE_EXTENSION)) {
+            continue;
+        }
+
+        // file name is log id, part and extension
+        let log_id = match path.file_name().and_then(|file_name| file_name.to_str()) {
+            Some(file_name) => match file_name.split('.').next() {
+                Some(log_id) => log_id.to_string(),
+                None => continue,
+            },
+            None => continue,
+        };
+
+        let started_at = match log_id.split('_').next().map(|started_at| started_at.parse::<u64>()) {
+            Some(Ok(started_at)) => started_at,
+            _ => continue,
+        };
+
+        log_files.entry((started_at, log_id)).or_default().push(path);
     }
 
-    pub fn get_stream_i(&self) -> u32 {
-        return self.stream_i;
+    return log_files
+        .into_iter()
+        .map(|((started_at, log_id), mut paths)| {
+            paths.sort();
+            (log_id, started_at, paths)
+        })
+        .collect();
+}
+
+/// Remove the logs of the log directory which are past the retention policy
+fn prune_past_logs(log_directory: &PathBuf) {
+    let log_files = get_log_files(log_directory);
+
+    let oldest_retained = get_timestamp_millis().saturating_sub(LOG_RETENTION.as_millis() as u64);
+    let retained_from = log_files.len().saturating_sub(MAX_RETAINED_LOG_STREAMS);
+
+    for (i, (_, started_at, paths)) in log_files.iter().enumerate() {
+        if i >= retained_from && *started_at >= oldest_retained {
+            continue;
+        }
+
+        for path in paths.iter() {
+            if let Err(e) = std::fs::remove_file(path) {
+                println!(
+                    "Unable to delete log file at {}: {}",
+                    path.to_string_lossy(),
+                    e
+                );
+            }
+        }
     }
+}
 
-    /// append log
-    pub fn append_log(&mut self, log: String) {
-        // emit name
-        let event_name = format!("log_{}", self.stream_i);
-        // append new line
-        let contents = log + "\n"; 
-        // emit log
-        self.app_handle.emit_all(&event_name, contents).unwrap();
+/// Get the past logs of the log directory, most recent first
+pub fn get_past_logs(log_directory: &PathBuf) -> Vec<FrontPastLog> {
+    return get_log_files(log_directory)
+        .into_iter()
+        .rev()
+        .map(|(log_id, started_at, paths)| FrontPastLog {
+            log_id: log_id,
+            started_at: started_at,
+            size: paths
+                .iter()
+                .filter_map(|path| std::fs::metadata(path).ok())
+                .map(|metadata| metadata.len())
+                .sum(),
+        })
+        .collect();
+}
+
+/// Read the entries of a past log of the log directory
+pub fn read_past_log(log_directory: &PathBuf, log_id: &String) -> Result<Vec<LogEntry>, String> {
+    // log ids come from the front end, make sure they stay inside the log directory
+    if log_id.is_empty() || !log_id.chars().all(|c| c.is_ascii_digit() || c == '_') {
+        return Err(format!("Invalid log id {}", log_id));
     }
 
-    /// Close the log
-    pub fn close_log(self) -> tauri::AppHandle{
-        // emit name
-        let event_name = format!("log_{}", self.stream_i);
-        // an empty log will be a closing signal
-        // this is safe because each append log has at least the new line character
-        let contents = ""; 
-        // emit log
-        self.app_handle.emit_all(&event_name, contents).unwrap();
+    let mut log_entries = Vec::<LogEntry>::new();
+    let mut part = 0;
 
-        {
-            // get logging stream coordinator
-            let mut logging_stream_coordinator_lock = self.logging_stream_coordinator.lock().unwrap();
-            let logging_stream_coordinator = logging_stream_coordinator_lock.borrow_mut();
+    loop {
+        let log_file_location = get_log_file_location(log_directory, log_id, part);
 
-            // close stream from stream coordinator
-            logging_stream_coordinator.done_with_stream();
+        if !log_file_location.exists() {
+            break;
         }
 
-        return self.app_handle;
+        let file = match File::open(&log_file_location) {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(format!(
+                    "Unable to open log file {}: {}",
+                    log_file_location.to_string_lossy(),
+                    e
+                ));
+            }
+        };
+
+        for line in BufReader::new(file).lines() {
+            let line = match line {
+                Ok(some) => some,
+                Err(e) => {
+                    return Err(format!("Unable to read log {}: {}", log_id, e));
+                }
+            };
+
+            // the last line might have been cut off by a crash
+            match serde_json::from_str::<LogEntry>(&line) {
+                Ok(log_entry) => log_entries.push(log_entry),
+                Err(_) => continue,
+            };
+        }
+
+        part += 1;
+    }
+
+    if part == 0 {
+        return Err(format!("Log {} could not be found", log_id));
     }
+
+    return Ok(log_entries);
 }
 
-/*
 #[cfg(test)]
 mod tests {
-    use tauri::Manager;
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use crate::utils::sequential_id_generator::SequentialIdGenerator;
+
+    use super::{
+        get_log_file_location, get_past_logs, get_timestamp_millis, prune_past_logs, read_past_log,
+        LogEntry, LogFileWriter, LogLevel, LoggingStreamManager, LOG_RETENTION, MAX_CLOSED_STREAMS,
+        MAX_LOG_FILE_SIZE, MAX_RETAINED_LOG_STREAMS, MAX_STREAM_LOGS,
+    };
+
+    fn get_test_log_directory(name: &str) -> PathBuf {
+        return std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+    }
+
+    #[test]
+    fn test_stream_isolation() {
+        let log_directory = get_test_log_directory("test_stream_isolation");
+
+        let mut logging_manager = LoggingStreamManager::default();
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        // two runs logging at the same time
+        let mut first_instance = logging_manager
+            .create_detached_stream(&mut sequential_id_generator, 0, log_directory.clone())
+            .unwrap();
+        let mut second_instance = logging_manager
+            .create_detached_stream(&mut sequential_id_generator, 0, log_directory.clone())
+            .unwrap();
+        assert_ne!(
+            first_instance.get_stream_i(),
+            second_instance.get_stream_i()
+        );
+
+        first_instance.append_log("first run".to_string());
+        second_instance.append_log("second run".to_string());
+
+        // shared instances append to the stream they were created from
+        let mut shared_instance = first_instance.get_shared_instance();
+        shared_instance.append_log("first run thread".to_string());
+
+        let first_logs = first_instance.get_logs();
+        assert_eq!(first_logs.len(), 2);
+        assert_eq!(first_logs[0].message, "first run");
+        assert_eq!(first_logs[1].message, "first run thread");
+
+        let second_logs = second_instance.get_logs();
+        assert_eq!(second_logs.len(), 1);
+        assert_eq!(second_logs[0].message, "second run");
+
+        // closing one stream leaves the other open
+        let first_stream_i = first_instance.get_stream_i();
+        let second_stream_i = second_instance.get_stream_i();
+        assert!(first_instance.close_log().is_none());
+
+        let first_stream = logging_manager.subscribe_to_stream(first_stream_i).unwrap();
+        assert!(first_stream.closed);
+        assert_eq!(first_stream.logs.len(), 2);
+
+        let second_stream = logging_manager
+            .subscribe_to_stream(second_stream_i)
+            .unwrap();
+        assert!(!second_stream.closed);
+
+        // logs appended to a closed stream are dropped
+        shared_instance.append_log("after close".to_string());
+        assert_eq!(
+            logging_manager
+                .subscribe_to_stream(first_stream_i)
+                .unwrap()
+                .logs
+                .len(),
+            2
+        );
+
+        second_instance.close_log();
+
+        std::fs::remove_dir_all(&log_directory).unwrap();
+    }
+
+    #[test]
+    fn test_close_window_session_streams() {
+        let log_directory = get_test_log_directory("test_close_window_session_streams");
+
+        let mut logging_manager = LoggingStreamManager::default();
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let mut closed_instance = logging_manager
+            .create_detached_stream(&mut sequential_id_generator, 1, log_directory.clone())
+            .unwrap();
+        let kept_instance = logging_manager
+            .create_detached_stream(&mut sequential_id_generator, 2, log_directory.clone())
+            .unwrap();
+
+        logging_manager.close_window_session_streams(1);
+
+        // the logs of the closed window session are forgotten
+        assert!(logging_manager
+            .subscribe_to_stream(closed_instance.get_stream_i())
+            .is_err());
+        closed_instance.append_log("after close".to_string());
+        assert_eq!(closed_instance.get_logs().len(), 0);
+
+        assert!(
+            !logging_manager
+                .subscribe_to_stream(kept_instance.get_stream_i())
+                .unwrap()
+                .closed
+        );
+
+        std::fs::remove_dir_all(&log_directory).unwrap();
+    }
+
+    #[test]
+    fn test_closed_stream_pruning() {
+        let log_directory = get_test_log_directory("test_closed_stream_pruning");
+
+        let mut logging_manager = LoggingStreamManager::default();
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        // an active stream is never pruned
+        let active_instance = logging_manager
+            .create_detached_stream(&mut sequential_id_generator, 0, log_directory.clone())
+            .unwrap();
+
+        let mut closed_stream_ids = Vec::new();
+
+        for _ in 0..MAX_CLOSED_STREAMS + 2 {
+            let mut logging_instance = logging_manager
+                .create_detached_stream(&mut sequential_id_generator, 0, log_directory.clone())
+                .unwrap();
+            logging_instance.append_log("log".to_string());
+
+            closed_stream_ids.push(logging_instance.get_stream_i());
+            logging_instance.close_log();
+        }
+
+        // the oldest closed streams are forgotten
+        for stream_i in closed_stream_ids[..2].iter() {
+            assert!(logging_manager.subscribe_to_stream(*stream_i).is_err());
+        }
+
+        for stream_i in closed_stream_ids[2..].iter() {
+            let stream = logging_manager.subscribe_to_stream(*stream_i).unwrap();
+            assert!(stream.closed);
+            assert_eq!(stream.logs.len(), 1);
+        }
+
+        assert!(
+            !logging_manager
+                .subscribe_to_stream(active_instance.get_stream_i())
+                .unwrap()
+                .closed
+        );
+
+        std::fs::remove_dir_all(&log_directory).unwrap();
+    }
 
     #[test]
-    fn test_log_streaming() {
-        // create tauri app handle
-        // create logging stream coordinator
+    fn test_stream_logs_cap() {
+        let log_directory = get_test_log_directory("test_stream_logs_cap");
+
+        let mut logging_manager = LoggingStreamManager::default();
+        let mut sequential_id_generator = SequentialIdGenerator::default();
 
-        // create logging stream instance
+        let mut logging_instance = logging_manager
+            .create_detached_stream(&mut sequential_id_generator, 0, log_directory.clone())
+            .unwrap();
 
-        // append to log on this instance
+        for i in 0..MAX_STREAM_LOGS + 5 {
+            logging_instance.append_log(format!("log {}", i));
+        }
 
-        // try to create another logging stream instance, fail
+        // only the most recent logs are kept, with their index in the stream
+        let stream = logging_manager
+            .subscribe_to_stream(logging_instance.get_stream_i())
+            .unwrap();
+        assert_eq!(stream.first_index, 5);
+        assert_eq!(stream.logs.len(), MAX_STREAM_LOGS);
+        assert_eq!(stream.logs[0].message, "log 5");
+        assert_eq!(
+            stream.logs[MAX_STREAM_LOGS - 1].message,
+            format!("log {}", MAX_STREAM_LOGS + 4)
+        );
+
+        logging_instance.close_log();
+
+        std::fs::remove_dir_all(&log_directory).unwrap();
+    }
+
+    #[test]
+    fn test_log_entry_json_lines() {
+        let log_entry = LogEntry::new(
+            LogLevel::Warning,
+            Some((3, "snippet".to_string())),
+            "message".to_string(),
+            BTreeMap::from([("key".to_string(), "value".to_string())]),
+        );
+
+        let line = serde_json::to_string(&log_entry).unwrap();
+
+        // one line per entry, with the level in lowercase
+        assert!(!line.contains('\n'));
+
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["level"], "warning");
+        assert_eq!(value["snippet_id"], 3);
+        assert_eq!(value["snippet_name"], "snippet");
+        assert_eq!(value["message"], "message");
+        assert_eq!(value["fields"]["key"], "value");
+
+        let read_log_entry: LogEntry = serde_json::from_str(&line).unwrap();
+        assert!(read_log_entry.level == LogLevel::Warning);
+        assert_eq!(read_log_entry.fields, log_entry.fields);
+
+        // logs of the run itself have no snippet, entries without fields can be read
+        let read_log_entry: LogEntry = serde_json::from_str(
+            r#"{"timestamp":0,"level":"error","snippet_id":null,"snippet_name":null,"message":"failed"}"#,
+        )
+        .unwrap();
+        assert!(read_log_entry.level == LogLevel::Error);
+        assert_eq!(read_log_entry.snippet_id, None);
+        assert!(read_log_entry.fields.is_empty());
+
+        // levels display in uppercase
+        assert_eq!(LogLevel::Debug.to_string(), "DEBUG");
+    }
+
+    #[test]
+    fn test_log_file_rotation() {
+        let log_directory = get_test_log_directory("test_log_file_rotation");
+
+        let mut log_file_writer = LogFileWriter::new(log_directory.clone(), 0).unwrap();
+
+        // enough entries to fill a few log files
+        let message = "x".repeat(1000);
+        let entry_count = 2 * MAX_LOG_FILE_SIZE as usize / message.len() + 10;
+
+        for i in 0..entry_count {
+            let log_entry = LogEntry::new(
+                LogLevel::Info,
+                None,
+                message.clone(),
+                BTreeMap::from([("i".to_string(), i.to_string())]),
+            );
+            log_file_writer.write_entry(&log_entry).unwrap();
+        }
+
+        // the stream continued in the next files, none of them past the max size
+        assert!(log_file_writer.part >= 2);
+
+        for part in 0..=log_file_writer.part {
+            let log_file_location =
+                get_log_file_location(&log_directory, &log_file_writer.log_id, part);
+            assert!(std::fs::metadata(log_file_location).unwrap().len() <= MAX_LOG_FILE_SIZE);
+        }
+
+        // the rotated stream is read back in order
+        let log_entries = read_past_log(&log_directory, &log_file_writer.log_id).unwrap();
+        assert_eq!(log_entries.len(), entry_count);
+
+        for (i, log_entry) in log_entries.iter().enumerate() {
+            assert_eq!(log_entry.fields["i"], i.to_string());
+        }
+
+        // a past log is listed once, with the size of all of its files
+        let past_logs = get_past_logs(&log_directory);
+        assert_eq!(past_logs.len(), 1);
+        assert_eq!(past_logs[0].log_id, log_file_writer.log_id);
+        assert!(past_logs[0].size > 2 * MAX_LOG_FILE_SIZE);
+
+        std::fs::remove_dir_all(&log_directory).unwrap();
+    }
+
+    #[test]
+    fn test_prune_past_logs() {
+        let log_directory = get_test_log_directory("test_prune_past_logs");
+        std::fs::create_dir_all(&log_directory).unwrap();
+
+        let create_past_log = |started_at: u64, stream_i: usize, parts: usize| -> String {
+            let log_id = format!("{}_{}", started_at, stream_i);
+
+            for part in 0..parts {
+                std::fs::write(get_log_file_location(&log_directory, &log_id, part), "").unwrap();
+            }
+
+            return log_id;
+        };
+
+        // a log past the retention time, followed by more logs than are retained
+        let now = get_timestamp_millis();
+        let expired_log_id = create_past_log(now - LOG_RETENTION.as_millis() as u64 - 1000, 0, 2);
+
+        let log_ids: Vec<String> = (0..MAX_RETAINED_LOG_STREAMS + 2)
+            .map(|i| create_past_log(now - 1000 + i as u64, i + 1, 1))
+            .collect();
+
+        // files of other extensions are left alone
+        std::fs::write(log_directory.join("notes.txt"), "").unwrap();
+
+        prune_past_logs(&log_directory);
+
+        let past_log_ids: Vec<String> = get_past_logs(&log_directory)
+            .into_iter()
+            .map(|past_log| past_log.log_id)
+            .collect();
+
+        // only the most recent logs are kept, most recent first
+        let expected_log_ids: Vec<String> = log_ids[2..].iter().rev().cloned().collect();
+        assert_eq!(past_log_ids, expected_log_ids);
+
+        assert!(!get_log_file_location(&log_directory, &expired_log_id, 0).exists());
+        assert!(!get_log_file_location(&log_directory, &expired_log_id, 1).exists());
+        assert!(log_directory.join("notes.txt").exists());
+
+        std::fs::remove_dir_all(&log_directory).unwrap();
+    }
+
+    #[test]
+    fn test_read_past_log_errors() {
+        let log_directory = get_test_log_directory("test_read_past_log_errors");
 
-        // drop logging stream instance
+        // log ids cannot leave the log directory
+        assert!(read_past_log(&log_directory, &"../1_0".to_string()).is_err());
+        assert!(read_past_log(&log_directory, &"".to_string()).is_err());
 
-        // create another logging stream instance successfully
+        // unknown log
+        assert!(read_past_log(&log_directory, &"1_0".to_string()).is_err());
     }
 }
-*/
\ No newline at end of file
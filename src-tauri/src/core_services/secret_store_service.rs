@@ -0,0 +1,187 @@
+use std::{io::Write, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    state_management::external_snippet_manager::PackagePath, utils::sequential_id_generator::Uuid,
+};
+
+use super::concurrent_processes::get_secrets_directory;
+
+// extension of the secrets store files
+const SECRET_STORE_EXTENSION: &str = "secrets";
+
+/// shown in place of the value of secret parameters, and of secrets in logs
+pub const SECRET_MASK: &str = "********";
+
+/// Secret parameter values of a project, which are never written into the project file
+/// the parameters are referenced the same way the project file references them
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SecretStore {
+    secrets: Vec<StoredSecret>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct StoredSecret {
+    snippet_package_path: PackagePath,
+    snippet_original_uuid: Uuid,
+    parameter_name: String,
+    value: String,
+}
+
+impl SecretStore {
+    /// add the value of a secret parameter of a snippet
+    pub fn add_secret(
+        &mut self,
+        snippet_package_path: PackagePath,
+        snippet_original_uuid: Uuid,
+        parameter_name: String,
+        value: String,
+    ) {
+        self.secrets.push(StoredSecret {
+            snippet_package_path: snippet_package_path,
+            snippet_original_uuid: snippet_original_uuid,
+            parameter_name: parameter_name,
+            value: value,
+        });
+    }
+
+    /// find the value of the secret parameter of a snippet
+    pub fn find_secret(
+        &self,
+        snippet_package_path: &PackagePath,
+        snippet_original_uuid: Uuid,
+        parameter_name: &String,
+    ) -> Option<&String> {
+        return self
+            .secrets
+            .iter()
+            .find(|secret| {
+                secret.snippet_package_path == *snippet_package_path
+                    && secret.snippet_original_uuid == snippet_original_uuid
+                    && secret.parameter_name == *parameter_name
+            })
+            .map(|secret| &secret.value);
+    }
+}
+
+/// get the location of the secrets store of the project, in the secrets directory
+/// so it is never next to the project file
+pub fn get_project_secret_store_location_from_name(project_name: &String) -> PathBuf {
+    return get_secrets_directory().join(format!("{}.{}", project_name, SECRET_STORE_EXTENSION));
+}
+
+/// Write the secrets store of the project, removing it when there are no secrets
+pub fn write_secret_store(location: &PathBuf, secret_store: &SecretStore) -> Result<(), String> {
+    if secret_store.secrets.is_empty() {
+        remove_secret_store(location);
+
+        return Ok(());
+    }
+
+    if let Some(secrets_directory) = location.parent() {
+        if let Err(e) = std::fs::create_dir_all(secrets_directory) {
+            return Err(format!(
+                "Unable to create secrets directory {}: {}",
+                secrets_directory.to_string_lossy(),
+                e
+            ));
+        }
+    }
+
+    let serialized_secret_store = match serde_json::to_string(secret_store) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!("Unable to serialize secrets: {}", e));
+        }
+    };
+
+    let mut open_options = std::fs::OpenOptions::new();
+    open_options.write(true).create(true).truncate(true);
+
+    // only the user can read the secrets
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        open_options.mode(0o600);
+    }
+
+    let mut file = match open_options.open(location) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!(
+                "Unable to create secrets file at {}: {}",
+                location.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    if let Err(e) = file.write_all(serialized_secret_store.as_bytes()) {
+        return Err(format!(
+            "Unable to write secrets to {}: {}",
+            location.to_string_lossy(),
+            e
+        ));
+    }
+
+    return Ok(());
+}
+
+/// Read the secrets store of the project, empty if the project has no secrets
+pub fn read_secret_store(location: &PathBuf) -> Result<SecretStore, String> {
+    if !location.exists() {
+        return Ok(SecretStore::default());
+    }
+
+    let serialized_secret_store = match std::fs::read_to_string(location) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!(
+                "Unable to read secrets file {}: {}",
+                location.to_string_lossy(),
+                e
+            ));
+        }
+    };
+
+    return match serde_json::from_str(&serialized_secret_store) {
+        Ok(some) => Ok(some),
+        Err(e) => Err(format!(
+            "Unable to read secrets file {}, it is not a valid secrets file: {}",
+            location.to_string_lossy(),
+            e
+        )),
+    };
+}
+
+/// remove the secrets store of the project, if it has one
+pub fn remove_secret_store(location: &PathBuf) {
+    if !location.exists() {
+        return;
+    }
+
+    if let Err(e) = std::fs::remove_file(location) {
+        println!(
+            "Unable to delete secrets file at {}: {}",
+            location.to_string_lossy(),
+            e
+        );
+    }
+}
+
+/// Replace every occurrence of the secrets in the text with the secret mask
+pub fn redact_secrets(text: &str, secrets: &Vec<String>) -> String {
+    let mut redacted = text.to_string();
+
+    for secret in secrets.iter() {
+        // an empty secret would match everywhere
+        if secret.is_empty() {
+            continue;
+        }
+
+        redacted = redacted.replace(secret.as_str(), SECRET_MASK);
+    }
+
+    return redacted;
+}
@@ -0,0 +1,168 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+// start and end of a reference to a variable in a parameter value, such as ${base_url}
+const VARIABLE_REFERENCE_START: &str = "${";
+const VARIABLE_REFERENCE_END: &str = "}";
+
+/// variables of the project grouped into named profiles, such as dev, staging or local
+/// profile name -> variable name -> value
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+#[serde(transparent)]
+pub struct VariableProfiles {
+    profiles: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl VariableProfiles {
+    /// add an empty profile
+    pub fn add_profile(&mut self, profile_name: String) -> Result<(), String> {
+        if profile_name.trim().is_empty() {
+            return Err("profile name cannot be empty".to_string());
+        }
+
+        if self.profiles.contains_key(&profile_name) {
+            return Err(format!("profile {} already exists", profile_name));
+        }
+
+        self.profiles.insert(profile_name, BTreeMap::new());
+
+        return Ok(());
+    }
+
+    /// remove a profile with all its variables
+    pub fn remove_profile(&mut self, profile_name: &String) -> Result<(), String> {
+        return match self.profiles.remove(profile_name) {
+            Some(_) => Ok(()),
+            None => Err(format!("profile {} does not exist", profile_name)),
+        };
+    }
+
+    /// set the value of a variable of a profile, adding the variable if it does not exist
+    pub fn set_variable(
+        &mut self,
+        profile_name: &String,
+        variable_name: String,
+        value: String,
+    ) -> Result<(), String> {
+        if !is_valid_variable_name(&variable_name) {
+            return Err(format!(
+                "variable name {} must start with a letter or underscore and only contain letters, digits and underscores",
+                variable_name
+            ));
+        }
+
+        let variables = match self.profiles.get_mut(profile_name) {
+            Some(some) => some,
+            None => {
+                return Err(format!("profile {} does not exist", profile_name));
+            }
+        };
+
+        variables.insert(variable_name, value);
+
+        return Ok(());
+    }
+
+    /// remove a variable of a profile
+    pub fn remove_variable(
+        &mut self,
+        profile_name: &String,
+        variable_name: &String,
+    ) -> Result<(), String> {
+        let variables = match self.profiles.get_mut(profile_name) {
+            Some(some) => some,
+            None => {
+                return Err(format!("profile {} does not exist", profile_name));
+            }
+        };
+
+        return match variables.remove(variable_name) {
+            Some(_) => Ok(()),
+            None => Err(format!(
+                "variable {} does not exist in profile {}",
+                variable_name, profile_name
+            )),
+        };
+    }
+
+    /// get the variables of a profile
+    pub fn get_variables(
+        &self,
+        profile_name: &String,
+    ) -> Result<&BTreeMap<String, String>, String> {
+        return match self.profiles.get(profile_name) {
+            Some(some) => Ok(some),
+            None => Err(format!("profile {} does not exist", profile_name)),
+        };
+    }
+}
+
+/// variable names are identifiers, so the end of a reference is never part of the name
+fn is_valid_variable_name(variable_name: &str) -> bool {
+    let mut characters = variable_name.chars();
+
+    return match characters.next() {
+        Some(first) if first.is_ascii_alphabetic() || first == '_' => {
+            characters.all(|character| character.is_ascii_alphanumeric() || character == '_')
+        }
+        _ => false,
+    };
+}
+
+/// If the value references a variable, and so can only be converted to its parameter type
+/// once its variables are substituted
+pub fn contains_variable_reference(value: &str) -> bool {
+    return value.contains(VARIABLE_REFERENCE_START);
+}
+
+/// Substitute the ${name} references of the value with the value of their variable,
+/// failing on references to variables that are not given. $${ is kept as ${
+pub fn substitute_variables(
+    value: &str,
+    variables: &BTreeMap<String, String>,
+) -> Result<String, String> {
+    let mut substituted = String::new();
+    let mut remaining = value;
+
+    while let Some(reference_start) = remaining.find(VARIABLE_REFERENCE_START) {
+        // escaped reference, the $ before it escapes it
+        if remaining[..reference_start].ends_with('$') {
+            substituted.push_str(&remaining[..reference_start - 1]);
+            substituted.push_str(VARIABLE_REFERENCE_START);
+            remaining = &remaining[reference_start + VARIABLE_REFERENCE_START.len()..];
+
+            continue;
+        }
+
+        substituted.push_str(&remaining[..reference_start]);
+
+        let name_start = reference_start + VARIABLE_REFERENCE_START.len();
+        let name_length = match remaining[name_start..].find(VARIABLE_REFERENCE_END) {
+            Some(some) => some,
+            None => {
+                return Err(format!(
+                    "reference to a variable in {} is missing its closing }}",
+                    value
+                ));
+            }
+        };
+        let variable_name = &remaining[name_start..name_start + name_length];
+
+        match variables.get(variable_name) {
+            Some(variable_value) => substituted.push_str(variable_value),
+            None => {
+                return Err(format!(
+                    "variable {} is not defined in the selected profile",
+                    variable_name
+                ));
+            }
+        };
+
+        remaining = &remaining[name_start + name_length + VARIABLE_REFERENCE_END.len()..];
+    }
+
+    substituted.push_str(remaining);
+
+    return Ok(substituted);
+}
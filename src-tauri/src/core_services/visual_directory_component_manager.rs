@@ -2,6 +2,7 @@
 use bimap::BiHashMap;
 use serde::{Deserialize, Serialize};
 
+use crate::state_management::external_snippet_manager::{ExternalSnippetManager, ExternalSnippetMetadata, SnippetInitializationError};
 use crate::utils::sequential_id_generator::{SequentialIdGenerator, Uuid};
 
 use super::directory_manager::{SnippetDirectory, SnippetDirectoryCategory, SnippetDirectoryEntry, SnippetDirectorySnippet, SnippetDirectoryType};
@@ -27,6 +28,10 @@ pub struct FrontDirectoryContent {
     is_directory: bool,
     level: u32,
     showing: bool,
+    // description, tags and the like of snippets, for tooltips and filtering by tag
+    metadata: Option<ExternalSnippetMetadata>,
+    // why the snippet could not be initialized, if it is broken
+    initialization_error: Option<SnippetInitializationError>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -55,7 +60,7 @@ impl VisualDirectoryComponentManager {
     /// Get the directory as front elements
     /// These are displayed in descencing order, expandable and contractable based on parent
     /// If the directory were to get reloaded, we would need to reload the front directory
-    pub fn get_directory_as_front(&mut self, snippet_directory: &SnippetDirectory, sequential_id_generator: &mut SequentialIdGenerator) -> Vec::<FrontDirectoryContent> {
+    pub fn get_directory_as_front(&mut self, snippet_directory: &SnippetDirectory, external_snippet_manager: &ExternalSnippetManager, sequential_id_generator: &mut SequentialIdGenerator) -> Vec::<FrontDirectoryContent> {
         // Walk directory recursivly, keeping track of level, calling VisualDirectoryComponentManager::new_from_directory_entry
         let mut front_directory_content = Vec::<FrontDirectoryContent>::new();
 
@@ -69,18 +74,18 @@ impl VisualDirectoryComponentManager {
 
         // We don't want to parse the root directory
 
-        self.front_directory_walker_seeker(root_directory_entry, &mut front_directory_content, sequential_id_generator);
+        self.front_directory_walker_seeker(root_directory_entry, &mut front_directory_content, external_snippet_manager, sequential_id_generator);
 
         // Remove root from directory fronts, which will be the first element
         return front_directory_content;
     }
 
-    fn front_directory_walker_seeker(&mut self, root_directory_entry: &SnippetDirectoryEntry, front_directory_content: &mut Vec::<FrontDirectoryContent>, sequential_id_generator: &mut SequentialIdGenerator) {
+    fn front_directory_walker_seeker(&mut self, root_directory_entry: &SnippetDirectoryEntry, front_directory_content: &mut Vec::<FrontDirectoryContent>, external_snippet_manager: &ExternalSnippetManager, sequential_id_generator: &mut SequentialIdGenerator) {
         match root_directory_entry.get_inner_as_ref() {
             SnippetDirectoryType::Category(category) => {
                 // Call children of category directory entry
                 for child_directory_entry in category.get_children() {
-                    self.front_directory_walker_helper(child_directory_entry, 1, front_directory_content, sequential_id_generator);
+                    self.front_directory_walker_helper(child_directory_entry, 1, front_directory_content, external_snippet_manager, sequential_id_generator);
                 }
             }
             SnippetDirectoryType::Snippet(_) => {
@@ -89,7 +94,7 @@ impl VisualDirectoryComponentManager {
         };
     }
 
-    fn front_directory_walker_helper(&mut self, current_directory_entry: &SnippetDirectoryEntry, level: u32, front_directory_content: &mut Vec::<FrontDirectoryContent>, sequential_id_generator: &mut SequentialIdGenerator) {
+    fn front_directory_walker_helper(&mut self, current_directory_entry: &SnippetDirectoryEntry, level: u32, front_directory_content: &mut Vec::<FrontDirectoryContent>, external_snippet_manager: &ExternalSnippetManager, sequential_id_generator: &mut SequentialIdGenerator) {
         let name = current_directory_entry.get_name();
         let uuid = current_directory_entry.get_uuid();
 
@@ -101,12 +106,12 @@ impl VisualDirectoryComponentManager {
 
                 // Call children of category directory entry
                 for child_directory_entry in category.get_children() {
-                    self.front_directory_walker_helper(child_directory_entry, level + 1, front_directory_content, sequential_id_generator);
+                    self.front_directory_walker_helper(child_directory_entry, level + 1, front_directory_content, external_snippet_manager, sequential_id_generator);
                 }
             },
             SnippetDirectoryType::Snippet(snippet) => {
                 // Create snippet as front
-                let front_directory_entry = FrontDirectoryContent::new_snippet_from_directory_entry(name, uuid, snippet, level, self, sequential_id_generator);
+                let front_directory_entry = FrontDirectoryContent::new_snippet_from_directory_entry(name, uuid, snippet, level, self, external_snippet_manager, sequential_id_generator);
                 front_directory_content.push(front_directory_entry);
             },
         };
@@ -122,12 +127,14 @@ impl FrontDirectoryContent {
             is_directory: is_directory,
             level: level,
             showing: showing,
+            metadata: None,
+            initialization_error: None,
         };
 
         return front_content;
     }
 
-    pub fn new_from_directory_entry(directory_entry: &SnippetDirectoryEntry, level: u32, visual_directory_component_manager: &mut VisualDirectoryComponentManager, sequential_id_generator: &mut SequentialIdGenerator) -> FrontDirectoryContent {
+    pub fn new_from_directory_entry(directory_entry: &SnippetDirectoryEntry, level: u32, visual_directory_component_manager: &mut VisualDirectoryComponentManager, external_snippet_manager: &ExternalSnippetManager, sequential_id_generator: &mut SequentialIdGenerator) -> FrontDirectoryContent {
         let name = directory_entry.get_name();
         let directory_entry_uuid = directory_entry.get_uuid();
 
@@ -136,12 +143,20 @@ impl FrontDirectoryContent {
                 return FrontDirectoryContent::new_category_from_directory_entry(name, directory_entry_uuid, some, level, visual_directory_component_manager, sequential_id_generator);
             },
             SnippetDirectoryType::Snippet(some) => {
-                return FrontDirectoryContent::new_snippet_from_directory_entry(name, directory_entry_uuid, some, level, visual_directory_component_manager, sequential_id_generator)
+                return FrontDirectoryContent::new_snippet_from_directory_entry(name, directory_entry_uuid, some, level, visual_directory_component_manager, external_snippet_manager, sequential_id_generator)
             },
         }
     }
 
-    fn new_snippet_from_directory_entry(name: String, directory_entry_uuid: Uuid, directory_entry: &SnippetDirectorySnippet, level: u32, visual_directory_component_manager: &mut VisualDirectoryComponentManager, sequential_id_generator: &mut SequentialIdGenerator) -> FrontDirectoryContent {
+    fn new_snippet_from_directory_entry(name: String, directory_entry_uuid: Uuid, directory_entry: &SnippetDirectorySnippet, level: u32, visual_directory_component_manager: &mut VisualDirectoryComponentManager, external_snippet_manager: &ExternalSnippetManager, sequential_id_generator: &mut SequentialIdGenerator) -> FrontDirectoryContent {
+        // snippets that failed to initialize have no external snippet, and so no metadata
+        let metadata = external_snippet_manager
+            .find_external_snippet_from_directory_uuid(directory_entry_uuid)
+            .map(|external_snippet| external_snippet.get_metadata().to_owned());
+        let initialization_error = external_snippet_manager
+            .find_initialization_error_from_directory_uuid(directory_entry_uuid)
+            .cloned();
+
         let front_directory_content = FrontDirectoryContent {
             id: sequential_id_generator.get_id(),
             name: name,
@@ -149,6 +164,8 @@ impl FrontDirectoryContent {
             is_directory: false,
             level: level,
             showing: false,
+            metadata: metadata,
+            initialization_error: initialization_error,
         };
 
         visual_directory_component_manager.directory_front_to_directory_entry.insert(front_directory_content.id, directory_entry_uuid);
@@ -170,7 +187,9 @@ impl FrontDirectoryContent {
             file_type: FrontDirectoryContentType::Directory,
             is_directory: false,
             level: level,
-            showing: showing
+            showing: showing,
+            metadata: None,
+            initialization_error: None,
         };
 
         visual_directory_component_manager.directory_front_to_directory_entry.insert(front_directory_content.id, directory_entry_uuid);
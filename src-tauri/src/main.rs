@@ -5,6 +5,9 @@ use std::ops::DerefMut;
 
 //use core_services::
 
+use core_services::concurrent_processes::{
+    spawn_autosave_thread, spawn_snippet_directory_watcher_thread,
+};
 use core_services::installation_manager::install_runables;
 
 //use snippet_python_module::python_module::call_init_2;
@@ -13,18 +16,33 @@ use crate::tauri_services::directory_tauri_service::{
     get_snippet_directory_details, get_workspace_details,
     spawn_initialize_snippet_directory_and_workspace, spawn_refresh_workspace_event,
 };
+use crate::tauri_services::logging_tauri_service::{
+    get_project_past_logs, read_project_past_log, subscribe_log_stream,
+};
 use crate::tauri_services::project_tauri_service::{
-    delete_project, get_directory_id_from_package_path,
+    close_project, delete_project, discard_recovery_project, get_directory_id_from_package_path,
     get_front_parameter_id_from_snippet_uuid_and_name,
-    get_front_snippet_connector_id_from_snippet_uuid_and_name, open_project, save_project,
+    get_front_snippet_connector_id_from_snippet_uuid_and_name, get_open_projects,
+    get_recovery_projects, new_project, open_project, restore_recovery_project, save_project,
+    switch_project,
+};
+use crate::tauri_services::run_history_tauri_service::{
+    compare_project_runs, get_project_runs, open_project_run,
 };
 use crate::tauri_services::snippet_tauri_service::{
-    check_pipeline_connector_capacity_full, delete_pipeline, delete_snippet, get_id,
+    check_pipeline_connector_capacity_full, copy_snippets, create_composite_snippet,
+    delete_pipeline, delete_snippet, duplicate_snippets, get_id,
     get_pipeline_connector_uuids_from_pipeline, get_pipeline_connector_uuids_from_snippet,
-    get_snippet_pipelines, new_pipeline, new_snippet, spawn_run_snippets,
-    update_snippet_parameter_value, update_snippet_position, validate_pipeline_connection,
+    get_snippet_pipelines, new_pipeline, new_snippet, paste_snippets, redo, spawn_run_snippets,
+    undo, update_snippet_parameter_value, update_snippet_position, validate_pipeline_connection,
+};
+use crate::tauri_services::variable_profile_tauri_service::{
+    add_project_variable_profile, get_project_variable_profiles, remove_project_variable,
+    remove_project_variable_profile, set_project_variable,
+};
+use crate::tauri_services::window_session_tauri_service::{
+    close_window_session, new_window_session,
 };
-use crate::tauri_services::window_session_tauri_service::new_window_session;
 
 pub mod core_components;
 pub mod core_services;
@@ -40,12 +58,35 @@ fn main() {
     // install necessary python runable files
     install_runables();
 
+    // autosave the open projects, so unsaved changes can be recovered after a crash
+    spawn_autosave_thread(application_state_guard.0.clone());
+
+    // the window sessions of a window are closed with it
+    let window_event_application_state = application_state_guard.clone();
+
+    // changed snippets are reloaded while the app is running
+    let watcher_application_state = application_state_guard.0.clone();
+
     tauri::Builder::default()
-        .setup(|_app| return Ok(()))
+        .setup(move |app| {
+            spawn_snippet_directory_watcher_thread(watcher_application_state, app.handle());
+
+            return Ok(());
+        })
         .manage(application_state_guard)
+        .on_window_event(move |event| {
+            if let tauri::WindowEvent::Destroyed = event.event() {
+                window_event_application_state
+                    .0
+                    .lock()
+                    .unwrap()
+                    .close_window_sessions_of_window(event.window().label());
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             logln,
             new_window_session,
+            close_window_session,
             new_snippet,
             validate_pipeline_connection,
             new_pipeline,
@@ -68,7 +109,31 @@ fn main() {
             open_project,
             get_workspace_details,
             spawn_refresh_workspace_event,
-            delete_project
+            delete_project,
+            get_recovery_projects,
+            restore_recovery_project,
+            discard_recovery_project,
+            undo,
+            redo,
+            copy_snippets,
+            paste_snippets,
+            duplicate_snippets,
+            create_composite_snippet,
+            new_project,
+            get_open_projects,
+            switch_project,
+            close_project,
+            subscribe_log_stream,
+            get_project_past_logs,
+            read_project_past_log,
+            get_project_runs,
+            open_project_run,
+            compare_project_runs,
+            get_project_variable_profiles,
+            add_project_variable_profile,
+            remove_project_variable_profile,
+            set_project_variable,
+            remove_project_variable
         ])
         .run(tauri::generate_context!())
         .expect("error while starting tauri application");
@@ -1,7 +1,8 @@
 //https://pyo3.rs/main/building_and_distribution#dynamically-embedding-the-python-interpreter
 
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, Read};
+use std::io::Read;
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -10,7 +11,8 @@ use pyo3::prelude::*;
 use pyo3::types::*;
 
 use crate::state_management::external_snippet_manager::{
-    ExternalSnippetParameterType, PackagePath,
+    ExternalSnippetMetadata, ExternalSnippetParameterOptions, ExternalSnippetParameterType,
+    PackagePath, SnippetInitializationError, SnippetInitializationPhase,
 };
 use crate::utils::sequential_id_generator::Uuid;
 
@@ -32,11 +34,15 @@ pub struct PythonSnippetBuildInformation {
 // the state of the builder once the snippets have been built
 pub struct FinalizedPythonSnipppetInitializerBuilder {
     built_snippets: Vec<PythonSnippetBuilderWrapper>,
+    // the snippets that could not be built
+    initialization_errors: Vec<SnippetInitializationError>,
 }
 
 // containing the state of the python builder after building
 pub struct PythonSnippetBuilderWrapper {
     directory_entry_uuid: Uuid,
+    // app.py file of the snippet
+    path: PathBuf,
     python_snippet_builder: PythonSnippetBuilder,
     package_path: PackagePath,
 }
@@ -50,8 +56,16 @@ pub struct PythonSnippetBuilder {
     inputs: Vec<String>,
     #[pyo3(get)]
     outputs: Vec<String>,
+    // (name, type, options)
+    #[pyo3(get)]
+    parameters: Vec<(String, String, ExternalSnippetParameterOptions)>,
+    #[pyo3(get)]
+    metadata: ExternalSnippetMetadata,
+    // io point name -> description
     #[pyo3(get)]
-    parameters: Vec<(String, String)>,
+    input_descriptions: HashMap<String, String>,
+    #[pyo3(get)]
+    output_descriptions: HashMap<String, String>,
 }
 
 impl InitializedPythonSnippetInitializerBuilder {
@@ -76,137 +90,186 @@ impl InitializedPythonSnippetInitializerBuilder {
         self.build_information.push(snippet_build_information);
     }
 
-    pub fn build(self) -> Result<FinalizedPythonSnipppetInitializerBuilder, String> {
-        let built_snippets = self.initialize_snippets()?;
+    /// initialize every snippet, collecting the snippets that could not be initialized
+    /// instead of failing on them
+    pub fn build(self) -> FinalizedPythonSnipppetInitializerBuilder {
+        let (built_snippets, initialization_errors) = self.initialize_snippets();
 
-        return Ok(FinalizedPythonSnipppetInitializerBuilder {
+        return FinalizedPythonSnipppetInitializerBuilder {
             built_snippets: built_snippets,
-        });
+            initialization_errors: initialization_errors,
+        };
     }
 
-    fn initialize_snippets(self) -> Result<Vec<PythonSnippetBuilderWrapper>, String> {
+    fn initialize_snippets(
+        self,
+    ) -> (
+        Vec<PythonSnippetBuilderWrapper>,
+        Vec<SnippetInitializationError>,
+    ) {
         let python_build_information_list = self.build_information;
 
         // set the pythonpath if not already set
         set_python_path();
 
         // We want to get the rust object since each python object will hold and maintain a reference to the gil and gil pool
-        let python_built_snippets = Python::with_gil(
-            |py| -> Result<Vec<PythonSnippetBuilderWrapper>, String> {
-                let mut python_snippet_builders: Vec<PythonSnippetBuilderWrapper> =
-                    Vec::<PythonSnippetBuilderWrapper>::new();
-
-                //TODO handle cases
-                // misnames python file, how do we communicate this to the end user?
-
-                for python_build_information in python_build_information_list {
-                    // Create file path
-                    let mut main_python_file_path = python_build_information.path.into_os_string();
-                    main_python_file_path.push("/app.py");
-                    let full_path: PathBuf = main_python_file_path.into();
-
-                    // Read the main file and the main file only
-                    let mut file = match File::open(full_path) {
-                        Ok(file) => file,
-                        Err(e) => {
-                            return Err(format!(
-                                "Could not open file to read python sippet: {}",
-                                e.to_string()
-                            ));
-                        }
-                    };
-
-                    // Read the contents of the file
-                    let mut contents = String::new();
-
-                    // Attempt to read the file contents in to the string
-                    match file.read_to_string(&mut contents) {
-                        io::Result::Ok(_) => (),
-                        io::Result::Err(e) => {
-                            //TODO return error
-                            return Err(format!(
-                                "Could not read the contents of the main python file: {}",
-                                e.to_string()
-                            ));
-                        }
-                    };
-
-                    // TODO Create new gil pool
-                    /*let pool = unsafe { py.new_pool() };
-                    let py = pool.python();*/
-
-                    // import code to pool
-                    let fun = match PyModule::from_code_bound(py, &contents, "", "") {
-                        PyResult::Ok(some) => some,
-                        PyResult::Err(e) => {
-                            return Err(format!(
-                                "Could not create python code from main python file: {}",
-                                e.to_string()
-                            ));
-                        }
-                    };
-
-                    let fun: Py<PyAny> = match fun.getattr("init") {
-                    PyResult::Ok(some) => some,
-                    PyResult::Err(e) => {
-                        return Err(format!("Could not get init function attribute from main python file code: {}", e.to_string()));
+        return Python::with_gil(|py| {
+            let mut python_snippet_builders: Vec<PythonSnippetBuilderWrapper> =
+                Vec::<PythonSnippetBuilderWrapper>::new();
+            let mut initialization_errors = Vec::<SnippetInitializationError>::new();
+
+            for python_build_information in python_build_information_list {
+                // a broken snippet should not keep the others from loading
+                match initialize_snippet(py, &python_build_information) {
+                    Ok(python_snippet_builder) => {
+                        // Create wrapper contianing extra necessary information
+                        python_snippet_builders.push(PythonSnippetBuilderWrapper::new(
+                            python_build_information.directory_uuid,
+                            python_build_information.package_path,
+                            python_build_information.path.join("app.py"),
+                            python_snippet_builder,
+                        ));
+                    }
+                    Err(e) => {
+                        initialization_errors.push(e);
                     }
-                }
-                .into();
-
-                    // Create arguments for init function
-                    // which includes a python callable object
-                    let obj =
-                        Bound::new(py, PythonSnippetBuilder::new(python_build_information.name))
-                            .unwrap();
-                    let args = PyTuple::new_bound(py, &[obj]);
-
-                    // Define python function call closure
-                    let init_python_return = match fun.call1(py, args) {
-                        PyResult::Ok(some) => some,
-                        PyResult::Err(e) => {
-                            return Err(format!(
-                                "Error calling init function from main python file: {}",
-                                e.to_string()
-                            ));
-                        }
-                    };
-
-                    let init_python_return: PythonSnippetBuilder = match init_python_return
-                        .extract(py)
-                    {
-                        PyResult::Ok(some) => some,
-                        PyResult::Err(e) => {
-                            return Err(format!("Error extacting python snippet builder result from init function call: {}", e.to_string()));
-                        }
-                    };
-
-                    // Create wrapper contianing extra necessary information
-                    let python_return_wrapper = PythonSnippetBuilderWrapper::new(
-                        python_build_information.directory_uuid,
-                        python_build_information.package_path,
-                        init_python_return,
-                    );
-
-                    python_snippet_builders.push(python_return_wrapper);
-                }
-
-                return Ok(python_snippet_builders);
-            },
-        )?;
-
-        return Ok(python_built_snippets);
+                };
+            }
+
+            return (python_snippet_builders, initialization_errors);
+        });
     }
 }
 
+/// Open, compile and call the init function of the app.py file of the snippet
+fn initialize_snippet(
+    py: Python<'_>,
+    python_build_information: &PythonSnippetBuildInformation,
+) -> Result<PythonSnippetBuilder, SnippetInitializationError> {
+    // Create file path
+    let full_path: PathBuf = python_build_information.path.join("app.py");
+
+    // creates the error of the snippet for the phase it failed in
+    let initialization_error =
+        |phase: SnippetInitializationPhase, message: String, traceback: Option<String>| {
+            return SnippetInitializationError::new(
+                python_build_information.directory_uuid,
+                python_build_information.package_path.to_owned(),
+                full_path.to_owned(),
+                phase,
+                message,
+                traceback,
+            );
+        };
+
+    // Read the main file and the main file only
+    let mut file = match File::open(&full_path) {
+        Ok(file) => file,
+        Err(e) => {
+            return Err(initialization_error(
+                SnippetInitializationPhase::Open,
+                format!("Could not open file to read python sippet: {}", e),
+                None,
+            ));
+        }
+    };
+
+    // Read the contents of the file
+    let mut contents = String::new();
+
+    // Attempt to read the file contents in to the string
+    if let Err(e) = file.read_to_string(&mut contents) {
+        return Err(initialization_error(
+            SnippetInitializationPhase::Open,
+            format!("Could not read the contents of the main python file: {}", e),
+            None,
+        ));
+    }
+
+    // import code to pool, with the path of the file so tracebacks point to it
+    let module = match PyModule::from_code_bound(py, &contents, &full_path.to_string_lossy(), "") {
+        PyResult::Ok(some) => some,
+        PyResult::Err(e) => {
+            return Err(initialization_error(
+                SnippetInitializationPhase::Compile,
+                format!("Could not create python code from main python file: {}", e),
+                format_traceback(py, &e),
+            ));
+        }
+    };
+
+    let fun = match module.getattr("init") {
+        PyResult::Ok(some) => some,
+        PyResult::Err(e) => {
+            return Err(initialization_error(
+                SnippetInitializationPhase::Compile,
+                format!(
+                    "Could not get init function attribute from main python file code: {}",
+                    e
+                ),
+                None,
+            ));
+        }
+    };
+
+    // Create arguments for init function
+    // which includes a python callable object
+    let obj = match Bound::new(
+        py,
+        PythonSnippetBuilder::new(python_build_information.name.to_owned()),
+    ) {
+        PyResult::Ok(some) => some,
+        PyResult::Err(e) => {
+            return Err(initialization_error(
+                SnippetInitializationPhase::Init,
+                format!("Could not create the python snippet builder: {}", e),
+                None,
+            ));
+        }
+    };
+
+    // Define python function call closure
+    let init_python_return = match fun.call1((obj,)) {
+        PyResult::Ok(some) => some,
+        PyResult::Err(e) => {
+            return Err(initialization_error(
+                SnippetInitializationPhase::Init,
+                format!("Error calling init function from main python file: {}", e),
+                format_traceback(py, &e),
+            ));
+        }
+    };
+
+    return match init_python_return.extract::<PythonSnippetBuilder>() {
+        PyResult::Ok(some) => Ok(some),
+        PyResult::Err(e) => Err(initialization_error(
+            SnippetInitializationPhase::Init,
+            format!(
+                "Error extacting python snippet builder result from init function call, init needs to return the snippet it was given: {}",
+                e
+            ),
+            None,
+        )),
+    };
+}
+
+/// format the traceback of the python error, if it has one
+fn format_traceback(py: Python<'_>, error: &PyErr) -> Option<String> {
+    return error
+        .traceback_bound(py)
+        .and_then(|traceback| traceback.format().ok());
+}
+
 impl PythonSnippetBuilderWrapper {
     pub fn new(
         directory_entry_uuid: Uuid,
         package_path: PackagePath,
+        path: PathBuf,
         python_snippet_builder: PythonSnippetBuilder,
     ) -> Self {
         return PythonSnippetBuilderWrapper {
             directory_entry_uuid: directory_entry_uuid,
+            path: path,
             package_path: package_path,
             python_snippet_builder: python_snippet_builder,
         };
@@ -228,13 +291,37 @@ impl PythonSnippetBuilderWrapper {
         &self.python_snippet_builder.outputs
     }
 
-    pub fn get_parameters(&self) -> &Vec<(String, String)> {
+    pub fn get_parameters(&self) -> &Vec<(String, String, ExternalSnippetParameterOptions)> {
         &self.python_snippet_builder.parameters
     }
 
+    pub fn get_metadata(&self) -> &ExternalSnippetMetadata {
+        &self.python_snippet_builder.metadata
+    }
+
+    pub fn get_input_description(&self, name: &String) -> Option<String> {
+        return self
+            .python_snippet_builder
+            .input_descriptions
+            .get(name)
+            .cloned();
+    }
+
+    pub fn get_output_description(&self, name: &String) -> Option<String> {
+        return self
+            .python_snippet_builder
+            .output_descriptions
+            .get(name)
+            .cloned();
+    }
+
     pub fn get_package_path(&self) -> PackagePath {
         return self.package_path.to_owned();
     }
+
+    pub fn get_path(&self) -> PathBuf {
+        return self.path.to_owned();
+    }
 }
 impl PythonSnippetBuildInformation {
     /// create new python build information for a snippet to be built
@@ -257,6 +344,10 @@ impl FinalizedPythonSnipppetInitializerBuilder {
     pub fn get_build_information(&self) -> &Vec<PythonSnippetBuilderWrapper> {
         return &self.built_snippets;
     }
+
+    pub fn get_initialization_errors(&self) -> &Vec<SnippetInitializationError> {
+        return &self.initialization_errors;
+    }
 }
 
 impl PythonSnippetBuilder {
@@ -274,7 +365,10 @@ impl PythonSnippetBuilder {
             name: name,
             inputs: Vec::<String>::new(),
             outputs: Vec::<String>::new(),
-            parameters: Vec::<(String, String)>::new(),
+            parameters: Vec::<(String, String, ExternalSnippetParameterOptions)>::new(),
+            metadata: ExternalSnippetMetadata::default(),
+            input_descriptions: HashMap::new(),
+            output_descriptions: HashMap::new(),
         }
     }
 
@@ -286,9 +380,9 @@ impl PythonSnippetBuilder {
         return Ok(());
     }*/
     /// callable method from python
-    /// insert io input point to snippet
-    #[pyo3(text_signature = "$self, name")]
-    fn add_input(&mut self, name: String) -> PyResult<()> {
+    /// insert io input point to snippet, optionally with a description
+    #[pyo3(signature = (name, description=None))]
+    fn add_input(&mut self, name: String, description: Option<String>) -> PyResult<()> {
         // if inputs is already in output, raise error to python
         if self.inputs.contains(&name) {
             return Err(PyValueError::new_err(format!(
@@ -297,6 +391,10 @@ impl PythonSnippetBuilder {
             )));
         }
 
+        if let Some(description) = description {
+            self.input_descriptions.insert(name.to_owned(), description);
+        }
+
         // insert input
         self.inputs.push(name);
 
@@ -304,9 +402,9 @@ impl PythonSnippetBuilder {
     }
 
     /// callable method from python
-    /// insert io input point to snippet
-    #[pyo3(text_signature = "$self, name")]
-    fn add_output(&mut self, name: String) -> PyResult<()> {
+    /// insert io output point to snippet, optionally with a description
+    #[pyo3(signature = (name, description=None))]
+    fn add_output(&mut self, name: String, description: Option<String>) -> PyResult<()> {
         // if inputs is already in output, raise error to python
         if self.outputs.contains(&name) {
             return Err(PyValueError::new_err(format!(
@@ -315,17 +413,37 @@ impl PythonSnippetBuilder {
             )));
         }
 
-        // insert input
+        if let Some(description) = description {
+            self.output_descriptions
+                .insert(name.to_owned(), description);
+        }
+
+        // insert output
         self.outputs.push(name);
 
         return Ok(());
     }
 
-    /// add parameter to snippet. Name must be in the list of supported types
+    /// add parameter to snippet. Name must be in the list of supported types:
+    /// SingleLineText, MultiLineText, Integer, Float, Boolean, Enum(choice, other choice), Json,
+    /// FilePath, DirectoryPath or Secret
     /// each parameter has it's own schema (TODO be supported).
     /// parameters are considered inputs
-    #[pyo3(text_signature = "$self, parameter_name, parameter_type")]
-    fn add_parameter(&mut self, parameter_name: String, parameter_type: String) -> PyResult<()> {
+    ///
+    /// optionally takes the default value, a description, if it is required,
+    /// the min and max of number parameters and a regex the value of text parameters must match
+    #[pyo3(signature = (parameter_name, parameter_type, default=None, description=None, required=false, min=None, max=None, regex=None))]
+    fn add_parameter(
+        &mut self,
+        parameter_name: String,
+        parameter_type: String,
+        default: Option<&Bound<'_, PyAny>>,
+        description: Option<String>,
+        required: bool,
+        min: Option<f64>,
+        max: Option<f64>,
+        regex: Option<String>,
+    ) -> PyResult<()> {
         // if inputs is already in output, raise error to python
         if self
             .parameters
@@ -343,21 +461,85 @@ impl PythonSnippetBuilder {
         }
 
         // needs to be of a supported parameter type
-        match ExternalSnippetParameterType::from_str(&parameter_type) {
-            Ok(_) => (),
+        let parameter_type_proper = match ExternalSnippetParameterType::from_str(&parameter_type) {
+            Ok(some) => some,
             Err(e) => {
                 return Err(PyValueError::new_err(format!(
-                    "parameter type {} is not of a valid type",
-                    parameter_type
+                    "parameter type {} is not of a valid type: {}",
+                    parameter_type, e
                 )));
             }
         };
 
+        let default = match default {
+            Some(value) => Some(default_value_to_string(&parameter_type_proper, value)?),
+            None => None,
+        };
+
+        let options =
+            ExternalSnippetParameterOptions::new(default, description, required, min, max, regex);
+
+        // options need to fit the parameter type
+        if let Err(e) = options.validate(&parameter_type_proper) {
+            return Err(PyValueError::new_err(format!(
+                "Cannot insert {} into snippet {}: {}",
+                parameter_name, self.name, e
+            )));
+        }
+
         // insert parameter
-        self.parameters.push((parameter_name, parameter_type));
+        self.parameters
+            .push((parameter_name, parameter_type, options));
 
         return Ok(());
     }
+
+    /// set the information shown about the snippet in the library and on the canvas
+    /// all optional, tags are what the library can be filtered by
+    /// and the icon is short text, such as an emoji, shown before the name
+    #[pyo3(signature = (description=None, tags=Vec::new(), version=None, author=None, icon=None))]
+    fn set_metadata(
+        &mut self,
+        description: Option<String>,
+        tags: Vec<String>,
+        version: Option<String>,
+        author: Option<String>,
+        icon: Option<String>,
+    ) -> PyResult<()> {
+        // tags are matched whole, so they cannot be blank
+        if tags.iter().any(|tag| tag.trim().is_empty()) {
+            return Err(PyValueError::new_err(format!(
+                "Cannot set the tags of snippet {}, tags cannot be empty",
+                self.name
+            )));
+        }
+
+        self.metadata = ExternalSnippetMetadata::new(description, tags, version, author, icon);
+
+        return Ok(());
+    }
+}
+
+/// Get the default value given from python in string format, the way it would be typed in
+fn default_value_to_string(
+    parameter_type: &ExternalSnippetParameterType,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<String> {
+    // dicts and lists are given as they are for json parameters
+    if let ExternalSnippetParameterType::Json = parameter_type {
+        let json_module = value.py().import_bound("json")?;
+
+        return json_module
+            .call_method1("dumps", (value,))?
+            .extract::<String>();
+    }
+
+    // python writes booleans capitalized
+    if let Ok(boolean) = value.downcast::<PyBool>() {
+        return Ok(boolean.is_true().to_string());
+    }
+
+    return Ok(value.str()?.to_string());
 }
 
 impl Default for PythonSnippetBuilder {
@@ -366,7 +548,10 @@ impl Default for PythonSnippetBuilder {
             name: String::new(),
             inputs: Vec::<String>::new(),
             outputs: Vec::<String>::new(),
-            parameters: Vec::<(String, String)>::new(),
+            parameters: Vec::<(String, String, ExternalSnippetParameterOptions)>::new(),
+            metadata: ExternalSnippetMetadata::default(),
+            input_descriptions: HashMap::new(),
+            output_descriptions: HashMap::new(),
         };
     }
 }
@@ -1,9 +1,11 @@
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     env,
     fs::File,
     io::{self, Read},
     path::PathBuf,
+    str::FromStr,
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 use pathdiff::diff_paths;
@@ -11,21 +13,33 @@ use petgraph::{graph::NodeIndex, visit::EdgeRef};
 use pyo3::{
     prelude::*,
     pymethods,
-    types::{PyAnyMethods, PyDict, PyModule},
+    types::{PyAnyMethods, PyDict, PyList, PyModule},
     IntoPy, Py, PyAny, PyResult, Python,
 };
 
 use crate::{
     core_components::snippet_manager::{
-        SnippetManager, SnippetParameterBaseStorage, SnippetParameterComponent,
+        PipelineComponent, SnippetManager, SnippetParameterBaseStorage, SnippetParameterComponent,
     },
     core_services::{
         concurrent_processes::{get_runables_directory, get_working_directory},
         directory_manager::DirectoryManager,
-        runtime_logging_service::LoggingStreamInstance,
+        project_service::{
+            build_composite_snippet_into_snippet_manager, read_composite_snippet_definition,
+        },
+        run_history_service::{
+            hash_graph_description, truncate_output_preview, RunParameterValue, RunRecord,
+            SnippetRunRecord, SnippetRunStatus,
+        },
+        runtime_logging_service::{LogLevel, LoggingStreamInstance},
     },
     state_management::{
-        external_snippet_manager::ExternalSnippetManager,
+        external_snippet_manager::{
+            BuiltinSnippetKind, ExternalSnippetKind, ExternalSnippetManager, PackagePath,
+            BUILTIN_CONSTANT_VALUE, BUILTIN_FOR_EACH_BODY, BUILTIN_FOR_EACH_ITEMS,
+            BUILTIN_FOR_EACH_PARALLEL, BUILTIN_FOR_EACH_RESULTS, BUILTIN_REROUTE_INPUT,
+            BUILTIN_REROUTE_OUTPUT,
+        },
         visual_snippet_component_manager::VisualSnippetComponentManager,
     },
     utils::sequential_id_generator::{SequentialIdGenerator, Uuid},
@@ -34,6 +48,9 @@ use crate::{
 // location of the python runner library
 const PYTHON_RUNNER_WRAPPER_LOCATION: &str = "snippet_runner.py";
 
+// max number of composite snippets a snippet can be nested in
+const COMPOSITE_SNIPPET_MAX_DEPTH: usize = 32;
+
 // Initialized builder, containing all the information to build the snippets
 pub struct InitializedPythonSnippetRunnerBuilder {
     // a map of each snippet id in the snippet manager to a snippet build information
@@ -44,18 +61,36 @@ pub struct InitializedPythonSnippetRunnerBuilder {
 
 pub struct PythonSnippetBuildInformation {
     visual_snippet_uuid: Uuid,
+    name: String,
     parameters: Vec<SnippetParameterComponent>,
     // names of inputs and outputs
     inputs: Vec<String>,
     outputs: Vec<String>,
     python_file: PathBuf,
+    // builtin snippets are run in rust, and have no python file
+    builtin_snippet_kind: Option<BuiltinSnippetKind>,
+    // sub graph run for each item of a for each snippet
+    for_each_body: Option<ForEachBody>,
+}
+
+/// Body of a for each snippet, run once for each item of its input list
+struct ForEachBody {
+    runner: InitializedPythonSnippetRunnerBuilder,
+    // input of the reroute snippet each item is given to
+    item_input: (Uuid, String),
+    // the outputs of the body are mapped to the inputs of this uuid, which is not a snippet
+    // so they are collected after each iteration
+    collector_uuid: Uuid,
+    output_names: Vec<String>,
+    parallel: bool,
 }
 
 #[pyclass]
 #[derive(FromPyObject)]
 pub struct PythonLogger {
+    // level, message and structured fields of each log
     #[pyo3(get, set)]
-    logs: Vec<(String, String)>,
+    logs: Vec<(String, String, BTreeMap<String, String>)>,
 }
 
 #[pyclass]
@@ -65,16 +100,27 @@ struct PythonRunnerResult {
     exception: bool,
     #[pyo3(get, set)]
     outputs: HashMap<(Uuid, String), Py<PyAny>>,
+    // names of the outputs the snippet skipped
+    #[pyo3(get, set)]
+    skipped_outputs: Vec<String>,
+    #[pyo3(get, set)]
+    skip_all: bool,
+    // repr of the value of each output the snippet gave
+    #[pyo3(get, set)]
+    output_previews: HashMap<String, String>,
 }
 
 impl Default for PythonSnippetBuildInformation {
     fn default() -> Self {
         return PythonSnippetBuildInformation {
             visual_snippet_uuid: Uuid::default(),
+            name: String::default(),
             parameters: Vec::default(),
             inputs: Vec::<String>::default(),
             outputs: Vec::<String>::default(),
             python_file: PathBuf::default(),
+            builtin_snippet_kind: None,
+            for_each_body: None,
         };
     }
 }
@@ -99,6 +145,8 @@ impl InitializedPythonSnippetRunnerBuilder {
         directory_manager: &DirectoryManager,
         visual_snippet_component_manager: &VisualSnippetComponentManager,
         sequential_id_generator: &mut SequentialIdGenerator,
+        project_directory: &PathBuf,
+        variables: &BTreeMap<String, String>,
     ) -> Result<Self, String> {
         // create information necessary to
         // a. run the necessary python code
@@ -106,9 +154,65 @@ impl InitializedPythonSnippetRunnerBuilder {
 
         // first make sure if it is even in a valid build state
         if !snippet_manager.validate_for_run() {
-            return Err("Snippet project is not in a valid runstate, check for any inputs that are not assigned".to_string());
+            return Err("Snippet project is not in a valid runstate, check for any inputs that are not assigned and for any missing snippets, connectors or parameters".to_string());
+        }
+
+        // expand composite snippets into the snippets they are built from
+        let (snippet_manager, expanded_to_project_snippet) = expand_composite_snippets(
+            snippet_manager,
+            external_snippet_manager,
+            directory_manager,
+            sequential_id_generator,
+        )?;
+
+        // find the front uuid of each snippet
+        let mut visual_snippet_uuids = HashMap::<Uuid, Uuid>::new();
+
+        for snippet in snippet_manager.get_snippets_as_ref() {
+            // snippets of composite snippets are shown as the composite snippet of the project
+            let project_snippet_uuid = match expanded_to_project_snippet.get(&snippet.get_uuid()) {
+                Some(uuid) => uuid.to_owned(),
+                None => snippet.get_uuid(),
+            };
+
+            // find snippet in visual snippet manager, get uuid
+            visual_snippet_uuids.insert(
+                snippet.get_uuid(),
+                visual_snippet_component_manager
+                    .find_snippet_front_uuid(&project_snippet_uuid)
+                    .unwrap(),
+            );
         }
 
+        return Self::build_from_expanded_snippet_manager(
+            &snippet_manager,
+            external_snippet_manager,
+            directory_manager,
+            sequential_id_generator,
+            &visual_snippet_uuids,
+            project_directory,
+            variables,
+            0,
+        );
+    }
+
+    /// Build the runner of a snippet manager whose composite snippets are expanded
+    ///
+    /// # Arguments
+    /// * 'visual_snippet_uuids' - front uuid of each snippet
+    /// * 'project_directory' - directory path parameters are relative to
+    /// * 'variables' - variables of the profile the parameters are resolved with
+    /// * 'depth' - number of for each snippets this is the body of
+    fn build_from_expanded_snippet_manager(
+        snippet_manager: &SnippetManager,
+        external_snippet_manager: &ExternalSnippetManager,
+        directory_manager: &DirectoryManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        visual_snippet_uuids: &HashMap<Uuid, Uuid>,
+        project_directory: &PathBuf,
+        variables: &BTreeMap<String, String>,
+        depth: usize,
+    ) -> Result<Self, String> {
         // build information
         let mut build_information = HashMap::<Uuid, PythonSnippetBuildInformation>::new();
 
@@ -123,14 +227,34 @@ impl InitializedPythonSnippetRunnerBuilder {
             // create empty snippet build information
             let mut python_snippet_build_information = PythonSnippetBuildInformation::default();
 
-            // find snippet in visual snippet manager, get uuid
-            python_snippet_build_information.visual_snippet_uuid = visual_snippet_component_manager
-                .find_snippet_front_uuid(&snippet.get_uuid())
-                .unwrap();
+            python_snippet_build_information.visual_snippet_uuid =
+                match visual_snippet_uuids.get(&snippet.get_uuid()) {
+                    Some(uuid) => uuid.to_owned(),
+                    None => {
+                        return Err(format!(
+                            "Could not find the front snippet of snippet {}",
+                            snippet.get_name()
+                        ));
+                    }
+                };
+
+            python_snippet_build_information.name = snippet.get_name();
 
             // create deep copy and set parameter values
             python_snippet_build_information.parameters = snippet.get_parameters_as_copy();
 
+            // variables are substituted first, as the resolved value is the one that must be valid,
+            // required parameters need a value, and snippets get the full path of path parameters
+            for parameter in python_snippet_build_information.parameters.iter_mut() {
+                if let Err(e) = parameter
+                    .resolve_variables(variables)
+                    .and_then(|_| parameter.check_value())
+                    .and_then(|_| parameter.resolve_path(project_directory))
+                {
+                    return Err(format!("Snippet {}: {}", snippet.get_name(), e));
+                }
+            }
+
             python_snippet_build_information.inputs = snippet.get_input_names();
             python_snippet_build_information.outputs = snippet.get_output_names();
 
@@ -144,6 +268,31 @@ impl InitializedPythonSnippetRunnerBuilder {
                 let external_snippet = external_snippet_manager
                     .find_external_snippet(external_snippet_id)
                     .unwrap();
+
+                // builtin snippets have no python file
+                if let ExternalSnippetKind::Builtin(builtin_snippet_kind) =
+                    external_snippet.get_kind()
+                {
+                    python_snippet_build_information.builtin_snippet_kind =
+                        Some(builtin_snippet_kind);
+
+                    if let BuiltinSnippetKind::ForEach = builtin_snippet_kind {
+                        python_snippet_build_information.for_each_body = Some(ForEachBody::build(
+                            &python_snippet_build_information,
+                            external_snippet_manager,
+                            directory_manager,
+                            sequential_id_generator,
+                            project_directory,
+                            variables,
+                            depth,
+                        )?);
+                    }
+
+                    build_information.insert(snippet.get_uuid(), python_snippet_build_information);
+
+                    continue;
+                }
+
                 let snippet_directory_entry = directory_manager
                     .find_directory_entry(external_snippet.get_package_path())
                     .unwrap();
@@ -165,7 +314,13 @@ impl InitializedPythonSnippetRunnerBuilder {
     }
 
     // run the python snippet runnere
-    pub fn run(mut self, logger: &mut LoggingStreamInstance) -> Result<(), String> {
+    // the run stops before the next snippet once cancelled is set
+    pub fn run(
+        self,
+        logger: &mut LoggingStreamInstance,
+        cancelled: &AtomicBool,
+        run_record: &mut RunRecord,
+    ) -> Result<(), String> {
         // inputs: reference to lock on the app handler
 
         //TODO every time we want to write a log, we aquire the lock and then release, rather than holding for build information
@@ -225,245 +380,291 @@ impl InitializedPythonSnippetRunnerBuilder {
                 }
             };
 
-            // queue for BFS
-            let mut run_queue = VecDeque::<NodeIndex>::new();
-            // set of nodes which we already ran
-            let mut run_set = HashSet::<NodeIndex>::new();
-
-            /*
-            Current mapping overview:
-            - graph: directed graph representing the flow of the build from one snippet to another snippet based on the io points
-                the weights of each node are the corresponding snippet id
-            - io mapping: maps
-                for each snippet id, maps it's input by input name (since names are unique based on io type (input or output) and snippet id)
-                    to a list of entries, each entry being a) the name of the output in the output snippet it maps to and b) the output snippet id it maps too
-             */
-            // contains the mapping of the next input, and the pyany value to be inserted
-            let mut input_cache = HashMap::<(Uuid, String), Py<PyAny>>::new();
-
-            // add all nodes which have no inputs
-            for node in self.graph.node_indices() {
-                // if node has no edges in
-                if self
-                    .graph
-                    .edges_directed(node, petgraph::Direction::Incoming)
-                    .count()
-                    == 0
-                {
-                    // if no neighbors
-                    // add to run queue
-                    run_queue.push_back(node);
-                }
+            let (_, skipped_snippet_names) = self.run_graph(
+                py,
+                &python_wrapper_run_snippet,
+                logger,
+                cancelled,
+                Some(run_record),
+                HashMap::<(Uuid, String), Py<PyAny>>::new(),
+            )?;
+
+            // report the skipped snippets
+            if skipped_snippet_names.len() > 0 {
+                logger.append_log(format!(
+                    "Skipped {} snippet(s): {}",
+                    skipped_snippet_names.len(),
+                    skipped_snippet_names.join(", ")
+                ));
             }
 
-            // while queue is not empty:
-            loop {
-                // pop item (which is next snippet to run) from queue
-                let run_node = match run_queue.pop_front() {
-                    Some(some) => some,
-                    None => {
-                        // if there is no run node, we are done; exit loop
-                        break;
-                    }
-                };
+            return Ok(());
+        })?;
 
-                // if this had already been ran
-                if run_set.contains(&run_node) {
-                    // skip running this node again, continue
-                    continue;
-                }
+        return Ok(());
+    }
 
-                // get snippet id of the node to run
-                // we can safely assume the node exists since we grabed it from the graph and the graph is not being modified
-                let snippet_id = self.graph.node_weight(run_node).unwrap().to_owned();
-
-                // get inputs for snippet
-                // if this fails, there is a critical logic error in the code
-                let snippet_python_build_information =
-                    self.build_information.remove(&snippet_id).unwrap();
-
-                // grab input parameters from hash map
-                // this maps each snippets output to the next snippet input id and name
-                let mut output_mapping = HashMap::<String, (Uuid, String)>::new();
-
-                //for each output
-                for output in snippet_python_build_information.outputs {
-                    match self
-                        .snippet_io_points_map
-                        .get(&(snippet_id, output.to_owned()))
-                    {
-                        // if it exists in mapping, insert all into cache
-                        Some(other_inputs) => {
-                            for other_input in other_inputs {
-                                // for each designated output
-                                output_mapping.insert(output.to_owned(), other_input.to_owned());
-                            }
-                        }
-                        // no mapping, then nothing to do
-                        None => (),
-                    }
+    /// Run the snippets of the graph
+    ///
+    /// # Arguments
+    /// * 'python_wrapper_run_snippet' - run_snippet function of the python runner wrapper
+    /// * 'cancelled' - set when the run was cancelled
+    /// * 'run_record' - record the outcome of each snippet is written to, if the run is recorded
+    /// * 'input_cache' - values already given to inputs of the snippets
+    ///
+    /// returns the input cache after the run, with the values of the inputs no snippet took,
+    /// and the names of the skipped snippets
+    fn run_graph(
+        &self,
+        py: Python<'_>,
+        python_wrapper_run_snippet: &Bound<'_, PyAny>,
+        logger: &mut LoggingStreamInstance,
+        cancelled: &AtomicBool,
+        mut run_record: Option<&mut RunRecord>,
+        mut input_cache: HashMap<(Uuid, String), Py<PyAny>>,
+    ) -> Result<(HashMap<(Uuid, String), Py<PyAny>>, Vec<String>), String> {
+        // queue for BFS
+        let mut run_queue = VecDeque::<NodeIndex>::new();
+        // set of nodes which we already ran
+        let mut run_set = HashSet::<NodeIndex>::new();
+
+        /*
+        Current mapping overview:
+        - graph: directed graph representing the flow of the build from one snippet to another snippet based on the io points
+            the weights of each node are the corresponding snippet id
+        - io mapping: maps
+            for each snippet id, maps it's input by input name (since names are unique based on io type (input or output) and snippet id)
+                to a list of entries, each entry being a) the name of the output in the output snippet it maps to and b) the output snippet id it maps too
+         */
+        // input cache contains the mapping of the next input, and the pyany value to be inserted
+
+        // inputs whose output was skipped
+        let mut skipped_inputs = HashSet::<(Uuid, String)>::new();
+        // set of nodes which were skipped instead of ran
+        let mut skipped_set = HashSet::<NodeIndex>::new();
+        let mut skipped_snippet_names = Vec::<String>::new();
+
+        // add all nodes which have no inputs
+        for node in self.graph.node_indices() {
+            // if node has no edges in
+            if self
+                .graph
+                .edges_directed(node, petgraph::Direction::Incoming)
+                .count()
+                == 0
+            {
+                // if no neighbors
+                // add to run queue
+                run_queue.push_back(node);
+            }
+        }
+
+        // while queue is not empty:
+        loop {
+            // pop item (which is next snippet to run) from queue
+            let run_node = match run_queue.pop_front() {
+                Some(some) => some,
+                None => {
+                    // if there is no run node, we are done; exit loop
+                    break;
                 }
+            };
 
-                let mut input_mapping = HashMap::<String, Py<PyAny>>::new();
+            // a snippet that already started is left to finish, the remaining ones are not ran
+            if cancelled.load(Ordering::Relaxed) {
+                return Err("Run was cancelled".to_string());
+            }
 
-                // fetch inputs for input mapping
-                for input in snippet_python_build_information.inputs {
-                    // if there is an input supplied, then input it
-                    // if there is no input, then do not include it
-                    match input_cache.remove(&(snippet_id.to_owned(), input.to_owned())) {
-                        Some(val) => {
-                            // insert into input mapping
-                            input_mapping.insert(input, val);
-                        }
-                        None => (),
-                    };
-                }
+            // if this had already been ran
+            if run_set.contains(&run_node) || skipped_set.contains(&run_node) {
+                // skip running this node again, continue
+                continue;
+            }
 
-                let mut parameter_mapping = HashMap::<String, SnippetParameterBaseStorage>::new();
+            // wait for all the parents to be ran or skipped,
+            // the last parent to run will insert this node again
+            let parents_done = self
+                .graph
+                .edges_directed(run_node, petgraph::Direction::Incoming)
+                .all(|edge| -> bool {
+                    return run_set.contains(&edge.source())
+                        || skipped_set.contains(&edge.source());
+                });
+
+            if !parents_done {
+                continue;
+            }
 
-                for parameter in snippet_python_build_information.parameters {
-                    parameter_mapping.insert(parameter.get_name(), parameter.get_storage().clone());
-                }
+            // get snippet id of the node to run
+            // we can safely assume the node exists since we grabed it from the graph and the graph is not being modified
+            let snippet_id = self.graph.node_weight(run_node).unwrap().to_owned();
 
-                // create pyresult class
-                let py_result_builder = match Bound::new(py, PythonRunnerResult::new()) {
-                    Ok(logger) => logger,
-                    Err(e) => {
-                        return Err(format!(
-                            "Could not bound the python runner result to the py gil: {}",
-                            e.to_string()
-                        ));
-                    }
-                };
+            // get inputs for snippet
+            // if this fails, there is a critical logic error in the code
+            let snippet_python_build_information = self.build_information.get(&snippet_id).unwrap();
 
-                // create python logger
-                let py_logger = match Bound::new(py, PythonLogger::new()) {
-                    Ok(logger) => logger,
-                    Err(e) => {
-                        return Err(format!(
-                            "Could not bound the python logger result to the py gil: {}",
-                            e.to_string()
-                        ));
-                    }
-                };
+            // a snippet whose connected inputs were all skipped is skipped as well
+            let mut has_skipped_input = false;
+            let mut has_input_value = false;
 
-                // convert parameter mapping to python
-                let py_parameter_mapping = parameter_mapping.into_py(py);
+            for input in snippet_python_build_information.inputs.iter() {
+                let input_key = (snippet_id.to_owned(), input.to_owned());
 
-                // convert input mapping to python
-                let py_input_mapping = input_mapping.into_py(py);
+                if skipped_inputs.remove(&input_key) {
+                    has_skipped_input = true;
+                } else if input_cache.contains_key(&input_key) {
+                    has_input_value = true;
+                }
+            }
 
-                // convert output mapping to python
-                let py_output_mapping = output_mapping.into_py(py);
+            if has_skipped_input && !has_input_value {
+                logger.append_snippet_log(
+                    LogLevel::Info,
+                    snippet_python_build_information.visual_snippet_uuid,
+                    snippet_python_build_information.name.to_owned(),
+                    format!(
+                        "SKIPPED {} as all of its inputs were skipped",
+                        snippet_python_build_information.name
+                    ),
+                    BTreeMap::new(),
+                );
+
+                // skip all of the outputs
+                self.skip_outputs(
+                    &snippet_id,
+                    snippet_python_build_information.outputs.iter(),
+                    &mut skipped_inputs,
+                );
+
+                skipped_snippet_names.push(snippet_python_build_information.name.to_owned());
+                skipped_set.insert(run_node.clone());
+
+                if let Some(run_record) = run_record.as_deref_mut() {
+                    run_record.set_snippet_result(
+                        snippet_id,
+                        SnippetRunStatus::Skipped,
+                        BTreeMap::new(),
+                    );
+                }
 
-                // convert to python module
-                let py_path =
-                    file_path_to_py_path(snippet_python_build_information.python_file.to_owned())?;
+                // get children of node, insert into run queue
+                for edge in self
+                    .graph
+                    .edges_directed(run_node, petgraph::Direction::Outgoing)
+                {
+                    run_queue.push_back(edge.target());
+                }
 
-                let kwargs = PyDict::new_bound(py);
+                continue;
+            }
 
-                match kwargs.set_item("result_builder", py_result_builder) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(format!(
-                            "Could not insert item into kwargs map: {}",
-                            e.to_string()
-                        ));
-                    }
-                };
-                match kwargs.set_item("snippet_path", py_path.to_owned().into_py(py)) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(format!(
-                            "Could not insert item into kwargs map: {}",
-                            e.to_string()
-                        ));
-                    }
-                };
-                match kwargs.set_item("function_inputs", py_input_mapping) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(format!(
-                            "Could not insert item into kwargs map: {}",
-                            e.to_string()
-                        ));
-                    }
-                };
-                match kwargs.set_item("input_mappings", py_output_mapping) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(format!(
-                            "Could not insert item into kwargs map: {}",
-                            e.to_string()
-                        ));
+            let snippet_name = snippet_python_build_information.name.to_owned();
+            let snippet_outputs = snippet_python_build_information.outputs.clone();
+
+            // grab input parameters from hash map
+            // this maps each snippets output to the next snippet input id and name
+            let mut output_mapping = HashMap::<String, Vec<(Uuid, String)>>::new();
+
+            //for each output
+            for output in snippet_python_build_information.outputs.iter() {
+                match self
+                    .snippet_io_points_map
+                    .get(&(snippet_id, output.to_owned()))
+                {
+                    // if it exists in mapping, each designated input gets its own copy
+                    Some(other_inputs) => {
+                        output_mapping.insert(output.to_owned(), other_inputs.to_owned());
                     }
-                };
+                    // no mapping, then nothing to do
+                    None => (),
+                }
+            }
 
-                match kwargs.set_item("logger", py_logger) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(format!(
-                            "Could not insert item into kwargs map: {}",
-                            e.to_string()
-                        ));
+            let mut input_mapping = HashMap::<String, Py<PyAny>>::new();
+
+            // fetch inputs for input mapping
+            for input in snippet_python_build_information.inputs.iter() {
+                // if there is an input supplied, then input it
+                // if there is no input, then do not include it
+                match input_cache.remove(&(snippet_id.to_owned(), input.to_owned())) {
+                    Some(val) => {
+                        // insert into input mapping
+                        input_mapping.insert(input.to_owned(), val);
                     }
+                    None => (),
                 };
+            }
 
-                match kwargs.set_item("parameter_values", py_parameter_mapping) {
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(format!(
-                            "Could not insert item into kwargs map: {}",
-                            e.to_string()
-                        ));
-                    }
+            let mut parameter_mapping = HashMap::<String, SnippetParameterBaseStorage>::new();
+
+            for parameter in snippet_python_build_information.parameters.iter() {
+                parameter_mapping.insert(parameter.get_name(), parameter.get_storage().clone());
+            }
+
+            // builtin snippets are run here instead of in the python wrapper
+            if let Some(builtin_snippet_kind) =
+                snippet_python_build_information.builtin_snippet_kind
+            {
+                let builtin_result = match &snippet_python_build_information.for_each_body {
+                    Some(for_each_body) => for_each_body.run(
+                        py,
+                        python_wrapper_run_snippet,
+                        logger,
+                        cancelled,
+                        input_mapping,
+                    ),
+                    None => run_builtin_snippet(
+                        py,
+                        builtin_snippet_kind,
+                        input_mapping,
+                        parameter_mapping,
+                    ),
                 };
 
-                // execute pywrapper
-                let run_result_result = match python_wrapper_run_snippet.call((), Some(&kwargs)) {
+                let outputs = match builtin_result {
                     Ok(some) => some,
                     Err(e) => {
-                        return Err(format!(
-                            "Critical exception occured in snippet runner: {}",
-                            e.to_string()
-                        ));
-                    }
-                };
-
-                // extract the result type
-                let run_results: (PythonRunnerResult, PythonLogger) =
-                    match run_result_result.extract() {
-                        Ok(result) => result,
-                        Err(e) => {
-                            return Err(format!(
-                                "Error in extracting snippet runner return type: {}",
-                                e.to_string()
-                            ))
+                        logger.append_snippet_log(
+                            LogLevel::Error,
+                            snippet_python_build_information.visual_snippet_uuid,
+                            snippet_name.to_owned(),
+                            format!("{} snippet failed: {}", builtin_snippet_kind, e),
+                            BTreeMap::new(),
+                        );
+
+                        if let Some(run_record) = run_record.as_deref_mut() {
+                            run_record.set_snippet_result(
+                                snippet_id,
+                                SnippetRunStatus::Failed,
+                                BTreeMap::new(),
+                            );
                         }
-                    };
 
-                let run_result = run_results.0;
-                let python_logger = run_results.1;
+                        return Err(format!("Snippet failed with previous exception"));
+                    }
+                };
 
-                // print logger statements
-                python_logger.print_logs(logger);
+                if let Some(run_record) = run_record.as_deref_mut() {
+                    let mut output_previews = BTreeMap::<String, String>::new();
 
-                // if an exception was raised
-                // Note: what would be more useful for this is to have a pyo3 conversion
-                // from tuple to rust type, but because we don't, we must use our own logic
-                // and call unwrap
-                match run_result.exception {
-                    true => {
-                        // Return exception
-                        return Err(format!("Snippet failed with previous exception"));
+                    for (output, value) in outputs.iter() {
+                        output_previews.insert(output.to_owned(), preview_value(py, value));
                     }
-                    false => (),
+
+                    run_record.set_snippet_result(
+                        snippet_id,
+                        SnippetRunStatus::Succeeded,
+                        output_previews,
+                    );
                 }
 
-                // for each output result
-                for output_result in run_result.outputs.into_iter() {
-                    // insert into input cache
-                    input_cache.insert(output_result.0, output_result.1);
+                // insert a copy of each output into the input cache of each input it maps to
+                for (output, value) in outputs {
+                    if let Some(other_inputs) = output_mapping.get(&output) {
+                        for other_input in other_inputs {
+                            input_cache.insert(other_input.to_owned(), deep_copy(py, &value)?);
+                        }
+                    }
                 }
 
                 // inlude node in run set
@@ -474,94 +675,1110 @@ impl InitializedPythonSnippetRunnerBuilder {
                     .graph
                     .edges_directed(run_node, petgraph::Direction::Outgoing)
                 {
-                    let child_node = edge.target();
-
-                    run_queue.push_back(child_node);
+                    run_queue.push_back(edge.target());
                 }
+
+                continue;
             }
 
-            // if we are missing any parameters, then we are still waiting on a child to run,
-            //   so reinsert into the queue and continue
+            // create pyresult class
+            let py_result_builder = match Bound::new(py, PythonRunnerResult::new()) {
+                Ok(logger) => logger,
+                Err(e) => {
+                    return Err(format!(
+                        "Could not bound the python runner result to the py gil: {}",
+                        e.to_string()
+                    ));
+                }
+            };
 
-            // call snippet with HashMap<output_name, i32 number of copies)
+            // create python logger
+            let py_logger = match Bound::new(py, PythonLogger::new()) {
+                Ok(logger) => logger,
+                Err(e) => {
+                    return Err(format!(
+                        "Could not bound the python logger result to the py gil: {}",
+                        e.to_string()
+                    ));
+                }
+            };
 
-            // get mapping entry of outputs to inputs, include this in the wrapper module
-            // note that not every output is assigned an input, so we want to check for the number of copies we need of each
+            // convert parameter mapping to python
+            let py_parameter_mapping = parameter_mapping.into_py(py);
 
-            // get return
-            // parse return into HashMap<string, Vec<PyAny>> from PyAny
-            // this is the output name, and the copies of pyany
+            // convert input mapping to python
+            let py_input_mapping = input_mapping.into_py(py);
 
-            // use the mapping and reducing to insert into input cache accordingly
+            // convert output mapping to python
+            let py_output_mapping = output_mapping.into_py(py);
 
-            // add child dependencies to queue
+            // convert to python module
+            let py_path =
+                file_path_to_py_path(snippet_python_build_information.python_file.to_owned())?;
 
-            return Ok(());
-        })?;
+            let kwargs = PyDict::new_bound(py);
 
-        return Ok(());
-    }
-}
+            match kwargs.set_item("result_builder", py_result_builder) {
+                Ok(_) => (),
+                Err(e) => {
+                    return Err(format!(
+                        "Could not insert item into kwargs map: {}",
+                        e.to_string()
+                    ));
+                }
+            };
+            match kwargs.set_item("snippet_path", py_path.to_owned().into_py(py)) {
+                Ok(_) => (),
+                Err(e) => {
+                    return Err(format!(
+                        "Could not insert item into kwargs map: {}",
+                        e.to_string()
+                    ));
+                }
+            };
+            match kwargs.set_item("function_inputs", py_input_mapping) {
+                Ok(_) => (),
+                Err(e) => {
+                    return Err(format!(
+                        "Could not insert item into kwargs map: {}",
+                        e.to_string()
+                    ));
+                }
+            };
+            match kwargs.set_item("input_mappings", py_output_mapping) {
+                Ok(_) => (),
+                Err(e) => {
+                    return Err(format!(
+                        "Could not insert item into kwargs map: {}",
+                        e.to_string()
+                    ));
+                }
+            };
 
-#[pymethods]
-impl PythonRunnerResult {
-    #[new]
-    fn new() -> Self {
-        return Self {
-            outputs: HashMap::new(),
-            exception: false,
-        };
-    }
+            match kwargs.set_item("logger", py_logger) {
+                Ok(_) => (),
+                Err(e) => {
+                    return Err(format!(
+                        "Could not insert item into kwargs map: {}",
+                        e.to_string()
+                    ));
+                }
+            };
 
-    #[pyo3(text_signature = "$self")]
-    fn set_exception_result(&mut self) {
-        self.exception = true;
-    }
+            match kwargs.set_item("parameter_values", py_parameter_mapping) {
+                Ok(_) => (),
+                Err(e) => {
+                    return Err(format!(
+                        "Could not insert item into kwargs map: {}",
+                        e.to_string()
+                    ));
+                }
+            };
 
-    #[pyo3(text_signature = "$self, outputs")]
-    fn set_successful_result(&mut self, outputs: HashMap<(u32, String), Py<PyAny>>) {
-        self.outputs = outputs;
-    }
-}
+            // execute pywrapper
+            let run_result_result = match python_wrapper_run_snippet.call((), Some(&kwargs)) {
+                Ok(some) => some,
+                Err(e) => {
+                    return Err(format!(
+                        "Critical exception occured in snippet runner: {}",
+                        e.to_string()
+                    ));
+                }
+            };
 
-#[pymethods]
-impl PythonLogger {
-    #[new]
-    fn new() -> Self {
-        return Self { logs: Vec::new() };
-    }
+            // extract the result type
+            let run_results: (PythonRunnerResult, PythonLogger) = match run_result_result.extract()
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    return Err(format!(
+                        "Error in extracting snippet runner return type: {}",
+                        e.to_string()
+                    ))
+                }
+            };
 
-    #[pyo3(text_signature = "$self, message")]
-    fn log(&mut self, message: String) -> PyResult<()> {
-        self.logs.push(("INFO".to_string(), message));
+            let run_result = run_results.0;
+            let python_logger = run_results.1;
+
+            // print logger statements
+            python_logger.print_logs(
+                logger,
+                snippet_python_build_information.visual_snippet_uuid,
+                &snippet_name,
+            );
+
+            // if an exception was raised
+            // Note: what would be more useful for this is to have a pyo3 conversion
+            // from tuple to rust type, but because we don't, we must use our own logic
+            // and call unwrap
+            match run_result.exception {
+                true => {
+                    if let Some(run_record) = run_record.as_deref_mut() {
+                        run_record.set_snippet_result(
+                            snippet_id,
+                            SnippetRunStatus::Failed,
+                            BTreeMap::new(),
+                        );
+                    }
 
-        return Ok(());
-    }
+                    // Return exception
+                    return Err(format!("Snippet failed with previous exception"));
+                }
+                false => (),
+            }
 
-    #[pyo3(text_signature = "$self, message")]
-    fn log_err(&mut self, message: String) -> PyResult<()> {
-        self.logs.push(("ERROR".to_string(), message));
+            if let Some(run_record) = run_record.as_deref_mut() {
+                run_record.set_snippet_result(
+                    snippet_id,
+                    SnippetRunStatus::Succeeded,
+                    run_result
+                        .output_previews
+                        .iter()
+                        .map(|(output, preview)| {
+                            (
+                                output.to_owned(),
+                                truncate_output_preview(preview.to_owned()),
+                            )
+                        })
+                        .collect(),
+                );
+            }
 
-        return Ok(());
-    }
-}
+            // for each output result
+            for output_result in run_result.outputs.into_iter() {
+                // insert into input cache
+                input_cache.insert(output_result.0, output_result.1);
+            }
 
-impl PythonLogger {
-    pub fn print_logs(&self, logger: &mut LoggingStreamInstance) {
-        for (log_type, log_message) in self.logs.iter() {
-            logger.append_log(format!("{} {}", log_type, log_message));
-        }
-    }
-}
+            // mark the inputs of the skipped outputs as skipped
+            if run_result.skip_all {
+                logger.append_snippet_log(
+                    LogLevel::Info,
+                    snippet_python_build_information.visual_snippet_uuid,
+                    snippet_name.to_owned(),
+                    format!("SKIPPED all outputs of {}", snippet_name),
+                    BTreeMap::new(),
+                );
+
+                self.skip_outputs(&snippet_id, snippet_outputs.iter(), &mut skipped_inputs);
+            } else if run_result.skipped_outputs.len() > 0 {
+                logger.append_snippet_log(
+                    LogLevel::Info,
+                    snippet_python_build_information.visual_snippet_uuid,
+                    snippet_name.to_owned(),
+                    format!(
+                        "SKIPPED outputs {} of {}",
+                        run_result.skipped_outputs.join(", "),
+                        snippet_name
+                    ),
+                    BTreeMap::from([(
+                        "skipped_outputs".to_string(),
+                        run_result.skipped_outputs.join(","),
+                    )]),
+                );
+
+                self.skip_outputs(
+                    &snippet_id,
+                    run_result.skipped_outputs.iter(),
+                    &mut skipped_inputs,
+                );
+            }
 
-fn file_path_to_py_path(mut path: PathBuf) -> Result<String, String> {
-    // remove file extension from end of path
-    path.set_extension("");
+            // inlude node in run set
+            run_set.insert(run_node.clone());
 
-    // get working directory
-    let mut runables_directory = get_runables_directory();
-    // pop runables directory from path to get base
-    runables_directory.pop();
+            // get children of node, insert into run queue
+            for edge in self
+                .graph
+                .edges_directed(run_node, petgraph::Direction::Outgoing)
+            {
+                let child_node = edge.target();
+
+                run_queue.push_back(child_node);
+            }
+        }
+
+        // if we are missing any parameters, then we are still waiting on a child to run,
+        //   so reinsert into the queue and continue
+
+        // call snippet with HashMap<output_name, i32 number of copies)
+
+        // get mapping entry of outputs to inputs, include this in the wrapper module
+        // note that not every output is assigned an input, so we want to check for the number of copies we need of each
+
+        // get return
+        // parse return into HashMap<string, Vec<PyAny>> from PyAny
+        // this is the output name, and the copies of pyany
+
+        // use the mapping and reducing to insert into input cache accordingly
+
+        // add child dependencies to queue
+
+        return Ok((input_cache, skipped_snippet_names));
+    }
+
+    /// Mark the inputs the outputs of the snippet are connected to as skipped
+    fn skip_outputs<'a>(
+        &self,
+        snippet_id: &Uuid,
+        outputs: impl Iterator<Item = &'a String>,
+        skipped_inputs: &mut HashSet<(Uuid, String)>,
+    ) {
+        for output in outputs {
+            if let Some(other_inputs) = self
+                .snippet_io_points_map
+                .get(&(snippet_id.to_owned(), output.to_owned()))
+            {
+                for other_input in other_inputs {
+                    skipped_inputs.insert(other_input.to_owned());
+                }
+            }
+        }
+    }
+
+    /// Create the record of a run of the snippets, before they are ran
+    pub fn create_run_record(&self) -> RunRecord {
+        // snippets in the order they were built
+        let mut snippet_uuids: Vec<&Uuid> = self.build_information.keys().collect();
+        snippet_uuids.sort();
+
+        let mut snippet_records = Vec::<SnippetRunRecord>::new();
+        let mut parameter_values = Vec::<RunParameterValue>::new();
+
+        for snippet_uuid in snippet_uuids {
+            let build_information = self.build_information.get(snippet_uuid).unwrap();
+
+            snippet_records.push(SnippetRunRecord::new(
+                snippet_uuid.to_owned(),
+                build_information.visual_snippet_uuid,
+                build_information.name.to_owned(),
+            ));
+
+            // secrets are recorded masked
+            for parameter in build_information.parameters.iter() {
+                parameter_values.push(RunParameterValue::new(
+                    build_information.name.to_owned(),
+                    parameter.get_name(),
+                    parameter.get_front_value(),
+                ));
+            }
+        }
+
+        return RunRecord::new(
+            hash_graph_description(&self.get_graph_description()),
+            parameter_values,
+            snippet_records,
+        );
+    }
+
+    /// Get the values of the secret parameters of the snippets, including of the bodies of for each snippets
+    pub fn get_secret_values(&self) -> Vec<String> {
+        let mut secret_values = Vec::<String>::new();
+
+        for build_information in self.build_information.values() {
+            for parameter in build_information.parameters.iter() {
+                if parameter.is_secret() {
+                    secret_values.push(parameter.get_storage().to_string());
+                }
+            }
+
+            if let Some(for_each_body) = &build_information.for_each_body {
+                secret_values.extend(for_each_body.runner.get_secret_values());
+            }
+        }
+
+        return secret_values;
+    }
+
+    /// Describe the snippets and pipelines of the graph by the names of the snippets and their connectors,
+    /// so the same graph has the same description whatever the ids and positions of its snippets
+    fn get_graph_description(&self) -> String {
+        let mut lines = Vec::<String>::new();
+
+        for build_information in self.build_information.values() {
+            lines.push(format!(
+                "snippet {} {} {:?} {:?}",
+                build_information.name,
+                build_information.python_file.to_string_lossy(),
+                build_information.inputs,
+                build_information.outputs
+            ));
+        }
+
+        for ((from_uuid, output), to_inputs) in self.snippet_io_points_map.iter() {
+            for (to_uuid, input) in to_inputs.iter() {
+                let from_name = match self.build_information.get(from_uuid) {
+                    Some(build_information) => build_information.name.to_owned(),
+                    None => continue,
+                };
+                let to_name = match self.build_information.get(to_uuid) {
+                    Some(build_information) => build_information.name.to_owned(),
+                    None => continue,
+                };
+
+                lines.push(format!(
+                    "pipeline {}.{} {}.{}",
+                    from_name, output, to_name, input
+                ));
+            }
+        }
+
+        // maps are not ordered, the description is
+        lines.sort();
+
+        return lines.join("\n");
+    }
+}
+
+impl ForEachBody {
+    /// Build the body of a for each snippet from the composite snippet of its body parameter.
+    /// The item is given to a reroute snippet connected to the single input of the composite snippet,
+    /// and each output of the composite snippet is connected to a reroute snippet to collect it
+    ///
+    /// # Arguments
+    /// * 'python_snippet_build_information' - build information of the for each snippet
+    /// * 'project_directory' - directory path parameters are relative to
+    /// * 'variables' - variables of the profile the parameters are resolved with
+    /// * 'depth' - number of for each snippets the for each snippet is the body of
+    fn build(
+        python_snippet_build_information: &PythonSnippetBuildInformation,
+        external_snippet_manager: &ExternalSnippetManager,
+        directory_manager: &DirectoryManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        project_directory: &PathBuf,
+        variables: &BTreeMap<String, String>,
+        depth: usize,
+    ) -> Result<Self, String> {
+        let name = &python_snippet_build_information.name;
+
+        // a for each snippet in its own body would build forever
+        if depth >= COMPOSITE_SNIPPET_MAX_DEPTH {
+            return Err(format!(
+                "For each snippet {} is nested more than {} for each snippets deep, its body may contain itself",
+                name, COMPOSITE_SNIPPET_MAX_DEPTH
+            ));
+        }
+
+        let mut body = String::new();
+        let mut parallel = false;
+
+        for parameter in python_snippet_build_information.parameters.iter() {
+            match (parameter.get_name().as_str(), parameter.get_storage()) {
+                (BUILTIN_FOR_EACH_BODY, SnippetParameterBaseStorage::String(value)) => {
+                    body = value.trim().to_string();
+                }
+                (BUILTIN_FOR_EACH_PARALLEL, SnippetParameterBaseStorage::Boolean(value)) => {
+                    parallel = *value;
+                }
+                _ => (),
+            }
+        }
+
+        if body.is_empty() {
+            return Err(format!(
+                "For each snippet {} has no body, set its {} parameter to the package path of a composite snippet",
+                name, BUILTIN_FOR_EACH_BODY
+            ));
+        }
+
+        let body_external_snippet = match external_snippet_manager
+            .find_external_snippet_from_package_path(&PackagePath::from(body.to_owned()))
+        {
+            Some(some) if some.is_composite() => some,
+            _ => {
+                return Err(format!(
+                    "Body {} of for each snippet {} is not a composite snippet",
+                    body, name
+                ));
+            }
+        };
+
+        let reroute_external_snippet = match external_snippet_manager
+            .find_builtin_external_snippet(BuiltinSnippetKind::Reroute)
+        {
+            Some(some) => some,
+            None => {
+                return Err("Could not find the builtin reroute snippet".to_string());
+            }
+        };
+
+        // create the body snippet manager
+        let mut body_snippet_manager = SnippetManager::default();
+
+        let composite_snippet_uuid = body_snippet_manager.new_snippet(
+            sequential_id_generator,
+            body_external_snippet,
+            0.0,
+            0.0,
+        );
+        // can safely unwrap as we just created the snippet
+        let composite_snippet = body_snippet_manager
+            .find_snippet(&composite_snippet_uuid)
+            .unwrap()
+            .clone();
+
+        let input_names = composite_snippet.get_input_names();
+
+        if input_names.len() != 1 {
+            return Err(format!(
+                "Body {} of for each snippet {} must have exactly one input, it has {}",
+                body,
+                name,
+                input_names.len()
+            ));
+        }
+
+        // connect a reroute snippet to each connector of the composite snippet
+        let mut item_input = (Uuid::default(), BUILTIN_REROUTE_INPUT.to_string());
+        let mut output_reroute_snippets = Vec::<(String, Uuid)>::new();
+
+        for (connector_name, input) in input_names
+            .into_iter()
+            .map(|input_name| (input_name, true))
+            .chain(
+                composite_snippet
+                    .get_output_names()
+                    .into_iter()
+                    .map(|output_name| (output_name, false)),
+            )
+        {
+            let reroute_snippet_uuid = body_snippet_manager.new_snippet(
+                sequential_id_generator,
+                reroute_external_snippet,
+                0.0,
+                0.0,
+            );
+            // can safely unwrap as we just created the snippet
+            let reroute_snippet = body_snippet_manager
+                .find_snippet(&reroute_snippet_uuid)
+                .unwrap();
+
+            // can safely unwrap as the connectors are from the snippets
+            let composite_pipeline_connector_uuid = composite_snippet
+                .find_pipeline_connector_from_name_and_input(connector_name.to_owned(), input)
+                .unwrap()
+                .get_uuid();
+
+            if input {
+                let reroute_pipeline_connector_uuid = reroute_snippet
+                    .find_pipeline_connector_from_name_and_input(
+                        BUILTIN_REROUTE_OUTPUT.to_string(),
+                        false,
+                    )
+                    .unwrap()
+                    .get_uuid();
+
+                body_snippet_manager.create_pipeline(
+                    sequential_id_generator,
+                    reroute_pipeline_connector_uuid,
+                    composite_pipeline_connector_uuid,
+                )?;
+
+                item_input.0 = reroute_snippet_uuid;
+            } else {
+                let reroute_pipeline_connector_uuid = reroute_snippet
+                    .find_pipeline_connector_from_name_and_input(
+                        BUILTIN_REROUTE_INPUT.to_string(),
+                        true,
+                    )
+                    .unwrap()
+                    .get_uuid();
+
+                body_snippet_manager.create_pipeline(
+                    sequential_id_generator,
+                    composite_pipeline_connector_uuid,
+                    reroute_pipeline_connector_uuid,
+                )?;
+
+                output_reroute_snippets.push((connector_name, reroute_snippet_uuid));
+            }
+        }
+
+        let (body_snippet_manager, _) = expand_composite_snippets(
+            &body_snippet_manager,
+            external_snippet_manager,
+            directory_manager,
+            sequential_id_generator,
+        )?;
+
+        // the snippets of the body are shown as the for each snippet
+        let visual_snippet_uuids: HashMap<Uuid, Uuid> = body_snippet_manager
+            .get_snippets_as_ref()
+            .into_iter()
+            .map(|snippet| {
+                (
+                    snippet.get_uuid(),
+                    python_snippet_build_information.visual_snippet_uuid,
+                )
+            })
+            .collect();
+
+        let mut runner =
+            InitializedPythonSnippetRunnerBuilder::build_from_expanded_snippet_manager(
+                &body_snippet_manager,
+                external_snippet_manager,
+                directory_manager,
+                sequential_id_generator,
+                &visual_snippet_uuids,
+                project_directory,
+                variables,
+                depth + 1,
+            )?;
+
+        // map the output reroute snippets to the collector
+        let collector_uuid = sequential_id_generator.get_id();
+        let mut output_names = Vec::<String>::new();
+
+        for (output_name, reroute_snippet_uuid) in output_reroute_snippets {
+            runner.snippet_io_points_map.insert(
+                (reroute_snippet_uuid, BUILTIN_REROUTE_OUTPUT.to_string()),
+                vec![(collector_uuid, output_name.to_owned())],
+            );
+
+            output_names.push(output_name);
+        }
+
+        return Ok(ForEachBody {
+            runner: runner,
+            item_input: item_input,
+            collector_uuid: collector_uuid,
+            output_names: output_names,
+            parallel: parallel,
+        });
+    }
+
+    /// Run the body for each item of the items input, returning the list of the outputs of each iteration
+    /// as the results output. With a single output, each result is the value of the output,
+    /// otherwise it is a dict of the output names to their values
+    fn run(
+        &self,
+        py: Python<'_>,
+        python_wrapper_run_snippet: &Bound<'_, PyAny>,
+        logger: &mut LoggingStreamInstance,
+        cancelled: &AtomicBool,
+        mut input_mapping: HashMap<String, Py<PyAny>>,
+    ) -> Result<HashMap<String, Py<PyAny>>, String> {
+        let items = match input_mapping.remove(BUILTIN_FOR_EACH_ITEMS) {
+            Some(some) => some,
+            None => {
+                return Err(format!("missing input {}", BUILTIN_FOR_EACH_ITEMS));
+            }
+        };
+
+        let items_iter = match items.bind(py).iter() {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(format!(
+                    "{} is not a list: {}",
+                    BUILTIN_FOR_EACH_ITEMS,
+                    e.to_string()
+                ));
+            }
+        };
+
+        let mut iteration_items = Vec::<Py<PyAny>>::new();
+
+        for item in items_iter {
+            match item {
+                Ok(some) => iteration_items.push(some.unbind()),
+                Err(e) => {
+                    return Err(format!(
+                        "Could not iterate over {}: {}",
+                        BUILTIN_FOR_EACH_ITEMS,
+                        e.to_string()
+                    ));
+                }
+            };
+        }
+
+        let mut results = Vec::<Py<PyAny>>::with_capacity(iteration_items.len());
+
+        if self.parallel {
+            let python_wrapper_run_snippet = python_wrapper_run_snippet.clone().unbind();
+
+            // run as many iterations at once as there are cores,
+            // each iteration runs in its own thread, which release the gil when the snippets do
+            let chunk_size = match std::thread::available_parallelism() {
+                Ok(some) => some.get(),
+                Err(_) => 1,
+            };
+
+            let mut iteration_items = iteration_items.into_iter().peekable();
+
+            while iteration_items.peek().is_some() {
+                let chunk: Vec<(Py<PyAny>, LoggingStreamInstance)> = iteration_items
+                    .by_ref()
+                    .take(chunk_size)
+                    .map(|item| (item, logger.get_shared_instance()))
+                    .collect();
+
+                let chunk_results = py.allow_threads(|| {
+                    return std::thread::scope(|scope| {
+                        let handles: Vec<_> = chunk
+                            .into_iter()
+                            .map(|(item, mut iteration_logger)| {
+                                let python_wrapper_run_snippet = &python_wrapper_run_snippet;
+
+                                return scope.spawn(move || {
+                                    return Python::with_gil(|py| {
+                                        return self.run_iteration(
+                                            py,
+                                            python_wrapper_run_snippet.bind(py),
+                                            &mut iteration_logger,
+                                            cancelled,
+                                            item,
+                                        );
+                                    });
+                                });
+                            })
+                            .collect();
+
+                        return handles
+                            .into_iter()
+                            .map(|handle| match handle.join() {
+                                Ok(result) => result,
+                                Err(_) => Err("For each iteration panicked".to_string()),
+                            })
+                            .collect::<Vec<Result<Py<PyAny>, String>>>();
+                    });
+                });
+
+                for chunk_result in chunk_results {
+                    results.push(chunk_result?);
+                }
+            }
+        } else {
+            for item in iteration_items {
+                results.push(self.run_iteration(
+                    py,
+                    python_wrapper_run_snippet,
+                    logger,
+                    cancelled,
+                    item,
+                )?);
+            }
+        }
+
+        let mut outputs = HashMap::<String, Py<PyAny>>::new();
+        outputs.insert(
+            BUILTIN_FOR_EACH_RESULTS.to_string(),
+            PyList::new_bound(py, results).into_py(py),
+        );
+
+        return Ok(outputs);
+    }
+
+    /// Run the body once with the item, returning the outputs of the body
+    fn run_iteration(
+        &self,
+        py: Python<'_>,
+        python_wrapper_run_snippet: &Bound<'_, PyAny>,
+        logger: &mut LoggingStreamInstance,
+        cancelled: &AtomicBool,
+        item: Py<PyAny>,
+    ) -> Result<Py<PyAny>, String> {
+        let mut input_cache = HashMap::<(Uuid, String), Py<PyAny>>::new();
+        input_cache.insert(self.item_input.to_owned(), item);
+
+        // snippets of the body are recorded as part of the for each snippet
+        let (mut input_cache, _) = self.runner.run_graph(
+            py,
+            python_wrapper_run_snippet,
+            logger,
+            cancelled,
+            None,
+            input_cache,
+        )?;
+
+        // outputs which were skipped or not returned are none
+        let mut take_output = |output_name: &String| -> Py<PyAny> {
+            return match input_cache.remove(&(self.collector_uuid, output_name.to_owned())) {
+                Some(some) => some,
+                None => py.None(),
+            };
+        };
+
+        if self.output_names.len() == 1 {
+            return Ok(take_output(&self.output_names[0]));
+        }
+
+        let result = PyDict::new_bound(py);
+
+        for output_name in self.output_names.iter() {
+            match result.set_item(output_name, take_output(output_name)) {
+                Ok(_) => (),
+                Err(e) => {
+                    return Err(format!(
+                        "Could not insert item into result dict: {}",
+                        e.to_string()
+                    ));
+                }
+            };
+        }
+
+        return Ok(result.into_py(py));
+    }
+}
+
+/// Create a deep copy of the python value, so each input it is given to gets its own copy
+fn deep_copy(py: Python<'_>, value: &Py<PyAny>) -> Result<Py<PyAny>, String> {
+    let copy_module = match py.import_bound("copy") {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!("Could not import copy module: {}", e.to_string()));
+        }
+    };
+
+    return match copy_module.call_method1("deepcopy", (value.clone_ref(py),)) {
+        Ok(some) => Ok(some.unbind()),
+        Err(e) => Err(format!("Could not copy output value: {}", e.to_string())),
+    };
+}
+
+/// Run a builtin snippet, returning the value of each of its outputs
+///
+/// # Arguments
+/// * 'builtin_snippet_kind' - builtin snippet to run
+/// * 'input_mapping' - value of each input of the snippet
+/// * 'parameter_mapping' - value of each parameter of the snippet
+fn run_builtin_snippet(
+    py: Python<'_>,
+    builtin_snippet_kind: BuiltinSnippetKind,
+    mut input_mapping: HashMap<String, Py<PyAny>>,
+    parameter_mapping: HashMap<String, SnippetParameterBaseStorage>,
+) -> Result<HashMap<String, Py<PyAny>>, String> {
+    let mut outputs = HashMap::<String, Py<PyAny>>::new();
+
+    // reroute snippets pass their input through
+    if let BuiltinSnippetKind::Reroute = builtin_snippet_kind {
+        if let Some(value) = input_mapping.remove(BUILTIN_REROUTE_INPUT) {
+            outputs.insert(BUILTIN_REROUTE_OUTPUT.to_string(), value);
+        }
+
+        return Ok(outputs);
+    }
+
+    // constant snippets emit their parameter value
+    let parameter_value = match parameter_mapping.get(BUILTIN_CONSTANT_VALUE) {
+        Some(some) => some.to_owned(),
+        None => {
+            return Err(format!("missing parameter {}", BUILTIN_CONSTANT_VALUE));
+        }
+    };
+
+    let py_value = match builtin_snippet_kind {
+        BuiltinSnippetKind::Reroute => {
+            // handled above
+            return Ok(outputs);
+        }
+        BuiltinSnippetKind::ForEach => {
+            return Err("for each snippets are run with their body".to_string());
+        }
+        // the parameter value was already converted to the type of the constant
+        BuiltinSnippetKind::ConstantInteger
+        | BuiltinSnippetKind::ConstantFloat
+        | BuiltinSnippetKind::ConstantString
+        | BuiltinSnippetKind::ConstantJson => parameter_value.into_py(py),
+    };
+
+    outputs.insert(BUILTIN_CONSTANT_VALUE.to_string(), py_value);
+
+    return Ok(outputs);
+}
+
+/// Expand the composite snippets into the snippets they are built from, in a copy of the snippet manager
+/// composite snippets within composite snippets are expanded as well
+///
+/// returns the expanded snippet manager, and the mapping of each expanded snippet to the
+/// snippet of the project it was expanded from
+fn expand_composite_snippets(
+    snippet_manager: &SnippetManager,
+    external_snippet_manager: &ExternalSnippetManager,
+    directory_manager: &DirectoryManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+) -> Result<(SnippetManager, HashMap<Uuid, Uuid>), String> {
+    let mut expanded_snippet_manager = snippet_manager.clone();
+    let mut expanded_to_project_snippet = HashMap::<Uuid, Uuid>::new();
+    // number of composite snippets each expanded snippet is nested in
+    let mut expanded_snippet_depth = HashMap::<Uuid, usize>::new();
+
+    loop {
+        // find the next composite snippet
+        let composite_snippet = match expanded_snippet_manager
+            .get_snippets_as_ref()
+            .into_iter()
+            .find(|snippet| {
+                match external_snippet_manager
+                    .find_external_snippet(snippet.get_external_snippet_id())
+                {
+                    Some(external_snippet) => external_snippet.is_composite(),
+                    None => false,
+                }
+            }) {
+            Some(snippet) => snippet.clone(),
+            // every composite snippet is expanded
+            None => break,
+        };
+
+        let composite_snippet_uuid = composite_snippet.get_uuid();
+
+        let depth = match expanded_snippet_depth.get(&composite_snippet_uuid) {
+            Some(depth) => depth.to_owned(),
+            None => 0,
+        };
+
+        // a composite snippet containing itself would expand forever
+        if depth >= COMPOSITE_SNIPPET_MAX_DEPTH {
+            return Err(format!(
+                "Composite snippet {} is nested more than {} composite snippets deep, it may contain itself",
+                composite_snippet.get_name(),
+                COMPOSITE_SNIPPET_MAX_DEPTH
+            ));
+        }
+
+        let project_snippet_uuid = match expanded_to_project_snippet.get(&composite_snippet_uuid) {
+            Some(uuid) => uuid.to_owned(),
+            None => composite_snippet_uuid,
+        };
+
+        // read the composite snippet file
+        // can safely unwrap as we found the external snippet above
+        let external_snippet = external_snippet_manager
+            .find_external_snippet(composite_snippet.get_external_snippet_id())
+            .unwrap();
+
+        let composite_file = match directory_manager
+            .find_directory_entry(external_snippet.get_package_path())
+            .and_then(|directory_entry| directory_entry.get_composite_file())
+        {
+            Some(some) => some,
+            None => {
+                return Err(format!(
+                    "Composite snippet file of {} not found, must have been deleted",
+                    composite_snippet.get_name()
+                ));
+            }
+        };
+
+        let composite_snippet_definition = read_composite_snippet_definition(composite_file)?;
+
+        // remove the composite snippet, keeping its pipelines to reconnect them to the inner snippets
+        let mut pipelines = Vec::<PipelineComponent>::new();
+
+        for pipeline_uuid in
+            expanded_snippet_manager.find_pipeline_uuids_from_snippet(&composite_snippet_uuid)
+        {
+            // can safely unwrap as the pipeline uuid is from the snippet manager
+            pipelines.push(
+                expanded_snippet_manager
+                    .find_pipeline(&pipeline_uuid)
+                    .unwrap()
+                    .clone(),
+            );
+
+            expanded_snippet_manager.delete_pipeline(&pipeline_uuid)?;
+        }
+
+        expanded_snippet_manager.delete_snippet(&composite_snippet_uuid)?;
+
+        let built_composite_snippet = build_composite_snippet_into_snippet_manager(
+            &composite_snippet_definition,
+            external_snippet_manager,
+            sequential_id_generator,
+            &mut expanded_snippet_manager,
+        )?;
+
+        for snippet_uuid in built_composite_snippet.snippet_uuids.iter() {
+            expanded_to_project_snippet.insert(snippet_uuid.to_owned(), project_snippet_uuid);
+            expanded_snippet_depth.insert(snippet_uuid.to_owned(), depth + 1);
+        }
+
+        // reconnect the pipelines to the connectors the composite connectors expose
+        for pipeline in pipelines.iter() {
+            let mut from_pipeline_connector_uuid = pipeline.get_from_pipeline_connector_uuid();
+            let mut to_pipeline_connector_uuid = pipeline.get_to_pipeline_connector_uuid();
+
+            // pipelines into the composite snippet end at its inputs, the others start at its outputs
+            let (composite_pipeline_connector_uuid, inner_pipeline_connectors) =
+                match composite_snippet.find_pipeline_connector(to_pipeline_connector_uuid) {
+                    Some(_) => (
+                        to_pipeline_connector_uuid,
+                        &built_composite_snippet.input_pipeline_connectors,
+                    ),
+                    None => (
+                        from_pipeline_connector_uuid,
+                        &built_composite_snippet.output_pipeline_connectors,
+                    ),
+                };
+
+            let inner_pipeline_connector_uuid = match composite_snippet
+                .find_pipeline_connector(composite_pipeline_connector_uuid)
+                .and_then(|pipeline_connector| {
+                    inner_pipeline_connectors.get(&pipeline_connector.get_name())
+                }) {
+                Some(uuid) => uuid.to_owned(),
+                None => {
+                    return Err(format!(
+                        "Connector of composite snippet {} no longer exists in the composite snippet file",
+                        composite_snippet.get_name()
+                    ));
+                }
+            };
+
+            if composite_pipeline_connector_uuid == to_pipeline_connector_uuid {
+                to_pipeline_connector_uuid = inner_pipeline_connector_uuid;
+            } else {
+                from_pipeline_connector_uuid = inner_pipeline_connector_uuid;
+            }
+
+            expanded_snippet_manager.create_pipeline(
+                sequential_id_generator,
+                from_pipeline_connector_uuid,
+                to_pipeline_connector_uuid,
+            )?;
+        }
+    }
+
+    return Ok((expanded_snippet_manager, expanded_to_project_snippet));
+}
+
+#[pymethods]
+impl PythonRunnerResult {
+    #[new]
+    fn new() -> Self {
+        return Self {
+            outputs: HashMap::new(),
+            exception: false,
+            skipped_outputs: Vec::new(),
+            skip_all: false,
+            output_previews: HashMap::new(),
+        };
+    }
+
+    #[pyo3(text_signature = "$self")]
+    fn set_exception_result(&mut self) {
+        self.exception = true;
+    }
+
+    #[pyo3(text_signature = "$self, outputs")]
+    fn set_successful_result(&mut self, outputs: HashMap<(u32, String), Py<PyAny>>) {
+        self.outputs = outputs;
+    }
+
+    #[pyo3(text_signature = "$self, skipped_outputs, skip_all")]
+    fn set_skipped_outputs(&mut self, skipped_outputs: Vec<String>, skip_all: bool) {
+        self.skipped_outputs = skipped_outputs;
+        self.skip_all = skip_all;
+    }
+
+    #[pyo3(text_signature = "$self, output_previews")]
+    fn set_output_previews(&mut self, output_previews: HashMap<String, String>) {
+        self.output_previews = output_previews;
+    }
+}
+
+#[pymethods]
+impl PythonLogger {
+    #[new]
+    fn new() -> Self {
+        return Self { logs: Vec::new() };
+    }
+
+    #[pyo3(signature = (message, **fields))]
+    fn debug(&mut self, message: String, fields: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        return self.push_log(LogLevel::Debug, message, fields);
+    }
+
+    #[pyo3(signature = (message, **fields))]
+    fn info(&mut self, message: String, fields: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        return self.push_log(LogLevel::Info, message, fields);
+    }
+
+    #[pyo3(signature = (message, **fields))]
+    fn warning(&mut self, message: String, fields: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        return self.push_log(LogLevel::Warning, message, fields);
+    }
+
+    #[pyo3(signature = (message, **fields))]
+    fn error(&mut self, message: String, fields: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        return self.push_log(LogLevel::Error, message, fields);
+    }
+
+    #[pyo3(signature = (message, **fields))]
+    fn log(&mut self, message: String, fields: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        return self.push_log(LogLevel::Info, message, fields);
+    }
+
+    #[pyo3(signature = (message, **fields))]
+    fn log_err(&mut self, message: String, fields: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        return self.push_log(LogLevel::Error, message, fields);
+    }
+}
+
+impl PythonLogger {
+    /// keep the log, with each field as the string of its value
+    fn push_log(
+        &mut self,
+        level: LogLevel,
+        message: String,
+        fields: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<()> {
+        let mut log_fields = BTreeMap::<String, String>::new();
+
+        if let Some(fields) = fields {
+            for (key, value) in fields.iter() {
+                log_fields.insert(key.str()?.to_string(), value.str()?.to_string());
+            }
+        }
+
+        self.logs.push((level.to_string(), message, log_fields));
+
+        return Ok(());
+    }
+
+    pub fn print_logs(
+        &self,
+        logger: &mut LoggingStreamInstance,
+        visual_snippet_uuid: Uuid,
+        snippet_name: &String,
+    ) {
+        for (log_type, log_message, log_fields) in self.logs.iter() {
+            // the logs can be set from python, unknown levels are kept as info
+            let level = match LogLevel::from_str(log_type) {
+                Ok(level) => level,
+                Err(_) => LogLevel::Info,
+            };
+
+            logger.append_snippet_log(
+                level,
+                visual_snippet_uuid,
+                snippet_name.to_owned(),
+                log_message.to_owned(),
+                log_fields.to_owned(),
+            );
+        }
+    }
+}
+
+/// Preview of an output value, as its truncated repr
+fn preview_value(py: Python<'_>, value: &Py<PyAny>) -> String {
+    let preview = match value.bind(py).repr() {
+        Ok(repr) => repr.to_string(),
+        Err(_) => "<no preview>".to_string(),
+    };
+
+    return truncate_output_preview(preview);
+}
+
+fn file_path_to_py_path(mut path: PathBuf) -> Result<String, String> {
+    // remove file extension from end of path
+    path.set_extension("");
+
+    // get working directory
+    let mut runables_directory = get_runables_directory();
+    // pop runables directory from path to get base
+    runables_directory.pop();
 
     // build base python runner location
     let base_python_runner_location = runables_directory.to_owned();
@@ -633,13 +1850,347 @@ pub fn set_python_path() {
 
 #[cfg(test)]
 mod test {
-    use std::path::PathBuf;
+    use std::{
+        collections::HashMap,
+        path::PathBuf,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+
+    use petgraph::{stable_graph::StableGraph, Directed};
+    use pyo3::{prelude::*, types::PyModule};
 
     use crate::{
-        core_services::concurrent_processes::{get_runables_directory, get_working_directory},
-        python_libraries::python_run_module::file_path_to_py_path,
+        core_components::snippet_manager::{SnippetManager, SnippetParameterBaseStorage},
+        core_services::{
+            concurrent_processes::{
+                get_logs_directory, get_runables_directory, get_working_directory,
+            },
+            directory_manager::{
+                DirectoryManager, COMPOSITE_SNIPPETS_CATEGORY, COMPOSITE_SNIPPET_FILE,
+            },
+            project_service::{write_composite_snippet_definition, ProjectManager},
+            runtime_logging_service::{LoggingStreamInstance, LoggingStreamManager},
+        },
+        python_libraries::python_run_module::{
+            expand_composite_snippets, file_path_to_py_path, run_builtin_snippet, ForEachBody,
+            InitializedPythonSnippetRunnerBuilder, PythonSnippetBuildInformation,
+        },
+        state_management::{
+            external_snippet_manager::{
+                BuiltinSnippetKind, ExternalSnippetManager, PackagePath, BUILTIN_CONSTANT_VALUE,
+                BUILTIN_FOR_EACH_ITEMS, BUILTIN_FOR_EACH_RESULTS, BUILTIN_REROUTE_INPUT,
+                BUILTIN_REROUTE_OUTPUT,
+            },
+            test_support::create_builtin_external_snippet_manager,
+        },
+        utils::sequential_id_generator::{SequentialIdGenerator, Uuid},
     };
 
+    // stand in for the python runner wrapper, running the snippets by the name of their python file
+    // - double: gives each mapped output double its x input, or 0 without one
+    // - skip: skips all of its outputs
+    // - fail: raises an exception when its x input is 2
+    // each run is recorded in calls, with the names of the inputs it was given,
+    // the run is cancelled through the cancel handle once a snippet is given cancel_at as its x input
+    const TEST_RUNNER_WRAPPER: &str = r#"
+calls = []
+cancel_handle = None
+cancel_at = None
+
+def run_snippet(*args, **kwargs):
+    snippet_name = kwargs["snippet_path"].split(".")[-1]
+    inputs = kwargs["function_inputs"]
+    result_builder = kwargs["result_builder"]
+
+    calls.append((snippet_name, sorted(inputs.keys())))
+
+    if snippet_name == "fail" and inputs.get("x") == 2:
+        result_builder.set_exception_result()
+        return result_builder, kwargs["logger"]
+
+    if cancel_at is not None and inputs.get("x") == cancel_at:
+        cancel_handle.cancel()
+
+    skip_all = snippet_name == "skip"
+    outputs = {}
+
+    if not skip_all:
+        for output_name, input_mappings in kwargs["input_mappings"].items():
+            for input_mapping in input_mappings:
+                outputs[tuple(input_mapping)] = inputs.get("x", 0) * 2
+
+    result_builder.set_successful_result(outputs)
+    result_builder.set_skipped_outputs([], skip_all)
+    result_builder.set_output_previews({})
+
+    return result_builder, kwargs["logger"]
+"#;
+
+    /// lets the test runner wrapper cancel the run
+    #[pyclass]
+    struct CancelHandle {
+        cancelled: Arc<AtomicBool>,
+    }
+
+    #[pymethods]
+    impl CancelHandle {
+        fn cancel(&self) {
+            self.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// import the test runner wrapper
+    fn create_test_runner_wrapper<'py>(py: Python<'py>) -> Bound<'py, PyModule> {
+        return PyModule::from_code_bound(
+            py,
+            TEST_RUNNER_WRAPPER,
+            "test_runner_wrapper.py",
+            "test_runner_wrapper",
+        )
+        .unwrap();
+    }
+
+    /// build information of a snippet, run by the test runner wrapper when it is not builtin
+    fn create_build_information(
+        name: &str,
+        python_file: &str,
+        inputs: Vec<&str>,
+        outputs: Vec<&str>,
+        builtin_snippet_kind: Option<BuiltinSnippetKind>,
+    ) -> PythonSnippetBuildInformation {
+        let mut python_snippet_build_information = PythonSnippetBuildInformation::default();
+        python_snippet_build_information.name = name.to_string();
+        python_snippet_build_information.inputs =
+            inputs.into_iter().map(|input| input.to_string()).collect();
+        python_snippet_build_information.outputs = outputs
+            .into_iter()
+            .map(|output| output.to_string())
+            .collect();
+        python_snippet_build_information.python_file = get_runables_directory().join(python_file);
+        python_snippet_build_information.builtin_snippet_kind = builtin_snippet_kind;
+
+        return python_snippet_build_information;
+    }
+
+    /// runner of the snippets, with the outputs connected to the inputs of the pipelines
+    fn create_runner(
+        snippets: Vec<(Uuid, PythonSnippetBuildInformation)>,
+        pipelines: Vec<((Uuid, &str), (Uuid, &str))>,
+    ) -> InitializedPythonSnippetRunnerBuilder {
+        let mut graph = StableGraph::<Uuid, (), Directed>::default();
+        let mut node_indices = HashMap::new();
+
+        for (snippet_uuid, _) in snippets.iter() {
+            node_indices.insert(*snippet_uuid, graph.add_node(*snippet_uuid));
+        }
+
+        let mut snippet_io_points_map = HashMap::<(Uuid, String), Vec<(Uuid, String)>>::new();
+
+        for (from, to) in pipelines {
+            graph.add_edge(node_indices[&from.0], node_indices[&to.0], ());
+
+            snippet_io_points_map
+                .entry((from.0, from.1.to_string()))
+                .or_default()
+                .push((to.0, to.1.to_string()));
+        }
+
+        return InitializedPythonSnippetRunnerBuilder::new(
+            snippets.into_iter().collect(),
+            graph,
+            snippet_io_points_map,
+        );
+    }
+
+    /// for each body giving each item to a snippet of the python file, collecting its y output
+    fn create_for_each_body(python_file: &str, parallel: bool) -> ForEachBody {
+        let (item_reroute_uuid, snippet_uuid, collector_uuid) = (1, 2, 3);
+
+        let mut runner = create_runner(
+            vec![
+                (
+                    item_reroute_uuid,
+                    create_build_information(
+                        "item",
+                        "",
+                        vec![BUILTIN_REROUTE_INPUT],
+                        vec![BUILTIN_REROUTE_OUTPUT],
+                        Some(BuiltinSnippetKind::Reroute),
+                    ),
+                ),
+                (
+                    snippet_uuid,
+                    create_build_information(python_file, python_file, vec!["x"], vec!["y"], None),
+                ),
+            ],
+            vec![(
+                (item_reroute_uuid, BUILTIN_REROUTE_OUTPUT),
+                (snippet_uuid, "x"),
+            )],
+        );
+
+        runner.snippet_io_points_map.insert(
+            (snippet_uuid, "y".to_string()),
+            vec![(collector_uuid, "y".to_string())],
+        );
+
+        return ForEachBody {
+            runner: runner,
+            item_input: (item_reroute_uuid, BUILTIN_REROUTE_INPUT.to_string()),
+            collector_uuid: collector_uuid,
+            output_names: vec!["y".to_string()],
+            parallel: parallel,
+        };
+    }
+
+    /// run the for each body over the items
+    ///
+    /// returns the results, and the runs of the test runner wrapper
+    fn run_for_each_body(
+        py: Python<'_>,
+        python_wrapper: &Bound<'_, PyModule>,
+        for_each_body: &ForEachBody,
+        cancelled: &AtomicBool,
+        items: Vec<i64>,
+    ) -> (Result<Vec<i64>, String>, Vec<(String, Vec<String>)>) {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let mut logging_manager = LoggingStreamManager::default();
+        let mut logger = create_test_logger(&mut logging_manager, &mut sequential_id_generator);
+
+        let mut input_mapping = HashMap::<String, Py<PyAny>>::new();
+        input_mapping.insert(BUILTIN_FOR_EACH_ITEMS.to_string(), items.into_py(py));
+
+        let results = for_each_body
+            .run(
+                py,
+                &python_wrapper.getattr("run_snippet").unwrap(),
+                &mut logger,
+                cancelled,
+                input_mapping,
+            )
+            .map(|mut outputs| {
+                return outputs
+                    .remove(BUILTIN_FOR_EACH_RESULTS)
+                    .unwrap()
+                    .extract::<Vec<i64>>(py)
+                    .unwrap();
+            });
+
+        logger.close_log();
+
+        let calls = python_wrapper.getattr("calls").unwrap().extract().unwrap();
+
+        return (results, calls);
+    }
+
+    /// log stream of a test run, which is not emitted
+    fn create_test_logger(
+        logging_manager: &mut LoggingStreamManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> LoggingStreamInstance {
+        return logging_manager
+            .create_detached_stream(
+                sequential_id_generator,
+                0,
+                get_logs_directory().join("tests"),
+            )
+            .unwrap();
+    }
+
+    /// add a new snippet of the external snippet of the package path
+    fn add_snippet(
+        snippet_manager: &mut SnippetManager,
+        external_snippet_manager: &ExternalSnippetManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        package_path: &PackagePath,
+    ) -> Uuid {
+        let external_snippet = external_snippet_manager
+            .find_external_snippet_from_package_path(package_path)
+            .unwrap();
+
+        return snippet_manager.new_snippet(sequential_id_generator, external_snippet, 0.0, 0.0);
+    }
+
+    /// connect the output of one snippet to the input of another
+    fn connect(
+        snippet_manager: &mut SnippetManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        from: (Uuid, &str),
+        to: (Uuid, &str),
+    ) {
+        let from_pipeline_connector_uuid = snippet_manager
+            .find_snippet(&from.0)
+            .unwrap()
+            .find_pipeline_connector_from_name_and_input(from.1.to_string(), false)
+            .unwrap()
+            .get_uuid();
+        let to_pipeline_connector_uuid = snippet_manager
+            .find_snippet(&to.0)
+            .unwrap()
+            .find_pipeline_connector_from_name_and_input(to.1.to_string(), true)
+            .unwrap()
+            .get_uuid();
+
+        snippet_manager
+            .create_pipeline(
+                sequential_id_generator,
+                from_pipeline_connector_uuid,
+                to_pipeline_connector_uuid,
+            )
+            .unwrap();
+    }
+
+    /// save the snippets of the project as a composite snippet of the snippet directory
+    ///
+    /// returns the package path of the composite snippet
+    fn add_composite_snippet(
+        project_manager: &ProjectManager,
+        snippet_uuids: &Vec<Uuid>,
+        name: &str,
+        directory_manager: &mut DirectoryManager,
+        external_snippet_manager: &mut ExternalSnippetManager,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> PackagePath {
+        let composite_snippet_definition = project_manager
+            .create_composite_snippet_definition(external_snippet_manager, snippet_uuids)
+            .unwrap();
+
+        let path = directory_manager
+            .get_composite_snippets_directory()
+            .unwrap()
+            .join(name);
+
+        write_composite_snippet_definition(
+            &composite_snippet_definition,
+            path.join(COMPOSITE_SNIPPET_FILE),
+        )
+        .unwrap();
+
+        let (directory_uuid, package_path) = directory_manager
+            .add_snippet_entry(
+                &COMPOSITE_SNIPPETS_CATEGORY.to_string(),
+                name.to_string(),
+                path,
+                sequential_id_generator,
+            )
+            .unwrap();
+
+        external_snippet_manager
+            .create_snippet_from_composite_snippet_definition(
+                &composite_snippet_definition,
+                name.to_string(),
+                package_path.to_owned(),
+                directory_uuid,
+                sequential_id_generator,
+            )
+            .unwrap();
+
+        return package_path;
+    }
+
     #[test]
     fn test_file_path_to_py_path() {
         // get working directory
@@ -658,4 +2209,514 @@ mod test {
             Ok("runables.snippets.root.main.basic_one_snippet.app".to_string())
         );
     }
+
+    #[test]
+    /// composite snippets, and the composite snippets within them, are expanded into their
+    /// inner snippets, with the pipelines into and out of them reconnected to the inner snippets
+    fn test_expand_composite_snippets() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let mut external_snippet_manager =
+            create_builtin_external_snippet_manager(&mut sequential_id_generator);
+        let reroute_package_path = PackagePath::from("builtin.reroute".to_string());
+        let constant_package_path = PackagePath::from("builtin.constant_integer".to_string());
+
+        let snippets_directory = std::env::temp_dir().join(format!(
+            "snippet_test_builder_expand_composite_{}",
+            std::process::id()
+        ));
+        let mut directory_manager = DirectoryManager::default();
+        directory_manager
+            .initialize(
+                &snippets_directory
+                    .join("root")
+                    .to_string_lossy()
+                    .to_string(),
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        // inner composite snippet of two reroutes, exposing the first input and the last output
+        let mut inner_project_manager = ProjectManager::new();
+        let first_reroute_uuid = add_snippet(
+            &mut inner_project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            &reroute_package_path,
+        );
+        let second_reroute_uuid = add_snippet(
+            &mut inner_project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            &reroute_package_path,
+        );
+        connect(
+            &mut inner_project_manager.snippet_manager,
+            &mut sequential_id_generator,
+            (first_reroute_uuid, BUILTIN_REROUTE_OUTPUT),
+            (second_reroute_uuid, BUILTIN_REROUTE_INPUT),
+        );
+
+        let inner_package_path = add_composite_snippet(
+            &inner_project_manager,
+            &vec![first_reroute_uuid, second_reroute_uuid],
+            "inner",
+            &mut directory_manager,
+            &mut external_snippet_manager,
+            &mut sequential_id_generator,
+        );
+
+        // outer composite snippet of a constant feeding the inner composite snippet
+        let mut outer_project_manager = ProjectManager::new();
+        let constant_uuid = add_snippet(
+            &mut outer_project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            &constant_package_path,
+        );
+        let inner_uuid = add_snippet(
+            &mut outer_project_manager.snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            &inner_package_path,
+        );
+        connect(
+            &mut outer_project_manager.snippet_manager,
+            &mut sequential_id_generator,
+            (constant_uuid, BUILTIN_CONSTANT_VALUE),
+            (inner_uuid, BUILTIN_REROUTE_INPUT),
+        );
+
+        let outer_package_path = add_composite_snippet(
+            &outer_project_manager,
+            &vec![constant_uuid, inner_uuid],
+            "outer",
+            &mut directory_manager,
+            &mut external_snippet_manager,
+            &mut sequential_id_generator,
+        );
+
+        // project of the outer composite snippet feeding a reroute
+        let mut snippet_manager = SnippetManager::default();
+        let outer_uuid = add_snippet(
+            &mut snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            &outer_package_path,
+        );
+        let last_reroute_uuid = add_snippet(
+            &mut snippet_manager,
+            &external_snippet_manager,
+            &mut sequential_id_generator,
+            &reroute_package_path,
+        );
+        connect(
+            &mut snippet_manager,
+            &mut sequential_id_generator,
+            (outer_uuid, BUILTIN_REROUTE_OUTPUT),
+            (last_reroute_uuid, BUILTIN_REROUTE_INPUT),
+        );
+
+        let (expanded_snippet_manager, expanded_to_project_snippet) = expand_composite_snippets(
+            &snippet_manager,
+            &external_snippet_manager,
+            &directory_manager,
+            &mut sequential_id_generator,
+        )
+        .unwrap();
+
+        // the project itself is left as it is
+        assert_eq!(snippet_manager.get_snippets_as_ref().len(), 2);
+
+        // constant -> reroute -> reroute -> last reroute
+        let expanded_snippets = expanded_snippet_manager.get_snippets_as_ref();
+
+        assert_eq!(expanded_snippets.len(), 4);
+        assert_eq!(expanded_snippet_manager.get_pipelines_as_ref().len(), 3);
+        assert!(expanded_snippets
+            .iter()
+            .all(|snippet| !external_snippet_manager
+                .find_external_snippet(snippet.get_external_snippet_id())
+                .unwrap()
+                .is_composite()));
+
+        // every inner snippet maps to the outer composite snippet of the project
+        for snippet in expanded_snippets.iter() {
+            if snippet.get_uuid() == last_reroute_uuid {
+                assert!(!expanded_to_project_snippet.contains_key(&last_reroute_uuid));
+                continue;
+            }
+
+            assert_eq!(
+                expanded_to_project_snippet.get(&snippet.get_uuid()),
+                Some(&outer_uuid)
+            );
+        }
+
+        // the pipeline out of the composite snippet now starts at the last inner reroute
+        let last_reroute_pipeline_uuids =
+            expanded_snippet_manager.find_pipeline_uuids_from_snippet(&last_reroute_uuid);
+
+        assert_eq!(last_reroute_pipeline_uuids.len(), 1);
+
+        let last_reroute_pipeline = expanded_snippet_manager
+            .find_pipeline(&last_reroute_pipeline_uuids[0])
+            .unwrap();
+        let from_snippet_uuid = expanded_snippet_manager
+            .find_snippet_uuid_from_pipeline_connector(
+                &last_reroute_pipeline.get_from_pipeline_connector_uuid(),
+            )
+            .unwrap();
+        let from_snippet = expanded_snippet_manager
+            .find_snippet(&from_snippet_uuid)
+            .unwrap();
+
+        assert_eq!(from_snippet.get_name(), "reroute");
+        assert!(expanded_to_project_snippet.contains_key(&from_snippet_uuid));
+
+        // the constant feeds the first inner reroute, through the input of the inner composite snippet
+        let constant_snippet = expanded_snippets
+            .iter()
+            .find(|snippet| snippet.get_name() == "constant_integer")
+            .unwrap();
+        let constant_pipeline_uuids =
+            expanded_snippet_manager.find_pipeline_uuids_from_snippet(&constant_snippet.get_uuid());
+
+        assert_eq!(constant_pipeline_uuids.len(), 1);
+
+        std::fs::remove_dir_all(snippets_directory).unwrap();
+    }
+
+    #[test]
+    fn test_run_builtin_reroute_snippet() {
+        Python::with_gil(|py| {
+            // the input is passed through as is
+            let value: Py<PyAny> = vec![1, 2, 3].into_py(py);
+
+            let mut input_mapping = HashMap::<String, Py<PyAny>>::new();
+            input_mapping.insert(BUILTIN_REROUTE_INPUT.to_string(), value.clone_ref(py));
+
+            let outputs = run_builtin_snippet(
+                py,
+                BuiltinSnippetKind::Reroute,
+                input_mapping,
+                HashMap::new(),
+            )
+            .unwrap();
+
+            assert_eq!(outputs.len(), 1);
+            assert!(outputs[BUILTIN_REROUTE_OUTPUT].is(&value));
+
+            // without an input there is nothing to pass through
+            let outputs = run_builtin_snippet(
+                py,
+                BuiltinSnippetKind::Reroute,
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .unwrap();
+
+            assert!(outputs.is_empty());
+        });
+    }
+
+    #[test]
+    fn test_run_builtin_constant_snippets() {
+        Python::with_gil(|py| {
+            let run_constant = |builtin_snippet_kind: BuiltinSnippetKind,
+                                value: SnippetParameterBaseStorage|
+             -> Py<PyAny> {
+                let mut parameter_mapping = HashMap::<String, SnippetParameterBaseStorage>::new();
+                parameter_mapping.insert(BUILTIN_CONSTANT_VALUE.to_string(), value);
+
+                let mut outputs = run_builtin_snippet(
+                    py,
+                    builtin_snippet_kind,
+                    HashMap::new(),
+                    parameter_mapping,
+                )
+                .unwrap();
+
+                assert_eq!(outputs.len(), 1);
+
+                return outputs.remove(BUILTIN_CONSTANT_VALUE).unwrap();
+            };
+
+            let value = run_constant(
+                BuiltinSnippetKind::ConstantInteger,
+                SnippetParameterBaseStorage::Integer(42),
+            );
+            assert_eq!(value.bind(py).extract::<i64>().unwrap(), 42);
+
+            let value = run_constant(
+                BuiltinSnippetKind::ConstantFloat,
+                SnippetParameterBaseStorage::Float(1.5),
+            );
+            assert_eq!(value.bind(py).extract::<f64>().unwrap(), 1.5);
+
+            let value = run_constant(
+                BuiltinSnippetKind::ConstantString,
+                SnippetParameterBaseStorage::String("text".to_string()),
+            );
+            assert_eq!(value.bind(py).extract::<String>().unwrap(), "text");
+
+            // json constants are given as python objects
+            let value = run_constant(
+                BuiltinSnippetKind::ConstantJson,
+                SnippetParameterBaseStorage::Json(serde_json::json!({"list": [1, 2]})),
+            );
+            let list = value.bind(py).get_item("list").unwrap();
+            assert_eq!(list.extract::<Vec<i64>>().unwrap(), vec![1, 2]);
+        });
+    }
+
+    #[test]
+    fn test_run_builtin_constant_snippet_without_value() {
+        Python::with_gil(|py| {
+            assert!(run_builtin_snippet(
+                py,
+                BuiltinSnippetKind::ConstantInteger,
+                HashMap::new(),
+                HashMap::new(),
+            )
+            .is_err());
+        });
+    }
+
+    #[test]
+    /// a snippet whose inputs were all skipped is skipped as well, down the graph,
+    /// while a snippet with an input value left runs without its skipped inputs
+    fn test_skip_propagation() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let mut logging_manager = LoggingStreamManager::default();
+        let mut logger = create_test_logger(&mut logging_manager, &mut sequential_id_generator);
+
+        let (skip_uuid, double_uuid, merge_uuid, first_reroute_uuid, second_reroute_uuid) =
+            (1, 2, 3, 4, 5);
+
+        let mut snippets = vec![
+            (
+                skip_uuid,
+                create_build_information("skip", "skip", vec![], vec!["y"], None),
+            ),
+            (
+                double_uuid,
+                create_build_information("double", "double", vec![], vec!["y"], None),
+            ),
+            (
+                merge_uuid,
+                create_build_information("merge", "double", vec!["x", "z"], vec!["y"], None),
+            ),
+            (
+                first_reroute_uuid,
+                create_build_information(
+                    "first_reroute",
+                    "",
+                    vec![BUILTIN_REROUTE_INPUT],
+                    vec![BUILTIN_REROUTE_OUTPUT],
+                    Some(BuiltinSnippetKind::Reroute),
+                ),
+            ),
+            (
+                second_reroute_uuid,
+                create_build_information(
+                    "second_reroute",
+                    "",
+                    vec![BUILTIN_REROUTE_INPUT],
+                    vec![BUILTIN_REROUTE_OUTPUT],
+                    Some(BuiltinSnippetKind::Reroute),
+                ),
+            ),
+        ];
+
+        // each snippet is shown as its own front snippet
+        for (snippet_uuid, python_snippet_build_information) in snippets.iter_mut() {
+            python_snippet_build_information.visual_snippet_uuid = *snippet_uuid + 100;
+        }
+
+        let runner = create_runner(
+            snippets,
+            vec![
+                (
+                    (skip_uuid, "y"),
+                    (first_reroute_uuid, BUILTIN_REROUTE_INPUT),
+                ),
+                (
+                    (first_reroute_uuid, BUILTIN_REROUTE_OUTPUT),
+                    (second_reroute_uuid, BUILTIN_REROUTE_INPUT),
+                ),
+                ((skip_uuid, "y"), (merge_uuid, "z")),
+                ((double_uuid, "y"), (merge_uuid, "x")),
+            ],
+        );
+
+        Python::with_gil(|py| {
+            let python_wrapper = create_test_runner_wrapper(py);
+            let python_wrapper_run_snippet = python_wrapper.getattr("run_snippet").unwrap();
+
+            let (input_cache, skipped_snippet_names) = runner
+                .run_graph(
+                    py,
+                    &python_wrapper_run_snippet,
+                    &mut logger,
+                    &AtomicBool::new(false),
+                    None,
+                    HashMap::new(),
+                )
+                .unwrap();
+
+            assert_eq!(
+                skipped_snippet_names,
+                vec!["first_reroute".to_string(), "second_reroute".to_string()]
+            );
+            assert!(input_cache.is_empty());
+
+            // the merge snippet ran with the input which was not skipped
+            let calls: Vec<(String, Vec<String>)> =
+                python_wrapper.getattr("calls").unwrap().extract().unwrap();
+            assert_eq!(calls.len(), 3);
+            assert!(calls.contains(&("double".to_string(), vec!["x".to_string()])));
+        });
+
+        // the skips are logged as logs of the snippets
+        let logs: Vec<serde_json::Value> = logger
+            .get_logs()
+            .iter()
+            .map(|log_entry| serde_json::to_value(log_entry).unwrap())
+            .collect();
+
+        let skip_log = logs
+            .iter()
+            .find(|log| log["snippet_id"] == skip_uuid + 100)
+            .unwrap();
+        assert_eq!(skip_log["level"], "info");
+        assert_eq!(skip_log["message"], "SKIPPED all outputs of skip");
+
+        for (snippet_uuid, snippet_name) in [
+            (first_reroute_uuid, "first_reroute"),
+            (second_reroute_uuid, "second_reroute"),
+        ] {
+            let skip_log = logs
+                .iter()
+                .find(|log| log["snippet_id"] == snippet_uuid + 100)
+                .unwrap();
+            assert_eq!(skip_log["level"], "info");
+            assert_eq!(skip_log["snippet_name"], snippet_name);
+            assert_eq!(
+                skip_log["message"],
+                format!("SKIPPED {} as all of its inputs were skipped", snippet_name)
+            );
+        }
+
+        logger.close_log();
+    }
+
+    #[test]
+    /// the results are in the order of the items, whether the iterations run one after another or at once
+    fn test_for_each_result_order() {
+        let items: Vec<i64> = (1..=20).collect();
+        let expected_results: Vec<i64> = items.iter().map(|item| item * 2).collect();
+
+        for parallel in [false, true] {
+            let for_each_body = create_for_each_body("double", parallel);
+
+            Python::with_gil(|py| {
+                let python_wrapper = create_test_runner_wrapper(py);
+
+                let (results, calls) = run_for_each_body(
+                    py,
+                    &python_wrapper,
+                    &for_each_body,
+                    &AtomicBool::new(false),
+                    items.clone(),
+                );
+
+                assert_eq!(results, Ok(expected_results.clone()));
+                assert_eq!(calls.len(), items.len());
+            });
+        }
+    }
+
+    #[test]
+    fn test_for_each_empty_items() {
+        let for_each_body = create_for_each_body("double", false);
+
+        Python::with_gil(|py| {
+            let python_wrapper = create_test_runner_wrapper(py);
+
+            let (results, calls) = run_for_each_body(
+                py,
+                &python_wrapper,
+                &for_each_body,
+                &AtomicBool::new(false),
+                vec![],
+            );
+
+            assert_eq!(results, Ok(vec![]));
+            assert!(calls.is_empty());
+        });
+    }
+
+    #[test]
+    /// a run cancelled during an iteration stops before the next iteration
+    fn test_for_each_cancellation() {
+        let for_each_body = create_for_each_body("double", false);
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        Python::with_gil(|py| {
+            let python_wrapper = create_test_runner_wrapper(py);
+
+            let cancel_handle = Bound::new(
+                py,
+                CancelHandle {
+                    cancelled: Arc::clone(&cancelled),
+                },
+            )
+            .unwrap();
+            python_wrapper
+                .setattr("cancel_handle", cancel_handle)
+                .unwrap();
+            python_wrapper.setattr("cancel_at", 2).unwrap();
+
+            let (results, calls) = run_for_each_body(
+                py,
+                &python_wrapper,
+                &for_each_body,
+                &cancelled,
+                vec![1, 2, 3, 4],
+            );
+
+            assert_eq!(results, Err("Run was cancelled".to_string()));
+            assert_eq!(calls.len(), 2);
+        });
+    }
+
+    #[test]
+    /// a failing iteration fails the for each snippet
+    fn test_for_each_failing_iteration() {
+        for parallel in [false, true] {
+            let for_each_body = create_for_each_body("fail", parallel);
+
+            Python::with_gil(|py| {
+                let python_wrapper = create_test_runner_wrapper(py);
+
+                let (results, calls) = run_for_each_body(
+                    py,
+                    &python_wrapper,
+                    &for_each_body,
+                    &AtomicBool::new(false),
+                    vec![1, 2, 3],
+                );
+
+                assert_eq!(
+                    results,
+                    Err("Snippet failed with previous exception".to_string())
+                );
+
+                // the iterations after the failing one are not ran one after another
+                if !parallel {
+                    assert_eq!(calls.len(), 2);
+                }
+            });
+        }
+    }
 }
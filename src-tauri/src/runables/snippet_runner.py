@@ -1,22 +1,81 @@
 import importlib
 import copy
+import logging
+import threading
 import sys
 import os
 
 # add runables path to sys modules
 sys.path.append(os.getcwd())
 
+# records of every level reach the snippet logging handler, the ui filters them by level
+logging.getLogger().setLevel(logging.DEBUG)
+
+class SkipOutput:
+    """sentinel returned as the value of an output to skip it"""
+    def __repr__(self):
+        return "SKIP"
+
+SKIP = SkipOutput()
+
+class OutputSkipper:
+    """skip api passed to the snippet, skipping the given outputs, or all outputs if none are given"""
+    def __init__(self):
+        self.skipped_outputs = set()
+        self.skip_all = False
+
+    def __call__(self, *output_names):
+        if len(output_names) == 0:
+            self.skip_all = True
+
+        self.skipped_outputs.update(output_names)
+
+class SnippetLoggingHandler(logging.Handler):
+    """forwards the records of the standard logging module, logged while running the snippet, to the snippet logger"""
+    def __init__(self, logger):
+        super().__init__(logging.DEBUG)
+        self.logger = logger
+        # snippets of a parallel for each run on other threads at the same time
+        self.thread_id = threading.get_ident()
+        self.exception_formatter = logging.Formatter()
+
+    def emit(self, record):
+        if record.thread != self.thread_id:
+            return
+
+        try:
+            message = record.getMessage()
+            fields = {"logger": record.name}
+
+            if record.exc_info:
+                fields["exception"] = self.exception_formatter.formatException(record.exc_info)
+
+            if record.levelno >= logging.ERROR:
+                self.logger.error(message, **fields)
+            elif record.levelno >= logging.WARNING:
+                self.logger.warning(message, **fields)
+            elif record.levelno >= logging.INFO:
+                self.logger.info(message, **fields)
+            else:
+                self.logger.debug(message, **fields)
+        except Exception:
+            self.handleError(record)
+
 def run_snippet(*args, **kwargs):
     snippet_path = kwargs["snippet_path"]
-    input_mappings: dict[str, tuple] = kwargs["input_mappings"]
+    input_mappings: dict[str, list] = kwargs["input_mappings"]
     result_builder = kwargs["result_builder"]
     logger = kwargs["logger"]
 
     # run...
     # :param module_path: path of the module relative to this file
     # :param function_inputs: inputs for the snippet mapped to their input name
-    # :param input_mappings: mapping of each input name to each output id and name
+    # :param input_mappings: mapping of each output name to the list of input ids and names it is given to
     # :param parameter_values: parameter values
+    # :param skip_output: sentinel, returning it as an output value skips the output
+    # :param skip_outputs: call with output names to skip them, or with none to skip all outputs
+    # :param logger: logger with debug, info, warning and error, each taking a message and optional key value fields,
+    #   records of the standard logging module are captured as well
 
     # import snippet from other file
     # reload if it has already been loaded
@@ -29,35 +88,73 @@ def run_snippet(*args, **kwargs):
     # get sub list of kwargs for function call
     run_kwargs = {k: v for k, v in kwargs.items() if k in ('logger', 'function_inputs', 'parameter_values')}
 
+    # skip api
+    skipper = OutputSkipper()
+    run_kwargs['skip_output'] = SKIP
+    run_kwargs['skip_outputs'] = skipper
+
     #call run function from snippet
     # handle any exeptions
 
+    # capture the records of the standard logging module
+    logging_handler = SnippetLoggingHandler(logger)
+    logging.getLogger().addHandler(logging_handler)
+
     try:
         outputs = py_snippet_runnable.run(*args, **run_kwargs)
     except Exception as e:
 
         # log exception
         # return false for success
-        logger.log_err(str(e))
+        logger.error(str(e), exception_type=type(e).__name__)
 
         run_exception = True
+    finally:
+        logging.getLogger().removeHandler(logging_handler)
 
     # If there was no runtime exception
     if run_exception is False:
         mapped_outputs = {}
+        skipped_outputs = []
+        output_previews = {}
+
+        # a snippet skipping all of its outputs does not need to return any
+        if outputs is None:
+            outputs = {}
 
         # for each output, map it to an output
         for output_name, output_value in outputs.items():
+            if skipper.skip_all or output_name in skipper.skipped_outputs or output_value is SKIP:
+                skipped_outputs.append(output_name)
+                continue
+
+            output_previews[output_name] = preview_value(output_value)
+
             if output_name in input_mappings:
-                # create deep copy
-                mapped_outputs[input_mappings[output_name]] = copy.deepcopy(output_value)
+                # each input gets its own deep copy
+                for input_mapping in input_mappings[output_name]:
+                    mapped_outputs[tuple(input_mapping)] = copy.deepcopy(output_value)
+
+        # outputs skipped through the api but not returned
+        for output_name in skipper.skipped_outputs:
+            if output_name not in skipped_outputs:
+                skipped_outputs.append(output_name)
 
         result_builder.set_successful_result(mapped_outputs)
+        result_builder.set_skipped_outputs(skipped_outputs, skipper.skip_all)
+        result_builder.set_output_previews(output_previews)
     else:
         result_builder.set_exception_result()
 
     return result_builder, logger
 
+def preview_value(value):
+    """repr of an output value kept in the run record, the record truncates it"""
+    try:
+        return repr(value)
+    except Exception:
+        return "<no preview>"
+
 def check_type(type, data):
     None
 
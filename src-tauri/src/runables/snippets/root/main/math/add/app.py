@@ -1,8 +1,9 @@
 def init(*args, **kwargs):
     snippet = args[0]
-    snippet.add_input("a")
-    snippet.add_input("b")
-    snippet.add_output("c")
+    snippet.set_metadata(description="Adds two numbers", tags=["math"], version="1.0.0")
+    snippet.add_input("a", description="first number")
+    snippet.add_input("b", description="second number")
+    snippet.add_output("c", description="sum of a and b")
 
     return snippet;
 
@@ -2,7 +2,7 @@
 
 def init(*args, **kwargs):
     snippet = args[0]
-    snippet.add_parameter("num_input", "SingleLineText")
+    snippet.add_parameter("num_input", "Float", default=1.0, description="number to output")
     snippet.add_output("num")
 
     return snippet;
@@ -11,9 +11,7 @@ def run(*args, **kwargs):
     inputs = kwargs['function_inputs']
     params = kwargs['parameter_values']
 
-    s = params["num_input"]
-
     outputs = {}
-    outputs["num"] = float(s)
+    outputs["num"] = params["num_input"]
 
     return outputs
@@ -5,10 +5,12 @@ use crate::core_services::runtime_logging_service::LoggingStreamManager;
 use crate::core_services::workspace_manager::WorkspaceManager;
 use crate::state_management::external_snippet_manager::ExternalSnippetManager;
 use crate::state_management::window_manager::WindowManager;
-use crate::utils::sequential_id_generator::SequentialIdGenerator;
+use crate::utils::sequential_id_generator::{SequentialIdGenerator, Uuid};
 //use crate::core_services::python_service::{call_init_todo_delete_this_method};
 
 pub mod external_snippet_manager;
+#[cfg(test)]
+pub mod test_support;
 pub mod visual_snippet_component_manager;
 pub mod window_manager;
 
@@ -22,6 +24,8 @@ pub struct ApplicationState {
     pub external_snippet_manager: ExternalSnippetManager,
     pub directory_manager: DirectoryManager,
     pub workspace_manager: WorkspaceManager,
+    // snippets last copied in any window session, in the clipboard format
+    pub clipboard: Option<String>,
 }
 
 impl Default for SharedApplicationState {
@@ -46,6 +50,7 @@ impl Default for ApplicationState {
             external_snippet_manager: ExternalSnippetManager::default(),
             directory_manager: DirectoryManager::default(),
             workspace_manager: WorkspaceManager::default(),
+            clipboard: None,
         };
     }
 }
@@ -58,4 +63,45 @@ impl ApplicationState {
     pub fn get_sequence_id_generator(&mut self) -> &mut SequentialIdGenerator {
         return &mut self.sequential_id_generator;
     }
+
+    /// close the window session, releasing everything it holds
+    /// active runs are cancelled, its log streams are closed and its projects are autosaved
+    /// so unsaved changes can still be recovered
+    pub fn close_window_session(&mut self, window_session_uuid: Uuid) -> Result<(), String> {
+        //borrow split
+        let window_manager = &mut self.window_manager;
+        let logging_manager = &mut self.logging_manager;
+        let external_snippet_manager = &self.external_snippet_manager;
+
+        let mut window_session = window_manager.close_window_session(window_session_uuid)?;
+
+        logging_manager.close_window_session_streams(window_session_uuid);
+
+        for project_manager in window_session.get_project_managers_mut() {
+            if let Err(e) = project_manager.autosave_project(external_snippet_manager) {
+                println!(
+                    "Unable to autosave project of closed window session {}: {}",
+                    window_session_uuid, e
+                );
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// close all the window sessions of the tauri window
+    pub fn close_window_sessions_of_window(&mut self, window_label: &str) {
+        let window_session_uuids = self
+            .window_manager
+            .find_window_session_uuids_from_label(window_label);
+
+        for window_session_uuid in window_session_uuids {
+            if let Err(e) = self.close_window_session(window_session_uuid) {
+                println!(
+                    "Unable to close window session {}: {}",
+                    window_session_uuid, e
+                );
+            }
+        }
+    }
 }
@@ -1,14 +1,17 @@
 use bimap::BiHashMap;
+use pyo3::pyclass;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, str::FromStr};
-use strum_macros::{Display, EnumString};
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+use strum_macros::{Display, EnumIter, EnumString};
 
 use crate::{
     core_components::snippet_manager::{
         PipelineConnectorComponent, SnippetParameterBaseStorage, SnippetParameterComponent,
     },
-    core_services::directory_manager::{
-        DirectoryManager, SnippetDirectoryEntry, SnippetDirectoryType,
+    core_services::{
+        directory_manager::{DirectoryManager, SnippetDirectoryEntry, SnippetDirectoryType},
+        project_service::{read_composite_snippet_definition, CompositeSnippetDefinition},
     },
     python_libraries::python_build_module::{
         FinalizedPythonSnipppetInitializerBuilder, InitializedPythonSnippetInitializerBuilder,
@@ -23,6 +26,62 @@ pub type Schema = String;
 pub struct ExternalSnippetManager {
     external_snippets: HashMap<Uuid, ExternalSnippet>,
     external_snippets_to_directory_entries: BiHashMap<Uuid, Uuid>,
+    // snippets of the directory that could not be initialized
+    initialization_errors: Vec<SnippetInitializationError>,
+}
+
+/// A snippet of the directory that could not be initialized, with why
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SnippetInitializationError {
+    directory_uuid: Uuid,
+    package_path: PackagePath,
+    // file of the snippet that failed
+    path: PathBuf,
+    phase: SnippetInitializationPhase,
+    message: String,
+    // python traceback, if the error was raised in python
+    traceback: Option<String>,
+}
+
+/// what was being done with the snippet when it failed
+#[derive(Serialize, Deserialize, Display, Clone, Copy, Debug, PartialEq)]
+pub enum SnippetInitializationPhase {
+    // reading its files
+    Open,
+    // compiling its python code
+    Compile,
+    // calling its init function
+    Init,
+    // creating the snippet from what init built
+    Register,
+}
+
+/// external snippets that changed when the snippet directory was reloaded
+#[derive(Default)]
+pub struct ExternalSnippetReload {
+    // external snippets that were built again, or for the first time
+    reloaded: Vec<Uuid>,
+    // (uuid, package path) of the external snippets that no longer exist
+    removed: Vec<(Uuid, PackagePath)>,
+    // snippets that could not be initialized by this build, older errors are not repeated
+    initialization_errors: Vec<SnippetInitializationError>,
+}
+
+/// snippets of the directory waiting to be built. Building them runs python, which does not
+/// need the external snippet manager, so it can be done without holding it
+pub struct InitializedExternalSnippetBuild {
+    external_snippet_reload: ExternalSnippetReload,
+    python_snippet_builder: InitializedPythonSnippetInitializerBuilder,
+    // (name, package path, directory uuid, composite file) of the composite snippets
+    composite_snippets: Vec<(String, PackagePath, Uuid, PathBuf)>,
+}
+
+/// snippets of the directory whose python was built, waiting to be added to the external snippet manager
+pub struct FinalizedExternalSnippetBuild {
+    external_snippet_reload: ExternalSnippetReload,
+    python_snippet_builder: FinalizedPythonSnipppetInitializerBuilder,
+    // (name, package path, directory uuid, composite file) of the composite snippets
+    composite_snippets: Vec<(String, PackagePath, Uuid, PathBuf)>,
 }
 
 pub struct ExternalSnippet {
@@ -32,6 +91,34 @@ pub struct ExternalSnippet {
     package_path: PackagePath,
     io_points: HashMap<Uuid, ExternalSnippetIOPoint>,
     parameters: HashMap<Uuid, ExternalSnippetParameter>,
+    kind: ExternalSnippetKind,
+    metadata: ExternalSnippetMetadata,
+}
+
+/// what the snippet is ran as
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ExternalSnippetKind {
+    // app.py file in the snippet directory
+    Python,
+    // built from other snippets, in a composite snippet file in the snippet directory
+    Composite,
+    // implemented in rust, needing no file
+    Builtin(BuiltinSnippetKind),
+}
+
+/// snippets implemented in rust, shown in the builtin category of the snippet directory
+#[derive(EnumString, EnumIter, Display, PartialEq, Debug, Clone, Copy)]
+#[strum(serialize_all = "snake_case")]
+pub enum BuiltinSnippetKind {
+    // passes its input through unchanged, for tidying pipelines
+    Reroute,
+    // emit the value of their parameter
+    ConstantInteger,
+    ConstantFloat,
+    ConstantString,
+    ConstantJson,
+    // runs the composite snippet of its body once for each item of its input list
+    ForEach,
 }
 
 #[derive(Debug)]
@@ -49,6 +136,22 @@ pub struct ExternalSnippetIOPoint {
     schema: Schema,
     //if it is an input node
     input: bool,
+    // help text shown with the connector
+    description: Option<String>,
+}
+
+// information about the snippet given with set_metadata in init, shown in the library and canvas
+#[pyclass]
+#[derive(Clone, Serialize, Deserialize, Default, Debug, PartialEq)]
+pub struct ExternalSnippetMetadata {
+    // help text shown with the snippet
+    description: Option<String>,
+    // the library can be filtered by tag
+    tags: Vec<String>,
+    version: Option<String>,
+    author: Option<String>,
+    // short text, such as an emoji, shown before the name of the snippet
+    icon: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -56,6 +159,7 @@ pub struct ExternalSnippetParameter {
     uuid: Uuid,
     name: String,
     p_type: ExternalSnippetParameterType,
+    options: ExternalSnippetParameterOptions,
 }
 
 #[derive(Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
@@ -64,9 +168,297 @@ pub struct PackagePath {
 }
 
 // supported list of parameter types
-#[derive(EnumString, PartialEq, Debug, Display, Clone, Serialize)]
+// written as their name, enums as Enum(choice, other choice)
+#[derive(PartialEq, Debug, Clone, Serialize)]
 pub enum ExternalSnippetParameterType {
     SingleLineText,
+    MultiLineText,
+    Integer,
+    Float,
+    Boolean,
+    // one of the choices, the first being the default
+    Enum(Vec<String>),
+    // passed to python parsed, as a dict, list or value
+    Json,
+    // resolved relative to the project directory, and checked to exist before a run
+    FilePath,
+    DirectoryPath,
+    // kept in the local secrets store instead of the project file, and masked everywhere it is shown
+    Secret,
+}
+
+impl FromStr for ExternalSnippetParameterType {
+    type Err = String;
+
+    fn from_str(parameter_type: &str) -> Result<Self, Self::Err> {
+        let parameter_type = parameter_type.trim();
+
+        match parameter_type {
+            "SingleLineText" => return Ok(ExternalSnippetParameterType::SingleLineText),
+            "MultiLineText" => return Ok(ExternalSnippetParameterType::MultiLineText),
+            "Integer" => return Ok(ExternalSnippetParameterType::Integer),
+            "Float" => return Ok(ExternalSnippetParameterType::Float),
+            "Boolean" => return Ok(ExternalSnippetParameterType::Boolean),
+            "Json" => return Ok(ExternalSnippetParameterType::Json),
+            "FilePath" => return Ok(ExternalSnippetParameterType::FilePath),
+            "DirectoryPath" => return Ok(ExternalSnippetParameterType::DirectoryPath),
+            "Secret" => return Ok(ExternalSnippetParameterType::Secret),
+            _ => (),
+        };
+
+        // enum with its choices
+        let choices = match parameter_type
+            .strip_prefix("Enum(")
+            .and_then(|rest| rest.strip_suffix(")"))
+        {
+            Some(some) => some,
+            None => {
+                return Err(format!("{} is not a parameter type", parameter_type));
+            }
+        };
+
+        let choices: Vec<String> = choices
+            .split(',')
+            .map(|choice| choice.trim().to_string())
+            .filter(|choice| !choice.is_empty())
+            .collect();
+
+        if choices.is_empty() {
+            return Err(format!(
+                "{} has no choices, list them as Enum(choice, other choice)",
+                parameter_type
+            ));
+        }
+
+        return Ok(ExternalSnippetParameterType::Enum(choices));
+    }
+}
+
+impl ExternalSnippetParameterType {
+    /// Convert the value, given in string format, to the base type of the parameter type
+    pub fn parse_value(&self, value: String) -> Result<SnippetParameterBaseStorage, &'static str> {
+        let storage = match self {
+            ExternalSnippetParameterType::SingleLineText
+            | ExternalSnippetParameterType::MultiLineText
+            | ExternalSnippetParameterType::Secret => {
+                // since we are already a string, no strict conversion needed
+                SnippetParameterBaseStorage::String(value)
+            }
+            // paths are only resolved and checked before a run, the file may not exist yet
+            ExternalSnippetParameterType::FilePath
+            | ExternalSnippetParameterType::DirectoryPath => {
+                SnippetParameterBaseStorage::String(value.trim().to_string())
+            }
+            ExternalSnippetParameterType::Json => {
+                match serde_json::from_str::<serde_json::Value>(&value) {
+                    Ok(some) => SnippetParameterBaseStorage::Json(some),
+                    Err(_) => {
+                        return Err("value is not valid json");
+                    }
+                }
+            }
+            ExternalSnippetParameterType::Integer => match value.trim().parse::<i64>() {
+                Ok(some) => SnippetParameterBaseStorage::Integer(some),
+                Err(_) => {
+                    return Err("value is not an integer");
+                }
+            },
+            ExternalSnippetParameterType::Float => match value.trim().parse::<f64>() {
+                Ok(some) if some.is_finite() => SnippetParameterBaseStorage::Float(some),
+                _ => {
+                    return Err("value is not a number");
+                }
+            },
+            ExternalSnippetParameterType::Boolean => match value.trim() {
+                "true" => SnippetParameterBaseStorage::Boolean(true),
+                "false" => SnippetParameterBaseStorage::Boolean(false),
+                _ => {
+                    return Err("value is not true or false");
+                }
+            },
+            ExternalSnippetParameterType::Enum(choices) => {
+                if !choices.contains(&value) {
+                    return Err("value is not one of the choices of the parameter");
+                }
+
+                SnippetParameterBaseStorage::String(value)
+            }
+        };
+
+        return Ok(storage);
+    }
+}
+
+// options of a parameter, given as keyword arguments to add_parameter in init
+#[pyclass]
+#[derive(Clone, Serialize, Default, Debug, PartialEq)]
+pub struct ExternalSnippetParameterOptions {
+    // value the parameter starts with, in string format
+    default: Option<String>,
+    // help text shown with the parameter
+    description: Option<String>,
+    // a run needs a value for the parameter
+    required: bool,
+    // bounds of number parameters
+    min: Option<f64>,
+    max: Option<f64>,
+    // pattern the whole value of text parameters must match
+    regex: Option<String>,
+}
+
+impl ExternalSnippetParameterOptions {
+    pub fn new(
+        default: Option<String>,
+        description: Option<String>,
+        required: bool,
+        min: Option<f64>,
+        max: Option<f64>,
+        regex: Option<String>,
+    ) -> Self {
+        return ExternalSnippetParameterOptions {
+            default: default,
+            description: description,
+            required: required,
+            min: min,
+            max: max,
+            regex: regex,
+        };
+    }
+
+    /// Check the options can be used for the parameter type, and its default fits the options
+    pub fn validate(&self, p_type: &ExternalSnippetParameterType) -> Result<(), String> {
+        let is_number = match p_type {
+            ExternalSnippetParameterType::Integer | ExternalSnippetParameterType::Float => true,
+            _ => false,
+        };
+        let is_text = match p_type {
+            ExternalSnippetParameterType::SingleLineText
+            | ExternalSnippetParameterType::MultiLineText => true,
+            _ => false,
+        };
+
+        if !is_number && (self.min.is_some() || self.max.is_some()) {
+            return Err(format!(
+                "min and max are only for Integer and Float parameters, not {}",
+                p_type
+            ));
+        }
+
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if min > max {
+                return Err(format!("min {} is greater than max {}", min, max));
+            }
+        }
+
+        if let Some(regex) = &self.regex {
+            if !is_text {
+                return Err(format!(
+                    "regex is only for SingleLineText and MultiLineText parameters, not {}",
+                    p_type
+                ));
+            }
+
+            if let Err(e) = Regex::new(regex) {
+                return Err(format!("regex {} is not valid: {}", regex, e));
+            }
+        }
+
+        if let Some(default) = &self.default {
+            // the default is written in the snippet, which is no place for a secret
+            if *p_type == ExternalSnippetParameterType::Secret {
+                return Err("Secret parameters cannot have a default".to_string());
+            }
+
+            let default_value = match p_type.parse_value(default.to_owned()) {
+                Ok(some) => some,
+                Err(e) => {
+                    return Err(format!("default {}: {}", default, e));
+                }
+            };
+
+            self.check_value(&"default".to_string(), &default_value)?;
+        }
+
+        return Ok(());
+    }
+
+    /// Check the value of the parameter against the constraints of the options
+    pub fn check_value(
+        &self,
+        parameter_name: &String,
+        value: &SnippetParameterBaseStorage,
+    ) -> Result<(), String> {
+        let number = match value {
+            SnippetParameterBaseStorage::Integer(some) => Some(*some as f64),
+            SnippetParameterBaseStorage::Float(some) => Some(*some),
+            _ => None,
+        };
+
+        if let Some(number) = number {
+            if let Some(min) = self.min {
+                if number < min {
+                    return Err(format!("{} must be at least {}", parameter_name, min));
+                }
+            }
+
+            if let Some(max) = self.max {
+                if number > max {
+                    return Err(format!("{} must be at most {}", parameter_name, max));
+                }
+            }
+        }
+
+        if let SnippetParameterBaseStorage::String(text) = value {
+            if self.required && text.is_empty() {
+                return Err(format!("{} is required", parameter_name));
+            }
+
+            if let Some(regex) = &self.regex {
+                // the regex must match the whole value
+                let is_match = match Regex::new(&format!("^(?:{})$", regex)) {
+                    Ok(some) => some.is_match(text),
+                    Err(_) => false,
+                };
+
+                if !is_match {
+                    return Err(format!("{} must match {}", parameter_name, regex));
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn get_default(&self) -> Option<String> {
+        return self.default.clone();
+    }
+
+    pub fn get_description(&self) -> Option<String> {
+        return self.description.clone();
+    }
+
+    pub fn is_required(&self) -> bool {
+        return self.required;
+    }
+}
+
+impl std::fmt::Display for ExternalSnippetParameterType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ExternalSnippetParameterType::SingleLineText => write!(f, "SingleLineText"),
+            ExternalSnippetParameterType::MultiLineText => write!(f, "MultiLineText"),
+            ExternalSnippetParameterType::Integer => write!(f, "Integer"),
+            ExternalSnippetParameterType::Float => write!(f, "Float"),
+            ExternalSnippetParameterType::Boolean => write!(f, "Boolean"),
+            ExternalSnippetParameterType::Enum(choices) => {
+                write!(f, "Enum({})", choices.join(", "))
+            }
+            ExternalSnippetParameterType::Json => write!(f, "Json"),
+            ExternalSnippetParameterType::FilePath => write!(f, "FilePath"),
+            ExternalSnippetParameterType::DirectoryPath => write!(f, "DirectoryPath"),
+            ExternalSnippetParameterType::Secret => write!(f, "Secret"),
+        }
+    }
 }
 
 // trait to define the conversion of parameter type into base storage type
@@ -80,20 +472,102 @@ pub trait IntoStorageType {
 impl IntoStorageType for ExternalSnippetParameterType {
     fn into_storage_type(external_snippet_parameter_type: &Self) -> SnippetParameterBaseStorage {
         match external_snippet_parameter_type {
-            ExternalSnippetParameterType::SingleLineText => {
+            ExternalSnippetParameterType::SingleLineText
+            | ExternalSnippetParameterType::MultiLineText
+            | ExternalSnippetParameterType::FilePath
+            | ExternalSnippetParameterType::DirectoryPath
+            | ExternalSnippetParameterType::Secret => {
                 SnippetParameterBaseStorage::String(String::default())
             }
+            ExternalSnippetParameterType::Json => {
+                SnippetParameterBaseStorage::Json(serde_json::Value::Null)
+            }
+            ExternalSnippetParameterType::Integer => SnippetParameterBaseStorage::Integer(0),
+            ExternalSnippetParameterType::Float => SnippetParameterBaseStorage::Float(0.0),
+            ExternalSnippetParameterType::Boolean => SnippetParameterBaseStorage::Boolean(false),
+            ExternalSnippetParameterType::Enum(choices) => {
+                SnippetParameterBaseStorage::String(choices.first().cloned().unwrap_or_default())
+            }
         }
     }
 }
 
 // impl way to get type from parameter type
 
+/// name of the input of the reroute snippet
+pub const BUILTIN_REROUTE_INPUT: &str = "input";
+/// name of the output of the reroute snippet
+pub const BUILTIN_REROUTE_OUTPUT: &str = "output";
+/// name of the output and the parameter of the constant snippets
+pub const BUILTIN_CONSTANT_VALUE: &str = "value";
+/// name of the list input of the for each snippet
+pub const BUILTIN_FOR_EACH_ITEMS: &str = "items";
+/// name of the list output of the for each snippet
+pub const BUILTIN_FOR_EACH_RESULTS: &str = "results";
+/// name of the parameter of the for each snippet with the package path of its body composite snippet
+pub const BUILTIN_FOR_EACH_BODY: &str = "body";
+/// name of the parameter of the for each snippet to run its iterations in parallel
+pub const BUILTIN_FOR_EACH_PARALLEL: &str = "parallel";
+
+impl BuiltinSnippetKind {
+    /// get the names of the inputs of the builtin snippet
+    pub fn get_inputs(&self) -> Vec<String> {
+        return match self {
+            BuiltinSnippetKind::Reroute => vec![BUILTIN_REROUTE_INPUT.to_string()],
+            BuiltinSnippetKind::ForEach => vec![BUILTIN_FOR_EACH_ITEMS.to_string()],
+            _ => Vec::new(),
+        };
+    }
+
+    /// get the names of the outputs of the builtin snippet
+    pub fn get_outputs(&self) -> Vec<String> {
+        return match self {
+            BuiltinSnippetKind::Reroute => vec![BUILTIN_REROUTE_OUTPUT.to_string()],
+            BuiltinSnippetKind::ForEach => vec![BUILTIN_FOR_EACH_RESULTS.to_string()],
+            _ => vec![BUILTIN_CONSTANT_VALUE.to_string()],
+        };
+    }
+
+    /// get the (name, type) of the parameters of the builtin snippet
+    pub fn get_parameters(&self) -> Vec<(String, ExternalSnippetParameterType)> {
+        return match self {
+            BuiltinSnippetKind::Reroute => Vec::new(),
+            BuiltinSnippetKind::ForEach => vec![
+                (
+                    BUILTIN_FOR_EACH_BODY.to_string(),
+                    ExternalSnippetParameterType::SingleLineText,
+                ),
+                (
+                    BUILTIN_FOR_EACH_PARALLEL.to_string(),
+                    ExternalSnippetParameterType::Boolean,
+                ),
+            ],
+            BuiltinSnippetKind::ConstantInteger => vec![(
+                BUILTIN_CONSTANT_VALUE.to_string(),
+                ExternalSnippetParameterType::Integer,
+            )],
+            BuiltinSnippetKind::ConstantFloat => vec![(
+                BUILTIN_CONSTANT_VALUE.to_string(),
+                ExternalSnippetParameterType::Float,
+            )],
+            BuiltinSnippetKind::ConstantJson => vec![(
+                BUILTIN_CONSTANT_VALUE.to_string(),
+                ExternalSnippetParameterType::Json,
+            )],
+            _ => vec![(
+                BUILTIN_CONSTANT_VALUE.to_string(),
+                ExternalSnippetParameterType::SingleLineText,
+            )],
+        };
+    }
+}
+
 impl Default for ExternalSnippetManager {
     fn default() -> Self {
         return ExternalSnippetManager {
             external_snippets: HashMap::new(),
             external_snippets_to_directory_entries: BiHashMap::new(),
+            initialization_errors: Vec::new(),
         };
     }
 }
@@ -126,11 +600,61 @@ impl IntoIterator for PackagePath {
 
 impl ExternalSnippetManager {
     /// Create the exernal snippets from the directory manager
+    /// snippets that cannot be initialized are skipped, and kept in the initialization errors
     pub fn create_external_snippets_from_directory(
         &mut self,
         directory_manager: &DirectoryManager,
         sequential_id_generator: &mut SequentialIdGenerator,
     ) -> Result<(), String> {
+        let external_snippet_build =
+            self.prepare_external_snippets_build(directory_manager, None, sequential_id_generator)?;
+
+        self.finish_external_snippets_build(
+            external_snippet_build.build(),
+            sequential_id_generator,
+        );
+
+        return Ok(());
+    }
+
+    /// Reload the external snippets after the snippet directory was rescanned, only building again
+    /// the snippets that are new or have a changed file. Snippets built again keep their uuid
+    ///
+    /// # Arguments
+    /// * 'changed_paths' - files in the snippet directory that were added, modified or removed
+    pub fn reload_external_snippets_from_directory(
+        &mut self,
+        directory_manager: &DirectoryManager,
+        changed_paths: &Vec<PathBuf>,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<ExternalSnippetReload, String> {
+        let external_snippet_build = self.prepare_external_snippets_build(
+            directory_manager,
+            Some(changed_paths),
+            sequential_id_generator,
+        )?;
+
+        return Ok(self.finish_external_snippets_build(
+            external_snippet_build.build(),
+            sequential_id_generator,
+        ));
+    }
+
+    /// Prepare building the external snippets of the snippets in the directory, all of them if there
+    /// are no changed paths, removing the external snippets whose directory entry no longer exists.
+    /// Builtin snippets are created right away, the others once the build is built and finished
+    pub fn prepare_external_snippets_build(
+        &mut self,
+        directory_manager: &DirectoryManager,
+        changed_paths: Option<&Vec<PathBuf>>,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<InitializedExternalSnippetBuild, String> {
+        let mut external_snippet_reload = ExternalSnippetReload::default();
+        // Create python snippet builder
+        let mut python_snippet_builder = InitializedPythonSnippetInitializerBuilder::new();
+        // (name, package path, directory uuid, composite file) of the composite snippets
+        let mut composite_snippets = Vec::<(String, PackagePath, Uuid, PathBuf)>::new();
+
         let root_directory_entry = match directory_manager
             .snippet_directory
             .get_root_directory_entry()
@@ -138,49 +662,223 @@ impl ExternalSnippetManager {
             Some(some) => some,
             // If no root, simply do not create the external snippet manager
             None => {
-                return Ok(());
+                return Ok(InitializedExternalSnippetBuild {
+                    external_snippet_reload: external_snippet_reload,
+                    python_snippet_builder: python_snippet_builder,
+                    composite_snippets: composite_snippets,
+                });
             }
         };
         let root_package = PackagePath::default();
 
-        // Create python snippet builder
-        let mut python_snippet_builder = InitializedPythonSnippetInitializerBuilder::new();
-
-        self.directory_walker(
+        // (directory entry, package path) of the snippets in the directory
+        let mut snippet_entries = Vec::<(&SnippetDirectoryEntry, PackagePath)>::new();
+        ExternalSnippetManager::directory_walker(
             root_directory_entry,
-            &mut python_snippet_builder,
-            sequential_id_generator,
+            &mut snippet_entries,
             root_package,
-        )?;
+        );
+
+        // remove the snippets that are no longer in the directory
+        let removed_directory_uuids: Vec<Uuid> = self
+            .external_snippets_to_directory_entries
+            .right_values()
+            .filter(|directory_uuid| {
+                !snippet_entries
+                    .iter()
+                    .any(|(directory_entry, _)| directory_entry.get_uuid() == **directory_uuid)
+            })
+            .copied()
+            .collect();
 
-        // Build python snippet builder
-        let python_snippet_builder: FinalizedPythonSnipppetInitializerBuilder =
-            python_snippet_builder.build()?;
+        for directory_uuid in removed_directory_uuids {
+            external_snippet_reload
+                .removed
+                .extend(self.remove_external_snippet(directory_uuid));
+        }
+
+        self.initialization_errors.retain(|initialization_error| {
+            snippet_entries.iter().any(|(directory_entry, _)| {
+                directory_entry.get_uuid() == initialization_error.directory_uuid
+            })
+        });
+
+        for (directory_entry, package_path) in snippet_entries {
+            let name = directory_entry.get_name();
+            let path = directory_entry.get_path();
+            let directory_uuid = directory_entry.get_uuid();
+
+            let builtin = match directory_entry.get_inner_as_ref() {
+                SnippetDirectoryType::Snippet(entry) => entry.is_builtin(),
+                SnippetDirectoryType::Category(_) => false,
+            };
+
+            // snippets already built, or that already failed to be, are kept when none of their files changed
+            if let Some(changed_paths) = changed_paths {
+                let built = self
+                    .external_snippets_to_directory_entries
+                    .contains_right(&directory_uuid)
+                    || self
+                        .find_initialization_error_from_directory_uuid(directory_uuid)
+                        .is_some();
+                // builtin snippets have no files
+                let changed = !builtin
+                    && changed_paths
+                        .iter()
+                        .any(|changed_path| changed_path.starts_with(&path));
+
+                if built && !changed {
+                    continue;
+                }
+            }
+
+            self.initialization_errors.retain(|initialization_error| {
+                initialization_error.directory_uuid != directory_uuid
+            });
+
+            // builtin snippets need nothing to be built
+            if builtin {
+                let builtin_snippet_kind = match BuiltinSnippetKind::from_str(&name) {
+                    Ok(some) => some,
+                    Err(_) => {
+                        return Err(format!("{} is not a builtin snippet", name));
+                    }
+                };
+
+                external_snippet_reload
+                    .reloaded
+                    .push(self.create_builtin_snippet(
+                        builtin_snippet_kind,
+                        package_path,
+                        directory_uuid,
+                        sequential_id_generator,
+                    )?);
+            } else if let Some(composite_file) = directory_entry.get_composite_file() {
+                // composite snippets have no python to build
+                composite_snippets.push((name, package_path, directory_uuid, composite_file));
+            } else {
+                // add snippet information for python building
+                python_snippet_builder.add_snippet(name, path, directory_uuid, package_path);
+            }
+        }
+
+        return Ok(InitializedExternalSnippetBuild {
+            external_snippet_reload: external_snippet_reload,
+            python_snippet_builder: python_snippet_builder,
+            composite_snippets: composite_snippets,
+        });
+    }
+
+    /// Finish building the external snippets, creating the snippets that were built and keeping
+    /// the errors of those that could not be
+    pub fn finish_external_snippets_build(
+        &mut self,
+        external_snippet_build: FinalizedExternalSnippetBuild,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> ExternalSnippetReload {
+        let FinalizedExternalSnippetBuild {
+            mut external_snippet_reload,
+            python_snippet_builder,
+            composite_snippets,
+        } = external_snippet_build;
+
+        // errors are only added from here on, the ones after this are new
+        let previous_error_count = self.initialization_errors.len();
+
+        self.initialization_errors.extend(
+            python_snippet_builder
+                .get_initialization_errors()
+                .to_owned(),
+        );
 
         // create external snippets from python snippet builders
         for python_snippet_information in python_snippet_builder.get_build_information() {
-            self.create_snippet_from_python_build_information(
+            match self.create_snippet_from_python_build_information(
                 python_snippet_information,
                 sequential_id_generator,
-            )?;
+            ) {
+                Ok(uuid) => external_snippet_reload.reloaded.push(uuid),
+                Err(e) => {
+                    self.initialization_errors
+                        .push(SnippetInitializationError::new(
+                            python_snippet_information.get_directory_entry_uuid(),
+                            python_snippet_information.get_package_path(),
+                            python_snippet_information.get_path(),
+                            SnippetInitializationPhase::Register,
+                            e,
+                            None,
+                        ));
+                }
+            };
         }
 
-        return Ok(());
+        // create external snippets from composite snippet files
+        for (name, package_path, directory_uuid, composite_file) in composite_snippets {
+            let composite_snippet_definition =
+                match read_composite_snippet_definition(composite_file.to_owned()) {
+                    Ok(some) => some,
+                    Err(e) => {
+                        self.initialization_errors
+                            .push(SnippetInitializationError::new(
+                                directory_uuid,
+                                package_path,
+                                composite_file,
+                                SnippetInitializationPhase::Open,
+                                e,
+                                None,
+                            ));
+
+                        continue;
+                    }
+                };
+
+            match self.create_snippet_from_composite_snippet_definition(
+                &composite_snippet_definition,
+                name,
+                package_path.to_owned(),
+                directory_uuid,
+                sequential_id_generator,
+            ) {
+                Ok(uuid) => external_snippet_reload.reloaded.push(uuid),
+                Err(e) => {
+                    self.initialization_errors
+                        .push(SnippetInitializationError::new(
+                            directory_uuid,
+                            package_path,
+                            composite_file,
+                            SnippetInitializationPhase::Register,
+                            e,
+                            None,
+                        ));
+                }
+            };
+        }
+
+        // snippets that broke while being built again no longer exist
+        let broken_directory_uuids: Vec<Uuid> = self
+            .initialization_errors
+            .iter()
+            .map(|initialization_error| initialization_error.directory_uuid)
+            .collect();
+
+        for directory_uuid in broken_directory_uuids {
+            external_snippet_reload
+                .removed
+                .extend(self.remove_external_snippet(directory_uuid));
+        }
+
+        external_snippet_reload.initialization_errors =
+            self.initialization_errors[previous_error_count..].to_vec();
+
+        return external_snippet_reload;
     }
 
-    /// Walk though the directory, creating snippets as we go
-    fn directory_walker(
-        &self,
-        directory_entry: &SnippetDirectoryEntry,
-        python_snippet_builder: &mut InitializedPythonSnippetInitializerBuilder,
-        sequential_id_generator: &mut SequentialIdGenerator,
+    /// Walk though the directory, collecting the snippets with their package path
+    fn directory_walker<'a>(
+        directory_entry: &'a SnippetDirectoryEntry,
+        snippet_entries: &mut Vec<(&'a SnippetDirectoryEntry, PackagePath)>,
         package_path: PackagePath,
-    ) -> Result<(), String> {
-        // get name
-        let name = directory_entry.get_name();
-        let path = directory_entry.get_path();
-        let directory_uuid = directory_entry.get_uuid();
-
+    ) {
         match directory_entry.get_inner_as_ref() {
             SnippetDirectoryType::Category(entry) => {
                 // if category, traverse children
@@ -189,21 +887,61 @@ impl ExternalSnippetManager {
                     let mut child_package_path = package_path.to_owned();
                     child_package_path.add(child_entry.get_name());
 
-                    self.directory_walker(
+                    ExternalSnippetManager::directory_walker(
                         child_entry,
-                        python_snippet_builder,
-                        sequential_id_generator,
+                        snippet_entries,
                         child_package_path,
-                    )?;
+                    );
                 }
             }
-            SnippetDirectoryType::Snippet(_entry) => {
-                // add snippet information for python building
-                python_snippet_builder.add_snippet(name, path, directory_uuid, package_path);
+            SnippetDirectoryType::Snippet(_) => {
+                snippet_entries.push((directory_entry, package_path));
             }
         };
+    }
 
-        return Ok(());
+    /// Add the external snippet of the directory entry to the manager, a snippet built again
+    /// replaces the one of the same directory entry and keeps its uuid
+    fn insert_external_snippet(
+        &mut self,
+        external_snippet: ExternalSnippet,
+        directory_uuid: Uuid,
+    ) -> Uuid {
+        let mut external_snippet = external_snippet;
+
+        if let Some(previous_uuid) = self
+            .external_snippets_to_directory_entries
+            .get_by_right(&directory_uuid)
+            .copied()
+        {
+            self.external_snippets.remove(&previous_uuid);
+            external_snippet.uuid = previous_uuid;
+        }
+
+        // get uuid of external snippet
+        let uuid = external_snippet.uuid;
+
+        // add directory entry to directory entry list
+        self.external_snippets_to_directory_entries
+            .insert(uuid.to_owned(), directory_uuid);
+
+        //add it to manager
+        self.external_snippets
+            .insert(uuid.to_owned(), external_snippet);
+
+        return uuid;
+    }
+
+    /// Remove the external snippet of the directory entry, if it has one
+    /// returns the uuid and package path of the removed external snippet
+    fn remove_external_snippet(&mut self, directory_uuid: Uuid) -> Option<(Uuid, PackagePath)> {
+        let (uuid, _) = self
+            .external_snippets_to_directory_entries
+            .remove_by_right(&directory_uuid)?;
+
+        let external_snippet = self.external_snippets.remove(&uuid)?;
+
+        return Some((uuid, external_snippet.package_path));
     }
 
     pub fn create_snippet_from_python_build_information(
@@ -220,26 +958,38 @@ impl ExternalSnippetManager {
             python_build_information.get_package_path(),
         );
 
+        external_snippet.metadata = python_build_information.get_metadata().to_owned();
+
         // add io (input and output) points
         //TODO pass errors to client
         for input in python_build_information.get_inputs() {
-            self.add_io_point_provided_external_snippet(
+            let io_point_uuid = self.add_io_point_provided_external_snippet(
                 sequential_id_generator,
                 &mut external_snippet,
                 input.to_owned(),
                 "".to_string(),
                 true,
             )?;
+
+            external_snippet.set_io_point_description(
+                io_point_uuid,
+                python_build_information.get_input_description(input),
+            )?;
         }
 
         for output in python_build_information.get_outputs() {
-            self.add_io_point_provided_external_snippet(
+            let io_point_uuid = self.add_io_point_provided_external_snippet(
                 sequential_id_generator,
                 &mut external_snippet,
                 output.to_owned(),
                 "".to_string(),
                 false,
             )?;
+
+            external_snippet.set_io_point_description(
+                io_point_uuid,
+                python_build_information.get_output_description(output),
+            )?;
         }
 
         for parameter in python_build_information.get_parameters() {
@@ -248,62 +998,113 @@ impl ExternalSnippetManager {
                 &mut external_snippet,
                 parameter.0.to_owned(),
                 parameter.1.to_owned(),
+                parameter.2.to_owned(),
             )?;
         }
 
-        // get uuid of external snippet
-        let uuid = external_snippet.uuid;
-
-        // add directory entry to directory entry list
-        self.external_snippets_to_directory_entries.insert(
-            uuid.to_owned(),
+        return Ok(self.insert_external_snippet(
+            external_snippet,
             python_build_information.get_directory_entry_uuid(),
-        );
-
-        //add it to manager
-        self.external_snippets
-            .insert(uuid.to_owned(), external_snippet);
-        return Ok(uuid);
+        ));
     }
 
-    /*
-    /// create snippet that does not input or output
-    pub fn create_non_acting_snippet(&mut self, sequential_id_generator: &mut SequentialIdGenerator, name: &str) -> Uuid {
-        //create external snippet
-        let mut external_snippet = ExternalSnippet::empty(sequential_id_generator, name);
-
-        //get uuid of external snippet
-        let uuid = external_snippet.uuid;
-
-        //create two non-acting snippet io points, one input, one output
-        for input_value in [true, false].iter()
-        {
-            //create new snippet io points
-            let snippet_io_point = ExternalSnippetIOPoint::new_non_acting_input(sequential_id_generator, input_value.to_owned());
+    /// Create the external snippet of a composite snippet, whose io points are the
+    /// connectors of the composite snippet
+    ///
+    /// # Arguments
+    /// * 'name' - name of the composite snippet
+    /// * 'package_path' - package path of the composite snippet
+    /// * 'directory_uuid' - uuid of the directory entry of the composite snippet
+    pub fn create_snippet_from_composite_snippet_definition(
+        &mut self,
+        composite_snippet_definition: &CompositeSnippetDefinition,
+        name: String,
+        package_path: PackagePath,
+        directory_uuid: Uuid,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<Uuid, String> {
+        let mut external_snippet =
+            ExternalSnippet::empty(sequential_id_generator, &name, package_path);
+        external_snippet.kind = ExternalSnippetKind::Composite;
 
-            //add existing io point
-            external_snippet.io_points.insert(snippet_io_point.uuid, snippet_io_point);
+        for input in composite_snippet_definition.get_input_names() {
+            self.add_io_point_provided_external_snippet(
+                sequential_id_generator,
+                &mut external_snippet,
+                input,
+                "".to_string(),
+                true,
+            )?;
         }
 
-        //add it to manager
-        self.external_snippets.push(external_snippet);
+        for output in composite_snippet_definition.get_output_names() {
+            self.add_io_point_provided_external_snippet(
+                sequential_id_generator,
+                &mut external_snippet,
+                output,
+                "".to_string(),
+                false,
+            )?;
+        }
 
-        return uuid;
+        return Ok(self.insert_external_snippet(external_snippet, directory_uuid));
     }
 
-    /// create a snippet that has no contents
-    pub fn create_empty_snippet(&mut self, sequential_id_generator: &mut SequentialIdGenerator, name: &str) -> Uuid {
-        //create external snippet
-        let external_snippet = ExternalSnippet::empty(sequential_id_generator, name);
+    /// Create the external snippet of a builtin snippet
+    ///
+    /// # Arguments
+    /// * 'builtin_snippet_kind' - builtin snippet to create
+    /// * 'package_path' - package path of the builtin snippet
+    /// * 'directory_uuid' - uuid of the directory entry of the builtin snippet
+    pub fn create_builtin_snippet(
+        &mut self,
+        builtin_snippet_kind: BuiltinSnippetKind,
+        package_path: PackagePath,
+        directory_uuid: Uuid,
+        sequential_id_generator: &mut SequentialIdGenerator,
+    ) -> Result<Uuid, String> {
+        let mut external_snippet = ExternalSnippet::empty(
+            sequential_id_generator,
+            &builtin_snippet_kind.to_string(),
+            package_path,
+        );
+        external_snippet.kind = ExternalSnippetKind::Builtin(builtin_snippet_kind);
+
+        for input in builtin_snippet_kind.get_inputs() {
+            self.add_io_point_provided_external_snippet(
+                sequential_id_generator,
+                &mut external_snippet,
+                input,
+                "".to_string(),
+                true,
+            )?;
+        }
+
+        for output in builtin_snippet_kind.get_outputs() {
+            self.add_io_point_provided_external_snippet(
+                sequential_id_generator,
+                &mut external_snippet,
+                output,
+                "".to_string(),
+                false,
+            )?;
+        }
 
-        //get uuid of external snippet
-        let uuid = external_snippet.uuid;
+        for (parameter_name, parameter_type) in builtin_snippet_kind.get_parameters() {
+            let external_snippet_parameter = ExternalSnippetParameter::new(
+                sequential_id_generator,
+                parameter_name,
+                parameter_type,
+                ExternalSnippetParameterOptions::default(),
+            );
 
-        //add it to manager
-        self.external_snippets.push(external_snippet);
+            external_snippet
+                .parameters
+                .insert(external_snippet_parameter.uuid, external_snippet_parameter);
+        }
 
-        return uuid;
-    }*/
+        return Ok(self.insert_external_snippet(external_snippet, directory_uuid));
+    }
 
     /// add io points, given the input and output points
     pub fn add_io_points(
@@ -481,16 +1282,23 @@ impl ExternalSnippetManager {
         external_snippet: &mut ExternalSnippet,
         parameter_name: String,
         parameter_type: String,
+        options: ExternalSnippetParameterOptions,
     ) -> Result<Uuid, &'static str> {
         // get as parameter type
-        let parameter_type_proper =
-            ExternalSnippetParameterType::from_str(&parameter_type).unwrap();
+        let parameter_type_proper = match ExternalSnippetParameterType::from_str(&parameter_type) {
+            Ok(some) => some,
+            Err(_) => {
+                return Err("parameter type is not a supported parameter type");
+            }
+        };
 
         // create external snippet parameter
+        // options were validated against the type when they were given in init
         let external_snippet_parameter = ExternalSnippetParameter::new(
             sequential_id_generator,
             parameter_name,
             parameter_type_proper,
+            options,
         );
 
         // get uuid of snippet parameter
@@ -530,6 +1338,32 @@ impl ExternalSnippetManager {
             .map(|(_, v)| v);
     }
 
+    /// find reference to external snippet from its package path
+    ///
+    /// # Arguments
+    ///
+    /// * 'package_path' - package path of the external snippet to find
+    pub fn find_external_snippet_from_package_path(
+        &self,
+        package_path: &PackagePath,
+    ) -> Option<&ExternalSnippet> {
+        return self
+            .external_snippets
+            .iter()
+            .find(|(_, external_snippet)| external_snippet.package_path == *package_path)
+            .map(|(_, v)| v);
+    }
+
+    /// find the external snippet of a builtin snippet
+    pub fn find_builtin_external_snippet(
+        &self,
+        builtin_snippet_kind: BuiltinSnippetKind,
+    ) -> Option<&ExternalSnippet> {
+        return self.external_snippets.values().find(|external_snippet| {
+            external_snippet.kind == ExternalSnippetKind::Builtin(builtin_snippet_kind)
+        });
+    }
+
     pub fn find_external_snippet_from_directory_uuid(
         &self,
         uuid: Uuid,
@@ -542,6 +1376,23 @@ impl ExternalSnippetManager {
         return self.find_external_snippet(external_snippet_uuid);
     }
 
+    /// get the snippets of the directory that could not be initialized
+    pub fn get_initialization_errors(&self) -> &Vec<SnippetInitializationError> {
+        return &self.initialization_errors;
+    }
+
+    /// find why the snippet of the directory entry could not be initialized
+    /// none if it was initialized
+    pub fn find_initialization_error_from_directory_uuid(
+        &self,
+        uuid: Uuid,
+    ) -> Option<&SnippetInitializationError> {
+        return self
+            .initialization_errors
+            .iter()
+            .find(|initialization_error| initialization_error.directory_uuid == uuid);
+    }
+
     pub fn find_directory_uuid_from_external_snippet(&self, uuid: Uuid) -> Option<Uuid> {
         return self
             .external_snippets_to_directory_entries
@@ -600,6 +1451,8 @@ impl ExternalSnippet {
             sub_directory: String::new(),
             io_points: HashMap::with_capacity(2),
             parameters: HashMap::new(),
+            kind: ExternalSnippetKind::Python,
+            metadata: ExternalSnippetMetadata::default(),
         };
 
         return external_snippet;
@@ -616,6 +1469,28 @@ impl ExternalSnippet {
         };
     }
 
+    /// set the help text of an io point
+    ///
+    /// # Arguments
+    /// * 'uuid' - uuid of the io point in question
+    /// * 'description' - help text of the io point
+    fn set_io_point_description(
+        &mut self,
+        uuid: Uuid,
+        description: Option<String>,
+    ) -> Result<(), String> {
+        let io_point = match self.find_io_point(uuid) {
+            Ok(some) => some,
+            Err(e) => {
+                return Err(e.to_string());
+            }
+        };
+
+        io_point.description = description;
+
+        return Ok(());
+    }
+
     fn find_parameter(&mut self, uuid: Uuid) -> Result<&mut ExternalSnippetParameter, &str> {
         match self.parameters.get_mut(&uuid) {
             Some(result) => return Ok(result),
@@ -636,6 +1511,18 @@ impl ExternalSnippet {
         return self.package_path.to_owned();
     }
 
+    pub fn get_kind(&self) -> ExternalSnippetKind {
+        return self.kind;
+    }
+
+    pub fn is_composite(&self) -> bool {
+        return self.kind == ExternalSnippetKind::Composite;
+    }
+
+    pub fn get_metadata(&self) -> &ExternalSnippetMetadata {
+        return &self.metadata;
+    }
+
     /// get the io points as pipeline connectors
     /// for the snippet manager
     pub fn create_pipeline_connectors_for_io_points(
@@ -645,12 +1532,15 @@ impl ExternalSnippet {
         let mut pipeline_connectors = Vec::with_capacity(self.io_points.len());
 
         for io_point_pair in &self.io_points {
-            pipeline_connectors.push(PipelineConnectorComponent::new(
+            let mut pipeline_connector = PipelineConnectorComponent::new(
                 sequential_id_generator,
                 io_point_pair.0.clone(),
                 &io_point_pair.1.name,
                 io_point_pair.1.input.clone(),
-            ))
+            );
+            pipeline_connector.set_description(io_point_pair.1.description.to_owned());
+
+            pipeline_connectors.push(pipeline_connector);
         }
 
         return pipeline_connectors;
@@ -666,17 +1556,23 @@ impl ExternalSnippet {
         for parameter in &self.parameters {
             // get paramter type as string
             let p_type = parameter.1.p_type.clone();
-            // get parameter type as new parameter storeage
-            let parameter_storage =
-                ExternalSnippetParameterType::into_storage_type(&parameter.1.p_type);
+            // get parameter type as new parameter storeage, filled with the default
+            let parameter_storage = match parameter.1.options.get_default() {
+                Some(default) => match p_type.parse_value(default) {
+                    Ok(some) => some,
+                    Err(_) => ExternalSnippetParameterType::into_storage_type(&p_type),
+                },
+                None => ExternalSnippetParameterType::into_storage_type(&p_type),
+            };
 
             // create parameter component
-            let parameter_component = SnippetParameterComponent::new(
+            let mut parameter_component = SnippetParameterComponent::new(
                 parameter_storage,
                 parameter.1.name.to_owned(),
                 p_type,
                 sequential_id_generator,
             );
+            parameter_component.set_options(parameter.1.options.clone());
 
             parameter_components.push(parameter_component);
         }
@@ -697,6 +1593,7 @@ impl ExternalSnippetIOPoint {
             name: String::from('_'),
             schema: Schema::new(),
             input: input,
+            description: None,
         };
 
         return snippet_io_point;
@@ -713,22 +1610,105 @@ impl ExternalSnippetIOPoint {
             name: name,
             schema: schema,
             input: input,
+            description: None,
         };
 
         return snippet_io_point;
     }
 }
 
+impl ExternalSnippetReload {
+    pub fn get_reloaded(&self) -> &Vec<Uuid> {
+        return &self.reloaded;
+    }
+
+    pub fn get_removed(&self) -> &Vec<(Uuid, PackagePath)> {
+        return &self.removed;
+    }
+
+    pub fn get_initialization_errors(&self) -> &Vec<SnippetInitializationError> {
+        return &self.initialization_errors;
+    }
+}
+
+impl InitializedExternalSnippetBuild {
+    /// build the python snippets, running the init function of each
+    pub fn build(self) -> FinalizedExternalSnippetBuild {
+        return FinalizedExternalSnippetBuild {
+            external_snippet_reload: self.external_snippet_reload,
+            python_snippet_builder: self.python_snippet_builder.build(),
+            composite_snippets: self.composite_snippets,
+        };
+    }
+}
+
+impl SnippetInitializationError {
+    pub fn new(
+        directory_uuid: Uuid,
+        package_path: PackagePath,
+        path: PathBuf,
+        phase: SnippetInitializationPhase,
+        message: String,
+        traceback: Option<String>,
+    ) -> Self {
+        return SnippetInitializationError {
+            directory_uuid: directory_uuid,
+            package_path: package_path,
+            path: path,
+            phase: phase,
+            message: message,
+            traceback: traceback,
+        };
+    }
+
+    pub fn get_traceback(&self) -> Option<String> {
+        return self.traceback.to_owned();
+    }
+}
+
+impl std::fmt::Display for SnippetInitializationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        return write!(
+            f,
+            "{} failed to {} at {}: {}",
+            self.package_path.to_string(),
+            self.phase.to_string().to_lowercase(),
+            self.path.to_string_lossy(),
+            self.message
+        );
+    }
+}
+
+impl ExternalSnippetMetadata {
+    pub fn new(
+        description: Option<String>,
+        tags: Vec<String>,
+        version: Option<String>,
+        author: Option<String>,
+        icon: Option<String>,
+    ) -> Self {
+        return ExternalSnippetMetadata {
+            description: description,
+            tags: tags,
+            version: version,
+            author: author,
+            icon: icon,
+        };
+    }
+}
+
 impl ExternalSnippetParameter {
     pub fn new(
         sequential_id_generator: &mut SequentialIdGenerator,
         name: String,
         p_type: ExternalSnippetParameterType,
+        options: ExternalSnippetParameterOptions,
     ) -> Self {
         let snippet_parameter = ExternalSnippetParameter {
             uuid: sequential_id_generator.get_id(),
             name: name,
             p_type: p_type,
+            options: options,
         };
 
         return snippet_parameter;
@@ -758,17 +1738,27 @@ impl ToString for PackagePath {
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use std::{collections::HashMap, str::FromStr};
 
     use crate::{
+        core_components::snippet_manager::{SnippetManager, SnippetParameterBaseStorage},
         core_services::directory_manager::DirectoryManager,
-        state_management::external_snippet_manager::{
-            ExternalSnippetIOPoint, ExternalSnippetParameter, ExternalSnippetParameterType,
+        state_management::{
+            external_snippet_manager::{
+                ExternalSnippetIOPoint, ExternalSnippetMetadata, ExternalSnippetParameter,
+                ExternalSnippetParameterOptions, ExternalSnippetParameterType,
+            },
+            visual_snippet_component_manager::VisualSnippetComponentManager,
         },
         utils::sequential_id_generator::SequentialIdGenerator,
     };
 
-    use super::{ExternalSnippet, ExternalSnippetManager, PackagePath};
+    use strum::IntoEnumIterator;
+
+    use super::{
+        BuiltinSnippetKind, ExternalSnippet, ExternalSnippetKind, ExternalSnippetManager,
+        PackagePath, SnippetInitializationError, SnippetInitializationPhase,
+    };
 
     #[test]
     /// Testing creating the external snippet manager from the directory manager.
@@ -798,7 +1788,11 @@ mod test {
             )
             .unwrap();
 
-        assert_eq!(external_snippet_manager.external_snippets.len(), 6);
+        // sample directory snippets and the builtin snippets
+        assert_eq!(
+            external_snippet_manager.external_snippets.len(),
+            6 + BuiltinSnippetKind::iter().count()
+        );
 
         // test for external snippet manager state
         let snippet_map: HashMap<String, &ExternalSnippet> = external_snippet_manager
@@ -1180,6 +2174,368 @@ mod test {
 
             assert_eq!(param.p_type, ExternalSnippetParameterType::SingleLineText);
         }
+
+        {
+            let external_snippet = match snippet_map.get("reroute") {
+                Some(snippet) => snippet,
+                None => {
+                    assert!(false);
+
+                    return;
+                }
+            };
+
+            // builtin snippets are under the builtin category
+            assert_eq!(
+                external_snippet.package_path.to_string(),
+                "builtin.reroute".to_string()
+            );
+            assert_eq!(
+                external_snippet.get_kind(),
+                ExternalSnippetKind::Builtin(BuiltinSnippetKind::Reroute)
+            );
+            assert_eq!(external_snippet.io_points.len(), 2);
+            assert_eq!(external_snippet.parameters.len(), 0);
+        }
+    }
+
+    #[test]
+    /// Testing the metadata and connector descriptions set in the init() of a snippet
+    /// reach the external snippet, the front content of its snippets and its library entry
+    fn test_external_snippet_metadata_and_descriptions() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let snippets_directory = std::env::temp_dir().join(format!(
+            "external_snippet_manager_metadata_test_{}",
+            std::process::id()
+        ));
+        let root_directory = snippets_directory.join("root");
+        let app_file = root_directory.join("main").join("add").join("app.py");
+
+        std::fs::create_dir_all(app_file.parent().unwrap()).unwrap();
+        std::fs::write(
+            &app_file,
+            "def init(*args, **kwargs):\n    snippet = args[0]\n    snippet.set_metadata(description=\"Adds two numbers\", tags=[\"math\"], version=\"1.0.0\")\n    snippet.add_input(\"a\", description=\"first number\")\n    snippet.add_input(\"b\")\n    snippet.add_output(\"c\")\n    return snippet\n",
+        )
+        .unwrap();
+
+        let mut directory_manager = DirectoryManager::default();
+        directory_manager
+            .initialize(
+                &root_directory.to_string_lossy().to_string(),
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let mut external_snippet_manager = ExternalSnippetManager::default();
+        external_snippet_manager
+            .create_external_snippets_from_directory(
+                &directory_manager,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let external_snippet = external_snippet_manager
+            .find_external_snippet_from_package_path(&PackagePath::from("main.add".to_string()))
+            .unwrap();
+
+        // metadata set in init
+        assert_eq!(
+            external_snippet.metadata,
+            ExternalSnippetMetadata::new(
+                Some("Adds two numbers".to_string()),
+                vec!["math".to_string()],
+                Some("1.0.0".to_string()),
+                None,
+                None
+            )
+        );
+
+        let io_map: HashMap<(String, bool), &ExternalSnippetIOPoint> = external_snippet
+            .io_points
+            .values()
+            .map(|element| -> ((String, bool), &ExternalSnippetIOPoint) {
+                return ((element.name.to_owned(), element.input.to_owned()), element);
+            })
+            .collect();
+
+        assert_eq!(
+            io_map[&("a".to_string(), true)].description,
+            Some("first number".to_string())
+        );
+        assert_eq!(io_map[&("b".to_string(), true)].description, None);
+        assert_eq!(io_map[&("c".to_string(), false)].description, None);
+
+        // the metadata and connector descriptions reach the front content of a new snippet
+        let mut snippet_manager = SnippetManager::default();
+        let mut visual_snippet_component_manager = VisualSnippetComponentManager::default();
+        let snippet_uuid =
+            snippet_manager.new_snippet(&mut sequential_id_generator, external_snippet, 0.0, 0.0);
+        let front_snippet = serde_json::to_value(
+            snippet_manager
+                .find_snippet(&snippet_uuid)
+                .unwrap()
+                .get_snippet_to_front_snippet(
+                    &mut visual_snippet_component_manager,
+                    &mut sequential_id_generator,
+                    &snippet_manager,
+                ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::from_value::<ExternalSnippetMetadata>(front_snippet["metadata"].clone())
+                .unwrap(),
+            external_snippet.metadata
+        );
+
+        let connector_descriptions: HashMap<(String, bool), serde_json::Value> = front_snippet
+            ["pipeline_connectors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|pipeline_connector| {
+                return (
+                    (
+                        pipeline_connector["name"].as_str().unwrap().to_string(),
+                        pipeline_connector["input"].as_bool().unwrap(),
+                    ),
+                    pipeline_connector["description"].clone(),
+                );
+            })
+            .collect();
+
+        assert_eq!(
+            connector_descriptions[&("a".to_string(), true)],
+            "first number"
+        );
+        assert!(connector_descriptions[&("b".to_string(), true)].is_null());
+
+        // and the library entry of the snippet
+        let front_directory_content =
+            directory_manager.get_as_front(&external_snippet_manager, &mut sequential_id_generator);
+        let front_directory_entry = front_directory_content
+            .iter()
+            .map(|front_directory_entry| serde_json::to_value(front_directory_entry).unwrap())
+            .find(|front_directory_entry| front_directory_entry["name"] == "add")
+            .unwrap();
+
+        assert_eq!(
+            serde_json::from_value::<ExternalSnippetMetadata>(
+                front_directory_entry["metadata"].clone()
+            )
+            .unwrap(),
+            external_snippet.metadata
+        );
+
+        std::fs::remove_dir_all(&snippets_directory).unwrap();
+    }
+
+    #[test]
+    /// snippets that cannot be initialized are skipped, with why, while the others still load
+    fn test_external_snippet_manager_initialization_errors() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let snippets_directory = std::env::temp_dir().join(format!(
+            "external_snippet_manager_test_{}",
+            std::process::id()
+        ));
+        let main_directory = snippets_directory.join("root").join("main");
+
+        let snippets = [
+            (
+                "working",
+                "def init(*args, **kwargs):\n    snippet = args[0]\n    snippet.add_input(\"a\")\n    return snippet\n",
+            ),
+            ("broken_compile", "def init(*args, **kwargs)\n    return args[0]\n"),
+            (
+                "broken_init",
+                "def init(*args, **kwargs):\n    raise ValueError(\"broken snippet\")\n",
+            ),
+        ];
+
+        for (name, code) in snippets.iter() {
+            std::fs::create_dir_all(main_directory.join(name)).unwrap();
+            std::fs::write(main_directory.join(name).join("app.py"), code).unwrap();
+        }
+
+        let mut directory_manager = DirectoryManager::default();
+        directory_manager
+            .initialize(
+                &snippets_directory
+                    .join("root")
+                    .to_string_lossy()
+                    .to_string(),
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let mut external_snippet_manager = ExternalSnippetManager::default();
+        external_snippet_manager
+            .create_external_snippets_from_directory(
+                &directory_manager,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        // the working snippet and the builtin snippets
+        assert_eq!(
+            external_snippet_manager.external_snippets.len(),
+            1 + BuiltinSnippetKind::iter().count()
+        );
+
+        let initialization_errors: HashMap<String, &SnippetInitializationError> =
+            external_snippet_manager
+                .get_initialization_errors()
+                .iter()
+                .map(|element| (element.package_path.to_string(), element))
+                .collect();
+
+        assert_eq!(initialization_errors.len(), 2);
+
+        let initialization_error = initialization_errors.get("main.broken_compile").unwrap();
+        assert_eq!(
+            initialization_error.phase,
+            SnippetInitializationPhase::Compile
+        );
+        assert_eq!(
+            initialization_error.path,
+            main_directory.join("broken_compile").join("app.py")
+        );
+
+        // errors raised in init have the traceback of where they were raised
+        let initialization_error = initialization_errors.get("main.broken_init").unwrap();
+        assert_eq!(initialization_error.phase, SnippetInitializationPhase::Init);
+        assert!(initialization_error
+            .get_traceback()
+            .unwrap()
+            .contains("app.py"));
+
+        // the directory entry of a broken snippet leads to its error
+        assert_eq!(
+            external_snippet_manager
+                .find_initialization_error_from_directory_uuid(initialization_error.directory_uuid),
+            Some(*initialization_error)
+        );
+
+        std::fs::remove_dir_all(snippets_directory).unwrap();
+    }
+
+    #[test]
+    /// snippets whose files changed are built again in place, new snippets are added
+    /// and snippets no longer in the directory are removed
+    fn test_external_snippet_manager_reload() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+
+        let snippets_directory = std::env::temp_dir().join(format!(
+            "external_snippet_manager_reload_test_{}",
+            std::process::id()
+        ));
+        let main_directory = snippets_directory.join("root").join("main");
+        let relative_snippet_directory = snippets_directory
+            .join("root")
+            .to_string_lossy()
+            .to_string();
+
+        let one_input = "def init(*args, **kwargs):\n    snippet = args[0]\n    snippet.add_input(\"a\")\n    return snippet\n";
+        let two_inputs = "def init(*args, **kwargs):\n    snippet = args[0]\n    snippet.add_input(\"a\")\n    snippet.add_input(\"b\")\n    return snippet\n";
+
+        for name in ["kept", "changed", "removed"] {
+            std::fs::create_dir_all(main_directory.join(name)).unwrap();
+            std::fs::write(main_directory.join(name).join("app.py"), one_input).unwrap();
+        }
+
+        let mut directory_manager = DirectoryManager::default();
+        directory_manager
+            .initialize(&relative_snippet_directory, &mut sequential_id_generator)
+            .unwrap();
+
+        let mut external_snippet_manager = ExternalSnippetManager::default();
+        external_snippet_manager
+            .create_external_snippets_from_directory(
+                &directory_manager,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let find_uuid = |external_snippet_manager: &ExternalSnippetManager, package_path: &str| {
+            return external_snippet_manager
+                .find_external_snippet_from_package_path(&PackagePath::from(
+                    package_path.to_string(),
+                ))
+                .map(|external_snippet| external_snippet.get_uuid());
+        };
+
+        let kept_uuid = find_uuid(&external_snippet_manager, "main.kept").unwrap();
+        let changed_uuid = find_uuid(&external_snippet_manager, "main.changed").unwrap();
+        let removed_uuid = find_uuid(&external_snippet_manager, "main.removed").unwrap();
+
+        // change the snippet files
+        std::fs::write(main_directory.join("changed").join("app.py"), two_inputs).unwrap();
+        std::fs::remove_dir_all(main_directory.join("removed")).unwrap();
+        std::fs::create_dir_all(main_directory.join("added")).unwrap();
+        std::fs::write(main_directory.join("added").join("app.py"), one_input).unwrap();
+
+        let changed_paths = vec![
+            main_directory.join("changed").join("app.py"),
+            main_directory.join("removed").join("app.py"),
+            main_directory.join("added").join("app.py"),
+        ];
+
+        // initializing again rescans the directory
+        directory_manager
+            .initialize(&relative_snippet_directory, &mut sequential_id_generator)
+            .unwrap();
+
+        let external_snippet_reload = external_snippet_manager
+            .reload_external_snippets_from_directory(
+                &directory_manager,
+                &changed_paths,
+                &mut sequential_id_generator,
+            )
+            .unwrap();
+
+        let added_uuid = find_uuid(&external_snippet_manager, "main.added").unwrap();
+
+        // only the changed and the new snippet were built again
+        assert_eq!(external_snippet_reload.get_reloaded().len(), 2);
+        assert!(external_snippet_reload
+            .get_reloaded()
+            .contains(&changed_uuid));
+        assert!(external_snippet_reload.get_reloaded().contains(&added_uuid));
+        assert!(
+            external_snippet_reload.get_removed()
+                == &vec![(removed_uuid, PackagePath::from("main.removed".to_string()))]
+        );
+
+        // the changed snippet keeps its uuid, with its new inputs
+        assert_eq!(
+            find_uuid(&external_snippet_manager, "main.changed"),
+            Some(changed_uuid)
+        );
+        assert_eq!(
+            external_snippet_manager
+                .find_external_snippet(changed_uuid)
+                .unwrap()
+                .io_points
+                .len(),
+            2
+        );
+        assert_eq!(find_uuid(&external_snippet_manager, "main.removed"), None);
+
+        // the rescanned directory entry still leads to the kept snippet
+        let kept_directory_entry = directory_manager
+            .find_directory_entry(PackagePath::from("main.kept".to_string()))
+            .unwrap();
+        assert_eq!(
+            external_snippet_manager
+                .find_external_snippet_from_directory_uuid(kept_directory_entry.get_uuid())
+                .unwrap()
+                .get_uuid(),
+            kept_uuid
+        );
+
+        std::fs::remove_dir_all(snippets_directory).unwrap();
     }
 
     // Test package path iterator
@@ -1201,4 +2557,125 @@ mod test {
         assert_eq!(package_path_iter.next().unwrap(), "sub3".to_string());
         assert_eq!(package_path_iter.next().unwrap(), "child".to_string());
     }
+
+    // Test parameter types are read from the type names given to add_parameter, and written back the same
+    #[test]
+    fn test_parameter_type_from_str() {
+        assert_eq!(
+            ExternalSnippetParameterType::from_str("Integer").unwrap(),
+            ExternalSnippetParameterType::Integer
+        );
+        assert_eq!(
+            ExternalSnippetParameterType::from_str("Boolean").unwrap(),
+            ExternalSnippetParameterType::Boolean
+        );
+        assert_eq!(
+            ExternalSnippetParameterType::from_str("Secret").unwrap(),
+            ExternalSnippetParameterType::Secret
+        );
+
+        let enum_type = ExternalSnippetParameterType::from_str("Enum(fast,  slow )").unwrap();
+        assert_eq!(
+            enum_type,
+            ExternalSnippetParameterType::Enum(vec!["fast".to_string(), "slow".to_string()])
+        );
+        assert_eq!(enum_type.to_string(), "Enum(fast, slow)");
+        assert_eq!(
+            ExternalSnippetParameterType::from_str(&enum_type.to_string()).unwrap(),
+            enum_type
+        );
+
+        assert!(ExternalSnippetParameterType::from_str("Enum()").is_err());
+        assert!(ExternalSnippetParameterType::from_str("Number").is_err());
+    }
+
+    // Test options given in init are checked against the parameter type, and constrain values
+    #[test]
+    fn test_parameter_options() {
+        // bounds of a number
+        let options = ExternalSnippetParameterOptions::new(
+            Some("5".to_string()),
+            Some("number of rows".to_string()),
+            false,
+            Some(1.0),
+            Some(10.0),
+            None,
+        );
+        options
+            .validate(&ExternalSnippetParameterType::Integer)
+            .unwrap();
+        assert!(options
+            .validate(&ExternalSnippetParameterType::SingleLineText)
+            .is_err());
+
+        let name = "rows".to_string();
+        options
+            .check_value(&name, &SnippetParameterBaseStorage::Integer(10))
+            .unwrap();
+        assert_eq!(
+            options
+                .check_value(&name, &SnippetParameterBaseStorage::Integer(11))
+                .unwrap_err(),
+            "rows must be at most 10"
+        );
+
+        // the default must fit the options
+        let options = ExternalSnippetParameterOptions::new(
+            Some("0".to_string()),
+            None,
+            false,
+            Some(1.0),
+            None,
+            None,
+        );
+        assert!(options
+            .validate(&ExternalSnippetParameterType::Integer)
+            .is_err());
+
+        // regex of a required text
+        let options = ExternalSnippetParameterOptions::new(
+            None,
+            None,
+            true,
+            None,
+            None,
+            Some("[a-z]+".to_string()),
+        );
+        options
+            .validate(&ExternalSnippetParameterType::SingleLineText)
+            .unwrap();
+
+        let name = "table".to_string();
+        options
+            .check_value(
+                &name,
+                &SnippetParameterBaseStorage::String("users".to_string()),
+            )
+            .unwrap();
+        // the whole value must match
+        assert!(options
+            .check_value(
+                &name,
+                &SnippetParameterBaseStorage::String("users2".to_string())
+            )
+            .is_err());
+        assert_eq!(
+            options
+                .check_value(&name, &SnippetParameterBaseStorage::String(String::new()))
+                .unwrap_err(),
+            "table is required"
+        );
+
+        let options = ExternalSnippetParameterOptions::new(
+            None,
+            None,
+            false,
+            None,
+            None,
+            Some("(".to_string()),
+        );
+        assert!(options
+            .validate(&ExternalSnippetParameterType::SingleLineText)
+            .is_err());
+    }
 }
@@ -0,0 +1,102 @@
+use crate::{
+    core_components::snippet_manager::SnippetManager,
+    state_management::external_snippet_manager::{
+        BuiltinSnippetKind, ExternalSnippetManager, PackagePath, BUILTIN_CONSTANT_VALUE,
+        BUILTIN_REROUTE_INPUT,
+    },
+    utils::sequential_id_generator::{SequentialIdGenerator, Uuid},
+};
+
+/// external snippet manager of the builtin reroute and constant integer snippets,
+/// which need no python to be created
+pub fn create_builtin_external_snippet_manager(
+    sequential_id_generator: &mut SequentialIdGenerator,
+) -> ExternalSnippetManager {
+    let mut external_snippet_manager = ExternalSnippetManager::default();
+
+    for (builtin_snippet_kind, package_path) in [
+        (BuiltinSnippetKind::Reroute, "builtin.reroute"),
+        (
+            BuiltinSnippetKind::ConstantInteger,
+            "builtin.constant_integer",
+        ),
+    ] {
+        let directory_uuid = sequential_id_generator.get_id();
+
+        external_snippet_manager
+            .create_builtin_snippet(
+                builtin_snippet_kind,
+                PackagePath::from(package_path.to_string()),
+                directory_uuid,
+                sequential_id_generator,
+            )
+            .unwrap();
+    }
+
+    return external_snippet_manager;
+}
+
+/// add a constant integer snippet with the value piped into a reroute snippet
+///
+/// returns the (constant snippet uuid, reroute snippet uuid)
+pub fn add_constant_into_reroute(
+    snippet_manager: &mut SnippetManager,
+    external_snippet_manager: &ExternalSnippetManager,
+    sequential_id_generator: &mut SequentialIdGenerator,
+    value: &str,
+) -> (Uuid, Uuid) {
+    let constant_external_snippet = external_snippet_manager
+        .find_external_snippet_from_package_path(&PackagePath::from(
+            "builtin.constant_integer".to_string(),
+        ))
+        .unwrap();
+    let reroute_external_snippet = external_snippet_manager
+        .find_external_snippet_from_package_path(&PackagePath::from("builtin.reroute".to_string()))
+        .unwrap();
+
+    let constant_uuid = snippet_manager.new_snippet(
+        sequential_id_generator,
+        constant_external_snippet,
+        10.0,
+        20.0,
+    );
+    let reroute_uuid = snippet_manager.new_snippet(
+        sequential_id_generator,
+        reroute_external_snippet,
+        110.0,
+        40.0,
+    );
+
+    let constant_snippet = snippet_manager.find_snippet(&constant_uuid).unwrap();
+    let from_pipeline_connector_uuid = constant_snippet
+        .find_pipeline_connector_from_name_and_input(BUILTIN_CONSTANT_VALUE.to_string(), false)
+        .unwrap()
+        .get_uuid();
+    let parameter_uuid = constant_snippet
+        .find_parameter_from_name(BUILTIN_CONSTANT_VALUE.to_string())
+        .unwrap()
+        .get_uuid();
+
+    let to_pipeline_connector_uuid = snippet_manager
+        .find_snippet(&reroute_uuid)
+        .unwrap()
+        .find_pipeline_connector_from_name_and_input(BUILTIN_REROUTE_INPUT.to_string(), true)
+        .unwrap()
+        .get_uuid();
+
+    snippet_manager
+        .create_pipeline(
+            sequential_id_generator,
+            from_pipeline_connector_uuid,
+            to_pipeline_connector_uuid,
+        )
+        .unwrap();
+
+    snippet_manager
+        .find_parameter(&parameter_uuid)
+        .unwrap()
+        .update_value(value.to_string())
+        .unwrap();
+
+    return (constant_uuid, reroute_uuid);
+}
@@ -1,8 +1,13 @@
 use bimap::BiHashMap;
 use serde::{Deserialize, Serialize};
 
-use crate::utils::sequential_id_generator::Uuid;
+use crate::{
+    core_services::project_service::UnresolvedProjectReference,
+    state_management::external_snippet_manager::ExternalSnippetMetadata,
+    utils::sequential_id_generator::Uuid,
+};
 
+#[derive(Clone)]
 pub struct VisualSnippetComponentManager {
     pipeline_front_to_pipeline: BiHashMap<Uuid, Uuid>,
     pipeline_connector_front_to_pipeline_connector: BiHashMap<Uuid, Uuid>,
@@ -15,6 +20,11 @@ pub struct VisualSnippetComponentManager {
 pub struct FrontSnippetContent {
     id: Uuid,
     name: String,
+    x_position: f64,
+    y_position: f64,
+    missing: bool,
+    // description, tags, version, author and icon of the snippet
+    metadata: ExternalSnippetMetadata,
     pipeline_connectors: Vec<FrontPipelineConnectorContent>,
     parameters: Vec<FrontParameterContent>,
 }
@@ -24,12 +34,17 @@ pub struct FrontPipelineConnectorContent {
     id: Uuid,
     name: String,
     input: bool,
+    missing: bool,
+    // help text of the connector
+    description: Option<String>,
 }
 
 //struct for the json serialization for pipieline
 #[derive(Serialize, Deserialize)]
 pub struct FrontPipelineContent {
     id: Uuid,
+    from_pipeline_connector_id: Uuid,
+    to_pipeline_connector_id: Uuid,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -37,6 +52,53 @@ pub struct FrontParameterContent {
     id: Uuid,
     name: String,
     p_type: String,
+    value: String,
+    // help text of the parameter
+    description: Option<String>,
+    required: bool,
+    missing: bool,
+}
+
+//struct for the json serialization of a fully built project
+#[derive(Serialize, Deserialize)]
+pub struct FrontProjectContent {
+    snippets: Vec<FrontSnippetContent>,
+    pipelines: Vec<FrontPipelineContent>,
+    unresolved_references: Vec<UnresolvedProjectReference>,
+}
+
+//struct for the json serialization of a removed pipeline
+#[derive(Serialize, Deserialize)]
+pub struct FrontRemovedPipelineContent {
+    id: Uuid,
+    from_pipeline_connector_id: Uuid,
+    to_pipeline_connector_id: Uuid,
+}
+
+//struct for the json serialization of an updated parameter value
+#[derive(Serialize, Deserialize)]
+pub struct FrontUpdatedParameterContent {
+    id: Uuid,
+    value: String,
+}
+
+//struct for the json serialization of a moved snippet
+#[derive(Serialize, Deserialize)]
+pub struct FrontMovedSnippetContent {
+    id: Uuid,
+    x_position: f64,
+    y_position: f64,
+}
+
+//struct for the json serialization of the changes made to the graph by an undo or redo
+#[derive(Serialize, Deserialize)]
+pub struct FrontGraphChanges {
+    pub added_snippets: Vec<FrontSnippetContent>,
+    pub removed_snippets: Vec<Uuid>,
+    pub added_pipelines: Vec<FrontPipelineContent>,
+    pub removed_pipelines: Vec<FrontRemovedPipelineContent>,
+    pub updated_parameters: Vec<FrontUpdatedParameterContent>,
+    pub moved_snippets: Vec<FrontMovedSnippetContent>,
 }
 
 impl Default for VisualSnippetComponentManager {
@@ -232,6 +294,24 @@ impl VisualSnippetComponentManager {
             .insert(front_uuid, parameter_uuid);
     }
 
+    /// delete parameter front with internal uuid
+    pub fn delete_parameter_by_internal(
+        &mut self,
+        internal_uuid: &Uuid,
+    ) -> Result<(), &'static str> {
+        match self
+            .parameter_front_to_parameter
+            .remove_by_right(internal_uuid)
+        {
+            Some(_) => {
+                return Ok(());
+            }
+            None => {
+                return Err("cannot delete parameter front from internal uuid, as parameter front does not exist");
+            }
+        };
+    }
+
     /// get snippet uuid from parameter
     pub fn find_parameter_uuid_from_parameter_front(
         &self,
@@ -250,12 +330,20 @@ impl FrontSnippetContent {
         uuid: Uuid,
         name: String,
         internal_id: Uuid,
+        x_position: f64,
+        y_position: f64,
+        missing: bool,
+        metadata: ExternalSnippetMetadata,
         pipeline_connectors: Vec<FrontPipelineConnectorContent>,
         parameters: Vec<FrontParameterContent>,
     ) -> Self {
         let front_content = FrontSnippetContent {
             id: uuid,
             name: name,
+            x_position: x_position,
+            y_position: y_position,
+            missing: missing,
+            metadata: metadata,
             pipeline_connectors: pipeline_connectors,
             parameters: parameters,
         };
@@ -274,11 +362,15 @@ impl FrontPipelineConnectorContent {
         pipeline_connector_id: Uuid,
         name: String,
         input: bool,
+        missing: bool,
+        description: Option<String>,
     ) -> Self {
         let front_content = FrontPipelineConnectorContent {
             id: uuid,
             name: name,
             input: input,
+            missing: missing,
+            description: description,
         };
 
         //add front content to visual component manager
@@ -293,8 +385,14 @@ impl FrontPipelineContent {
         visual_snippet_component_manager: &mut VisualSnippetComponentManager,
         uuid: Uuid,
         pipeline_uuid: Uuid,
+        from_pipeline_connector_front_uuid: Uuid,
+        to_pipeline_connector_front_uuid: Uuid,
     ) -> Self {
-        let front_content = FrontPipelineContent { id: uuid };
+        let front_content = FrontPipelineContent {
+            id: uuid,
+            from_pipeline_connector_id: from_pipeline_connector_front_uuid,
+            to_pipeline_connector_id: to_pipeline_connector_front_uuid,
+        };
 
         //add front content to visual component manager
         visual_snippet_component_manager.put_pipeline(uuid, pipeline_uuid);
@@ -314,11 +412,19 @@ impl FrontParameterContent {
         parameter_uuid: Uuid,
         name: String,
         p_type: String,
+        value: String,
+        description: Option<String>,
+        required: bool,
+        missing: bool,
     ) -> Self {
         let front_content = FrontParameterContent {
             id: uuid,
             name: name,
             p_type: p_type,
+            value: value,
+            description: description,
+            required: required,
+            missing: missing,
         };
 
         // add front content to visual component manager
@@ -327,3 +433,73 @@ impl FrontParameterContent {
         return front_content;
     }
 }
+
+impl FrontProjectContent {
+    pub fn new(
+        snippets: Vec<FrontSnippetContent>,
+        pipelines: Vec<FrontPipelineContent>,
+        unresolved_references: Vec<UnresolvedProjectReference>,
+    ) -> Self {
+        return FrontProjectContent {
+            snippets: snippets,
+            pipelines: pipelines,
+            unresolved_references: unresolved_references,
+        };
+    }
+}
+
+impl Default for FrontProjectContent {
+    fn default() -> Self {
+        return FrontProjectContent {
+            snippets: Vec::new(),
+            pipelines: Vec::new(),
+            unresolved_references: Vec::new(),
+        };
+    }
+}
+
+impl FrontRemovedPipelineContent {
+    pub fn new(
+        uuid: Uuid,
+        from_pipeline_connector_front_uuid: Uuid,
+        to_pipeline_connector_front_uuid: Uuid,
+    ) -> Self {
+        return FrontRemovedPipelineContent {
+            id: uuid,
+            from_pipeline_connector_id: from_pipeline_connector_front_uuid,
+            to_pipeline_connector_id: to_pipeline_connector_front_uuid,
+        };
+    }
+}
+
+impl FrontUpdatedParameterContent {
+    pub fn new(uuid: Uuid, value: String) -> Self {
+        return FrontUpdatedParameterContent {
+            id: uuid,
+            value: value,
+        };
+    }
+}
+
+impl FrontMovedSnippetContent {
+    pub fn new(uuid: Uuid, x_position: f64, y_position: f64) -> Self {
+        return FrontMovedSnippetContent {
+            id: uuid,
+            x_position: x_position,
+            y_position: y_position,
+        };
+    }
+}
+
+impl Default for FrontGraphChanges {
+    fn default() -> Self {
+        return FrontGraphChanges {
+            added_snippets: Vec::new(),
+            removed_snippets: Vec::new(),
+            added_pipelines: Vec::new(),
+            removed_pipelines: Vec::new(),
+            updated_parameters: Vec::new(),
+            moved_snippets: Vec::new(),
+        };
+    }
+}
@@ -1,36 +1,70 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
 use serde::Serialize;
 
-use crate::core_components::snippet_manager::SnippetManager;
 use crate::core_services::project_service::ProjectManager;
 use crate::utils::sequential_id_generator::SequentialIdGenerator;
 use crate::utils::sequential_id_generator::Uuid;
 
-use super::visual_snippet_component_manager::VisualSnippetComponentManager;
+use super::external_snippet_manager::ExternalSnippetManager;
+use super::visual_snippet_component_manager::FrontProjectContent;
 
 pub struct WindowManager {
-    window_sessions: Vec<WindowSession>,
+    window_sessions: HashMap<Uuid, WindowSession>,
 }
 
 pub struct WindowSession {
+    pub uuid: Uuid,
+    // label of the tauri window the session belongs to
+    window_label: String,
+    // open projects, in the order of their tabs
+    projects: Vec<OpenProject>,
+    active_project_uuid: Uuid,
+    // cancellation flags of the runs spawned from the session
+    active_runs: Vec<Arc<AtomicBool>>,
+}
+
+pub struct OpenProject {
     pub uuid: Uuid,
     pub project_manager: ProjectManager,
 }
 
+//struct for the json serialization of an open project tab
+#[derive(Serialize)]
+pub struct FrontOpenProject {
+    id: Uuid,
+    // none if the project was never saved or opened
+    name: Option<String>,
+    dirty: bool,
+    active: bool,
+}
+
+//struct for the json serialization of a project opened into a tab
+#[derive(Serialize)]
+pub struct FrontOpenedProject {
+    project_id: Uuid,
+    project_content: FrontProjectContent,
+}
+
 impl WindowManager {
     /// create a new window session for the window sessions manager
     /// returns uuid of session
     pub fn new_window_session(
         &mut self,
         sequential_id_generator: &mut SequentialIdGenerator,
+        window_label: String,
     ) -> Uuid {
         //create new window session
-        let window_session = WindowSession::new(sequential_id_generator);
+        let window_session = WindowSession::new(sequential_id_generator, window_label);
 
         //copy window session uuid
         let window_session_uuid = window_session.uuid;
 
-        //add window session to window sessions list
-        self.window_sessions.push(window_session);
+        //add window session to window sessions map
+        self.window_sessions
+            .insert(window_session_uuid, window_session);
 
         //return uuid of window session
         return window_session_uuid;
@@ -38,60 +72,225 @@ impl WindowManager {
 
     /// find a reference to a window session in the window manager
     pub fn find_window_session(&self, uuid: u32) -> Option<&WindowSession> {
-        let window_index_result: Option<usize> =
-            self.window_sessions.iter().position(|w| w.uuid == uuid);
-
-        // handle result cases
-        // if found, get index
-        // else, return with none
-        let window_index: usize = match window_index_result {
-            Some(i) => i,
-            None => return None,
-        };
-
-        //get mutable reference to window session
-        let window: &WindowSession = self.window_sessions.get(window_index).unwrap(); //self.window_sessions.iter().find(|&w| w.uuid == uuid).as_mut();
-
-        //return result
-        return Some(window);
+        return self.window_sessions.get(&uuid);
     }
 
     /// find a mutable reference window session in the window manager
     pub fn find_window_session_mut(&mut self, uuid: u32) -> Option<&mut WindowSession> {
-        let window_index_result: Option<usize> =
-            self.window_sessions.iter().position(|w| w.uuid == uuid);
+        return self.window_sessions.get_mut(&uuid);
+    }
 
-        // handle result cases
-        // if found, get index
-        // else, return with none
-        let window_index: usize = match window_index_result {
-            Some(i) => i,
-            None => return None,
+    /// remove the window session from the window manager, cancelling its active runs
+    /// returns the removed window session
+    pub fn close_window_session(&mut self, uuid: Uuid) -> Result<WindowSession, &'static str> {
+        let mut window_session = match self.window_sessions.remove(&uuid) {
+            Some(result) => result,
+            None => {
+                return Err("window session could not be found");
+            }
         };
 
-        //get mutable reference to window session
-        let window: &mut WindowSession = self.window_sessions.get_mut(window_index).unwrap(); //self.window_sessions.iter().find(|&w| w.uuid == uuid).as_mut();
+        window_session.cancel_active_runs();
+
+        return Ok(window_session);
+    }
+
+    /// get the uuids of the window sessions of the tauri window
+    pub fn find_window_session_uuids_from_label(&self, window_label: &str) -> Vec<Uuid> {
+        return self
+            .window_sessions
+            .values()
+            .filter(|window_session| window_session.window_label == window_label)
+            .map(|window_session| window_session.uuid)
+            .collect();
+    }
+
+    /// get mutable references to all the window sessions
+    pub fn get_window_sessions_mut(&mut self) -> impl Iterator<Item = &mut WindowSession> {
+        return self.window_sessions.values_mut();
+    }
+}
 
-        //return result
-        return Some(window);
+impl FrontOpenedProject {
+    pub fn new(project_id: Uuid, project_content: FrontProjectContent) -> Self {
+        return FrontOpenedProject {
+            project_id: project_id,
+            project_content: project_content,
+        };
     }
 }
 
 impl Default for WindowManager {
     fn default() -> Self {
         return WindowManager {
-            window_sessions: Vec::with_capacity(1),
+            window_sessions: HashMap::with_capacity(1),
         };
     }
 }
 
 impl WindowSession {
-    /// create a new window session
-    pub fn new(sequential_id_generator: &mut SequentialIdGenerator) -> Self {
-        return WindowSession {
+    /// create a new window session, with an empty project open
+    pub fn new(sequential_id_generator: &mut SequentialIdGenerator, window_label: String) -> Self {
+        let mut window_session = WindowSession {
             uuid: sequential_id_generator.get_id(),
-            project_manager: ProjectManager::new(),
+            window_label: window_label,
+            projects: Vec::with_capacity(1),
+            active_project_uuid: 0,
+            active_runs: Vec::new(),
+        };
+
+        window_session.add_project(sequential_id_generator, ProjectManager::new());
+
+        return window_session;
+    }
+
+    /// open the project in a new tab, making it the active project
+    /// returns uuid of the project
+    pub fn add_project(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        project_manager: ProjectManager,
+    ) -> Uuid {
+        let project_uuid = sequential_id_generator.get_id();
+
+        self.projects.push(OpenProject {
+            uuid: project_uuid,
+            project_manager: project_manager,
+        });
+        self.active_project_uuid = project_uuid;
+
+        return project_uuid;
+    }
+
+    /// find a reference to an open project manager
+    pub fn find_project_manager(&self, uuid: Uuid) -> Option<&ProjectManager> {
+        return self
+            .projects
+            .iter()
+            .find(|open_project| open_project.uuid == uuid)
+            .map(|open_project| &open_project.project_manager);
+    }
+
+    /// find a mutable reference to an open project manager
+    pub fn find_project_manager_mut(&mut self, uuid: Uuid) -> Option<&mut ProjectManager> {
+        return self
+            .projects
+            .iter_mut()
+            .find(|open_project| open_project.uuid == uuid)
+            .map(|open_project| &mut open_project.project_manager);
+    }
+
+    /// find the uuid of the open project of the project name
+    pub fn find_project_uuid_from_name(&self, project_name: &String) -> Option<Uuid> {
+        return self
+            .projects
+            .iter()
+            .find(|open_project| {
+                open_project.project_manager.get_project_name().as_ref() == Some(project_name)
+            })
+            .map(|open_project| open_project.uuid);
+    }
+
+    /// make the open project the active project
+    pub fn switch_project(&mut self, uuid: Uuid) -> Result<(), &'static str> {
+        if let None = self.find_project_manager(uuid) {
+            return Err("project could not be found");
+        }
+
+        self.active_project_uuid = uuid;
+
+        return Ok(());
+    }
+
+    /// close the open project, opening an empty project if it was the last one
+    /// returns uuid of the active project
+    pub fn close_project(
+        &mut self,
+        sequential_id_generator: &mut SequentialIdGenerator,
+        uuid: Uuid,
+    ) -> Result<Uuid, &'static str> {
+        let project_index = match self
+            .projects
+            .iter()
+            .position(|open_project| open_project.uuid == uuid)
+        {
+            Some(i) => i,
+            None => {
+                return Err("project could not be found");
+            }
         };
+
+        let closed_project = self.projects.remove(project_index);
+
+        // the project was closed on purpose, its changes do not need to be recovered
+        closed_project.project_manager.discard_recovery();
+
+        if self.projects.len() == 0 {
+            self.add_project(sequential_id_generator, ProjectManager::new());
+        } else if self.active_project_uuid == uuid {
+            // the tab taking the place of the closed tab becomes active
+            let active_index = project_index.min(self.projects.len() - 1);
+            self.active_project_uuid = self.projects[active_index].uuid;
+        }
+
+        return Ok(self.active_project_uuid);
+    }
+
+    pub fn get_active_project_uuid(&self) -> Uuid {
+        return self.active_project_uuid;
+    }
+
+    /// get a reference to the project manager of the active project
+    pub fn get_active_project_manager(&self) -> &ProjectManager {
+        // can safely unwrap as the active project is always open
+        return self.find_project_manager(self.active_project_uuid).unwrap();
+    }
+
+    /// get mutable references to all the open project managers
+    pub fn get_project_managers_mut(&mut self) -> impl Iterator<Item = &mut ProjectManager> {
+        return self
+            .projects
+            .iter_mut()
+            .map(|open_project| &mut open_project.project_manager);
+    }
+
+    /// register a new run of the session
+    /// returns the flag the run checks to know it was cancelled
+    pub fn add_active_run(&mut self) -> Arc<AtomicBool> {
+        // runs that finished have dropped their flag
+        self.active_runs
+            .retain(|cancelled| Arc::strong_count(cancelled) > 1);
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.active_runs.push(Arc::clone(&cancelled));
+
+        return cancelled;
+    }
+
+    /// cancel all the runs of the session, they stop before running their next snippet
+    pub fn cancel_active_runs(&mut self) {
+        for cancelled in self.active_runs.drain(..) {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// get the open projects, in the order of their tabs
+    pub fn get_open_projects(
+        &self,
+        external_snippet_manager: &ExternalSnippetManager,
+    ) -> Vec<FrontOpenProject> {
+        return self
+            .projects
+            .iter()
+            .map(|open_project| FrontOpenProject {
+                id: open_project.uuid,
+                name: open_project.project_manager.get_project_name(),
+                dirty: open_project
+                    .project_manager
+                    .has_unsaved_changes(external_snippet_manager),
+                active: open_project.uuid == self.active_project_uuid,
+            })
+            .collect();
     }
 }
 
@@ -99,7 +298,184 @@ impl Default for WindowSession {
     fn default() -> Self {
         return WindowSession {
             uuid: 0,
-            project_manager: ProjectManager::default(),
+            window_label: String::default(),
+            projects: vec![OpenProject {
+                uuid: 0,
+                project_manager: ProjectManager::default(),
+            }],
+            active_project_uuid: 0,
+            active_runs: Vec::new(),
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use crate::{
+        core_services::project_service::ProjectManager,
+        state_management::external_snippet_manager::ExternalSnippetManager,
+        utils::sequential_id_generator::SequentialIdGenerator,
+    };
+
+    use super::{WindowManager, WindowSession};
+
+    #[test]
+    fn test_open_switch_and_close_tabs() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let external_snippet_manager = ExternalSnippetManager::default();
+
+        // a new session starts with an empty project
+        let mut window_session =
+            WindowSession::new(&mut sequential_id_generator, "main".to_string());
+        let first_project_uuid = window_session.get_active_project_uuid();
+        assert_eq!(
+            window_session
+                .get_open_projects(&external_snippet_manager)
+                .len(),
+            1
+        );
+
+        // opened projects become active, in the order of their tabs
+        let second_project_uuid =
+            window_session.add_project(&mut sequential_id_generator, ProjectManager::new());
+        let third_project_uuid =
+            window_session.add_project(&mut sequential_id_generator, ProjectManager::new());
+        assert_eq!(window_session.get_active_project_uuid(), third_project_uuid);
+
+        let open_projects = window_session.get_open_projects(&external_snippet_manager);
+        assert_eq!(
+            open_projects
+                .iter()
+                .map(|open_project| open_project.id)
+                .collect::<Vec<_>>(),
+            vec![first_project_uuid, second_project_uuid, third_project_uuid]
+        );
+        assert_eq!(
+            open_projects
+                .iter()
+                .map(|open_project| open_project.active)
+                .collect::<Vec<_>>(),
+            vec![false, false, true]
+        );
+
+        // switch tabs
+        window_session.switch_project(second_project_uuid).unwrap();
+        assert_eq!(
+            window_session.get_active_project_uuid(),
+            second_project_uuid
+        );
+        assert!(window_session.switch_project(1000).is_err());
+        assert_eq!(
+            window_session.get_active_project_uuid(),
+            second_project_uuid
+        );
+
+        // closing the active tab activates the tab taking its place
+        let active_project_uuid = window_session
+            .close_project(&mut sequential_id_generator, second_project_uuid)
+            .unwrap();
+        assert_eq!(active_project_uuid, third_project_uuid);
+        assert!(window_session
+            .find_project_manager(second_project_uuid)
+            .is_none());
+
+        // or the tab before it, if it was the last tab
+        let active_project_uuid = window_session
+            .close_project(&mut sequential_id_generator, third_project_uuid)
+            .unwrap();
+        assert_eq!(active_project_uuid, first_project_uuid);
+
+        // closing an inactive tab leaves the active tab
+        let fourth_project_uuid =
+            window_session.add_project(&mut sequential_id_generator, ProjectManager::new());
+        let active_project_uuid = window_session
+            .close_project(&mut sequential_id_generator, first_project_uuid)
+            .unwrap();
+        assert_eq!(active_project_uuid, fourth_project_uuid);
+
+        // closing the last tab opens an empty project
+        let active_project_uuid = window_session
+            .close_project(&mut sequential_id_generator, fourth_project_uuid)
+            .unwrap();
+        assert_ne!(active_project_uuid, fourth_project_uuid);
+        assert_eq!(
+            window_session
+                .get_open_projects(&external_snippet_manager)
+                .len(),
+            1
+        );
+        assert!(window_session
+            .find_project_manager(active_project_uuid)
+            .is_some());
+
+        assert!(window_session
+            .close_project(&mut sequential_id_generator, fourth_project_uuid)
+            .is_err());
+    }
+
+    #[test]
+    fn test_close_window_session_cancels_runs() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let mut window_manager = WindowManager::default();
+
+        let closed_window_session_uuid =
+            window_manager.new_window_session(&mut sequential_id_generator, "main".to_string());
+        let kept_window_session_uuid =
+            window_manager.new_window_session(&mut sequential_id_generator, "other".to_string());
+
+        let (first_run, second_run) = {
+            let window_session = window_manager
+                .find_window_session_mut(closed_window_session_uuid)
+                .unwrap();
+            (
+                window_session.add_active_run(),
+                window_session.add_active_run(),
+            )
+        };
+        let kept_run = window_manager
+            .find_window_session_mut(kept_window_session_uuid)
+            .unwrap()
+            .add_active_run();
+
+        window_manager
+            .close_window_session(closed_window_session_uuid)
+            .unwrap();
+
+        // the runs of the closed session are cancelled, the runs of other sessions go on
+        assert!(first_run.load(Ordering::Relaxed));
+        assert!(second_run.load(Ordering::Relaxed));
+        assert!(!kept_run.load(Ordering::Relaxed));
+
+        assert!(window_manager
+            .find_window_session(closed_window_session_uuid)
+            .is_none());
+        assert!(window_manager
+            .close_window_session(closed_window_session_uuid)
+            .is_err());
+        assert_eq!(
+            window_manager.find_window_session_uuids_from_label("other"),
+            vec![kept_window_session_uuid]
+        );
+        assert!(window_manager
+            .find_window_session_uuids_from_label("main")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_finished_runs_are_forgotten() {
+        let mut sequential_id_generator = SequentialIdGenerator::default();
+        let mut window_session =
+            WindowSession::new(&mut sequential_id_generator, "main".to_string());
+
+        // a run which finished dropped its flag
+        drop(window_session.add_active_run());
+        let active_run = window_session.add_active_run();
+        assert_eq!(window_session.active_runs.len(), 1);
+
+        window_session.cancel_active_runs();
+        assert!(active_run.load(Ordering::Relaxed));
+        assert!(window_session.active_runs.is_empty());
+    }
+}
@@ -1,4 +1,7 @@
 pub mod directory_tauri_service;
+pub mod logging_tauri_service;
 pub mod project_tauri_service;
+pub mod run_history_tauri_service;
 pub mod snippet_tauri_service;
+pub mod variable_profile_tauri_service;
 pub mod window_session_tauri_service;
@@ -7,6 +7,7 @@ use tauri::Manager;
 
 use crate::{
     core_services::{
+        concurrent_processes::get_logs_directory,
         concurrent_processes::spawn_initialize_directory_event,
         concurrent_processes::spawn_initialize_workspace_event,
         installation_manager::{fetch_new_snippets_zip, unpack_snippet_zip_if_exists},
@@ -26,9 +27,10 @@ pub fn get_snippet_directory_details(
 
     let sequential_id_generator = &mut state.sequential_id_generator;
     let directory_manager = &mut state.directory_manager;
+    let external_snippet_manager = &state.external_snippet_manager;
 
     // get front directory content
-    return directory_manager.get_as_front(sequential_id_generator);
+    return directory_manager.get_as_front(external_snippet_manager, sequential_id_generator);
 }
 
 #[tauri::command]
@@ -56,11 +58,16 @@ pub fn spawn_initialize_snippet_directory_and_workspace(
     let mut state_guard: MutexGuard<ApplicationState> = application_state.0.lock().unwrap();
     let state = state_guard.deref_mut();
 
-    // create log file and stream from window uuid
-    // that way the log instance is specific to the window uuid
+    // create a log stream of its own for the initialization,
+    // it belongs to the window session that asked for it
     let mut logging_instance = state
         .logging_manager
-        .create_new_stream(app_handle, window_session_uuid)
+        .create_new_stream(
+            app_handle,
+            &mut state.sequential_id_generator,
+            window_session_uuid,
+            get_logs_directory().join("initialization"),
+        )
         .unwrap();
     let stream_i = logging_instance.get_stream_i();
 
@@ -86,13 +93,12 @@ pub fn spawn_initialize_snippet_directory_and_workspace(
 
         spawn_initialize_workspace_event(application_state_ref_two.0).await;
 
-        // close the log
-        let app_handle = logging_instance.close_log();
-
-        // emit event back to front end
-        app_handle
-            .emit_all("directory_and_workspace_initialized", "".to_string())
-            .unwrap();
+        // close the log, emit event back to front end
+        if let Some(app_handle) = logging_instance.close_log() {
+            app_handle
+                .emit_all("directory_and_workspace_initialized", "".to_string())
+                .unwrap();
+        }
 
         // spawn download zip file
         match fetch_new_snippets_zip().await {
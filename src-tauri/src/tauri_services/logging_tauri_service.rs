@@ -0,0 +1,92 @@
+use crate::{
+    core_services::{
+        project_service::ProjectManager,
+        runtime_logging_service::{
+            get_past_logs, read_past_log, FrontLogStream, FrontPastLog, LogEntry,
+        },
+    },
+    state_management::{window_manager::WindowSession, SharedApplicationState},
+    utils::sequential_id_generator::Uuid,
+};
+use std::ops::DerefMut;
+
+/// subscribe to a log stream, returning the logs of the stream so far
+/// the logs emitted afterwards are listened to on the log event of the stream
+#[tauri::command]
+pub fn subscribe_log_stream(
+    application_state: tauri::State<SharedApplicationState>,
+    stream_id: Uuid,
+) -> Result<FrontLogStream, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    return state.logging_manager.subscribe_to_stream(stream_id);
+}
+
+/// get the past run logs of the project, most recent first
+#[tauri::command]
+pub fn get_project_past_logs(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+) -> Result<Vec<FrontPastLog>, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &WindowSession = match state
+        .window_manager
+        .find_window_session(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &ProjectManager = match window_session.find_project_manager(project_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("project could not be found".to_string());
+        }
+    };
+
+    return Ok(get_past_logs(&project_manager.get_logs_directory()));
+}
+
+/// read the entries of a past run log of the project
+#[tauri::command]
+pub fn read_project_past_log(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    log_id: String,
+) -> Result<Vec<LogEntry>, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &WindowSession = match state
+        .window_manager
+        .find_window_session(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &ProjectManager = match window_session.find_project_manager(project_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("project could not be found".to_string());
+        }
+    };
+
+    return read_past_log(&project_manager.get_logs_directory(), &log_id);
+}
@@ -1,11 +1,17 @@
 use crate::{
     core_services::{
         concurrent_processes::get_projects_directory,
-        project_service::{get_project_directory_location_from_name, Plan},
+        project_service::{
+            discard_recovery_project as discard_recovery_project_file,
+            get_recovery_projects as get_recovery_project_files, ProjectManager,
+            ProjectReferenceRemapping, RecoveryProject,
+        },
     },
     state_management::{
-        external_snippet_manager::PackagePath, window_manager::WindowSession, ApplicationState,
-        SharedApplicationState,
+        external_snippet_manager::PackagePath,
+        visual_snippet_component_manager::FrontProjectContent,
+        window_manager::{FrontOpenProject, FrontOpenedProject, WindowSession},
+        ApplicationState, SharedApplicationState,
     },
     utils::sequential_id_generator::Uuid,
 };
@@ -19,6 +25,7 @@ use std::{
 pub fn save_project(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     project_name: String,
 ) -> Result<(), String> {
     // get the state
@@ -27,7 +34,7 @@ pub fn save_project(
 
     //borrow split
     let window_manager = &mut state.window_manager;
-    let external_snippet_manager = &mut state.external_snippet_manager;
+    let external_snippet_manager = &state.external_snippet_manager;
 
     //find window session
     let window_session: &mut WindowSession =
@@ -38,33 +45,44 @@ pub fn save_project(
             }
         };
 
-    // borrow split
-    let project_manager = &mut window_session.project_manager;
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
 
     // remove project parent part from name
     let project_name = project_name.trim_start_matches("projects.").to_string();
 
-    // get location to save project
-    let projects_location = get_project_directory_location_from_name(project_name);
-
     // save project
-    project_manager.save_project(external_snippet_manager, projects_location)?;
+    project_manager.save_project(external_snippet_manager, project_name)?;
 
     return Ok(());
 }
-///the service for commands between tauri and the front end
+/// open the project into a tab of the window session
+///
+/// # Arguments
+/// * 'project_uuid' - project to open the project into, for opening it again with remappings,
+///     none to open it in a new tab
 #[tauri::command]
 pub fn open_project(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Option<Uuid>,
     project_id: String,
-) -> Result<Plan, String> {
+    remappings: Vec<ProjectReferenceRemapping>,
+) -> Result<FrontOpenedProject, String> {
     // get the state
     let state_guard = &mut application_state.0.lock().unwrap();
     let state = &mut state_guard.deref_mut();
 
     //borrow split
     let window_manager = &mut state.window_manager;
+    let external_snippet_manager = &state.external_snippet_manager;
+    let sequential_id_generator = &mut state.sequential_id_generator;
 
     //find window session
     let window_session: &mut WindowSession =
@@ -75,34 +93,242 @@ pub fn open_project(
             }
         };
 
-    // borrow split
-    let project_manager = &mut window_session.project_manager;
-
     // remove project parent part from name
     let project_name = project_id.trim_start_matches("projects.").to_string();
 
-    // get location to save project from project name
-    let project_location = get_project_directory_location_from_name(project_name);
+    // the same project file open in two tabs would overwrite each other
+    if let Some(open_project_uuid) = window_session.find_project_uuid_from_name(&project_name) {
+        if Some(open_project_uuid) != project_uuid {
+            return Err(format!("Project {} is already open", project_name));
+        }
+    }
+
+    let mut new_project_manager = ProjectManager::new();
+
+    let project_manager: &mut ProjectManager = match project_uuid {
+        Some(project_uuid) => match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        },
+        None => &mut new_project_manager,
+    };
 
-    // try to get project build plan
-    let project_build_plan = match project_manager.open_project(project_location) {
+    // build the project from the project file
+    // on failure the currently open project is left untouched
+    let front_project_content = match project_manager.open_project(
+        external_snippet_manager,
+        sequential_id_generator,
+        project_name,
+        &remappings,
+    ) {
         Ok(some) => some,
         Err(e) => {
-            // if cannot get plan, create default (empty) one
-            println!(
-                "Could not create project build plan: {}, resorting to empty build plan",
-                e.to_string()
-            );
-            project_manager.get_default_plan()
+            return Err(format!("Could not open project: {}", e));
         }
     };
 
-    // clear visual snippet service
+    let project_uuid = match project_uuid {
+        Some(project_uuid) => project_uuid,
+        None => window_session.add_project(sequential_id_generator, new_project_manager),
+    };
 
-    // clear snippet service
+    // send fully built project to front end
+    return Ok(FrontOpenedProject::new(project_uuid, front_project_content));
+}
 
-    // send plan to front end, load
-    return Ok(project_build_plan);
+/// get the autosaved projects with changes that were never saved
+#[tauri::command]
+pub fn get_recovery_projects() -> Result<Vec<RecoveryProject>, String> {
+    return get_recovery_project_files();
+}
+
+/// restore an autosaved project into a tab of the window session
+///
+/// # Arguments
+/// * 'project_uuid' - project to restore the project into, for restoring it again with remappings,
+///     none to restore it in a new tab
+#[tauri::command]
+pub fn restore_recovery_project(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Option<Uuid>,
+    recovery_id: String,
+    remappings: Vec<ProjectReferenceRemapping>,
+) -> Result<FrontOpenedProject, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //borrow split
+    let window_manager = &mut state.window_manager;
+    let external_snippet_manager = &state.external_snippet_manager;
+    let sequential_id_generator = &mut state.sequential_id_generator;
+
+    //find window session
+    let window_session: &mut WindowSession =
+        match window_manager.find_window_session_mut(window_session_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("window session could not be found".to_string());
+            }
+        };
+
+    let mut new_project_manager = ProjectManager::new();
+
+    let project_manager: &mut ProjectManager = match project_uuid {
+        Some(project_uuid) => match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        },
+        None => &mut new_project_manager,
+    };
+
+    // build the project from the recovery file
+    // on failure the currently open project is left untouched
+    let front_project_content = match project_manager.restore_recovery_project(
+        external_snippet_manager,
+        sequential_id_generator,
+        recovery_id,
+        &remappings,
+    ) {
+        Ok(some) => some,
+        Err(e) => {
+            return Err(format!("Could not restore project: {}", e));
+        }
+    };
+
+    let project_uuid = match project_uuid {
+        Some(project_uuid) => project_uuid,
+        None => window_session.add_project(sequential_id_generator, new_project_manager),
+    };
+
+    return Ok(FrontOpenedProject::new(project_uuid, front_project_content));
+}
+
+/// open a new empty project in a new tab of the window session
+/// returns uuid of the project
+#[tauri::command]
+pub fn new_project(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+) -> Result<Uuid, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //borrow split
+    let window_manager = &mut state.window_manager;
+    let sequential_id_generator = &mut state.sequential_id_generator;
+
+    //find window session
+    let window_session: &mut WindowSession =
+        match window_manager.find_window_session_mut(window_session_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("window session could not be found".to_string());
+            }
+        };
+
+    return Ok(window_session.add_project(sequential_id_generator, ProjectManager::new()));
+}
+
+/// get the open projects of the window session, in the order of their tabs
+#[tauri::command]
+pub fn get_open_projects(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+) -> Result<Vec<FrontOpenProject>, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //borrow split
+    let window_manager = &state.window_manager;
+    let external_snippet_manager = &state.external_snippet_manager;
+
+    //find window session
+    let window_session: &WindowSession =
+        match window_manager.find_window_session(window_session_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("window session could not be found".to_string());
+            }
+        };
+
+    return Ok(window_session.get_open_projects(external_snippet_manager));
+}
+
+/// make the open project the active project of the window session
+/// returns the front content to draw the project with
+#[tauri::command]
+pub fn switch_project(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+) -> Result<FrontProjectContent, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //borrow split
+    let window_manager = &mut state.window_manager;
+    let sequential_id_generator = &mut state.sequential_id_generator;
+
+    //find window session
+    let window_session: &mut WindowSession =
+        match window_manager.find_window_session_mut(window_session_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("window session could not be found".to_string());
+            }
+        };
+
+    window_session.switch_project(project_uuid)?;
+
+    // can safely unwrap as the project was just switched to
+    let project_manager = window_session
+        .find_project_manager_mut(project_uuid)
+        .unwrap();
+
+    return project_manager.create_front_project_content(sequential_id_generator);
+}
+
+/// close the open project of the window session, discarding its unsaved changes
+/// returns uuid of the active project
+#[tauri::command]
+pub fn close_project(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+) -> Result<Uuid, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //borrow split
+    let window_manager = &mut state.window_manager;
+    let sequential_id_generator = &mut state.sequential_id_generator;
+
+    //find window session
+    let window_session: &mut WindowSession =
+        match window_manager.find_window_session_mut(window_session_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("window session could not be found".to_string());
+            }
+        };
+
+    return Ok(window_session.close_project(sequential_id_generator, project_uuid)?);
+}
+
+/// discard an autosaved project
+#[tauri::command]
+pub fn discard_recovery_project(recovery_id: String) -> Result<(), String> {
+    return discard_recovery_project_file(recovery_id);
 }
 
 #[tauri::command]
@@ -127,8 +353,8 @@ pub fn delete_project(
             }
         };
 
-    // borrow split
-    let project_manager = &mut window_session.project_manager;
+    // any project manager can delete the project file
+    let project_manager = window_session.get_active_project_manager();
 
     // remove project parent part from name
     let project_name = project_id.trim_start_matches("projects.").to_string();
@@ -189,6 +415,7 @@ pub fn get_directory_id_from_package_path(
 pub fn get_front_snippet_connector_id_from_snippet_uuid_and_name(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_snippet_id: Uuid,
     snippet_connector_name: &str,
 ) -> Result<Uuid, String> {
@@ -208,8 +435,14 @@ pub fn get_front_snippet_connector_id_from_snippet_uuid_and_name(
             }
         };
 
-    // borrow split
-    let project_manager = &window_session.project_manager;
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
 
     let visual_snippet_component_manager = &project_manager.visual_component_manager;
     let snippet_manager = &project_manager.snippet_manager;
@@ -266,6 +499,7 @@ pub fn get_front_snippet_connector_id_from_snippet_uuid_and_name(
 pub fn get_front_parameter_id_from_snippet_uuid_and_name(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_snippet_id: Uuid,
     parameter_name: &str,
 ) -> Result<Uuid, String> {
@@ -285,8 +519,14 @@ pub fn get_front_parameter_id_from_snippet_uuid_and_name(
             }
         };
 
-    // borrow split
-    let project_manager = &window_session.project_manager;
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
 
     let visual_snippet_component_manager = &project_manager.visual_component_manager;
     let snippet_manager = &project_manager.snippet_manager;
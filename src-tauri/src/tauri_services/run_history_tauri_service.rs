@@ -0,0 +1,119 @@
+use crate::{
+    core_services::{
+        project_service::ProjectManager,
+        run_history_service::{
+            compare_run_records, get_run_summaries, read_run_record, FrontRunComparison,
+            FrontRunSummary, RunRecord,
+        },
+    },
+    state_management::{window_manager::WindowSession, SharedApplicationState},
+    utils::sequential_id_generator::Uuid,
+};
+use std::ops::DerefMut;
+
+/// get the recorded runs of the project, most recent first
+#[tauri::command]
+pub fn get_project_runs(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+) -> Result<Vec<FrontRunSummary>, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &WindowSession = match state
+        .window_manager
+        .find_window_session(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &ProjectManager = match window_session.find_project_manager(project_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("project could not be found".to_string());
+        }
+    };
+
+    return get_run_summaries(&project_manager.get_runs_directory());
+}
+
+/// open a recorded run of the project, with its parameters, snippet outcomes and logs
+#[tauri::command]
+pub fn open_project_run(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    run_id: String,
+) -> Result<RunRecord, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &WindowSession = match state
+        .window_manager
+        .find_window_session(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &ProjectManager = match window_session.find_project_manager(project_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("project could not be found".to_string());
+        }
+    };
+
+    return read_run_record(&project_manager.get_runs_directory(), &run_id);
+}
+
+/// compare two recorded runs of the project
+#[tauri::command]
+pub fn compare_project_runs(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    left_run_id: String,
+    right_run_id: String,
+) -> Result<FrontRunComparison, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &WindowSession = match state
+        .window_manager
+        .find_window_session(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &ProjectManager = match window_session.find_project_manager(project_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("project could not be found".to_string());
+        }
+    };
+
+    let runs_directory = project_manager.get_runs_directory();
+
+    let left_run_record = read_run_record(&runs_directory, &left_run_id)?;
+    let right_run_record = read_run_record(&runs_directory, &right_run_id)?;
+
+    return Ok(compare_run_records(&left_run_record, &right_run_record));
+}
@@ -1,15 +1,24 @@
 use serde::Serialize;
+use tauri::Manager;
 
 use crate::{
-    core_services::concurrent_processes::spawn_run_snippets_event,
+    core_services::{
+        concurrent_processes::spawn_run_snippets_event,
+        directory_manager::{COMPOSITE_SNIPPETS_CATEGORY, COMPOSITE_SNIPPET_FILE},
+        history_manager::{apply_operation, HistoryOperation},
+        project_service::{write_composite_snippet_definition, ProjectManager},
+    },
     python_libraries::python_run_module::InitializedPythonSnippetRunnerBuilder,
     state_management::{
-        visual_snippet_component_manager::{FrontPipelineContent, FrontSnippetContent},
+        visual_snippet_component_manager::{
+            FrontGraphChanges, FrontPipelineContent, FrontSnippetContent,
+        },
         window_manager::WindowSession,
         ApplicationState, SharedApplicationState,
     },
     utils::sequential_id_generator::Uuid,
 };
+use std::collections::BTreeMap;
 use std::ops::DerefMut;
 use std::sync::MutexGuard;
 
@@ -23,6 +32,7 @@ use std::sync::MutexGuard;
 pub fn new_snippet(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     directory_front_uuid: Uuid,
     x_position: f64,
     y_position: f64,
@@ -46,10 +56,19 @@ pub fn new_snippet(
             }
         };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
     //borrow split
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
+    let history_manager = &mut project_manager.history_manager;
     let visual_directory_component_manager = &mut directory_manager.visual_component_manager;
 
     //get file container external snippet uuid from directory front uuid
@@ -93,6 +112,10 @@ pub fn new_snippet(
         &snippet_manager,
     );
 
+    history_manager.record(HistoryOperation::CreateSnippet {
+        snippet: snippet.clone(),
+    });
+
     //return uuid
     return Ok(front_snippet);
 }
@@ -105,6 +128,7 @@ pub fn new_snippet(
 pub fn get_pipeline_connector_uuids_from_snippet(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_uuid: Uuid,
 ) -> Result<Vec<Uuid>, &str> {
     // get the state
@@ -122,10 +146,18 @@ pub fn get_pipeline_connector_uuids_from_snippet(
         }
     };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
     //borrow split
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
 
     //get pipeline uuid from front uuid
     let snippet_uuid = match visual_snippet_component_manager.find_snippet_uuid(&front_uuid) {
@@ -176,9 +208,10 @@ pub fn get_pipeline_connector_uuids_from_snippet(
 pub fn update_snippet_parameter_value(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_uuid: Uuid,
     value: String,
-) -> Result<(), &str> {
+) -> Result<(), String> {
     // get the state
     let state_guard = &mut application_state.0.lock().unwrap();
     let state = state_guard.deref_mut();
@@ -190,14 +223,23 @@ pub fn update_snippet_parameter_value(
     {
         Some(result) => result,
         None => {
-            return Err("window session could not be found");
+            return Err("window session could not be found".to_string());
         }
     };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
     //borrow split
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
+    let history_manager = &mut project_manager.history_manager;
 
     // get the snippet parameter uuid from the front parameter uuid
     let parameter_uuid = match visual_snippet_component_manager
@@ -205,7 +247,9 @@ pub fn update_snippet_parameter_value(
     {
         Some(some) => some,
         None => {
-            return Err("could not find parameter front in visual snippet component manager");
+            return Err(
+                "could not find parameter front in visual snippet component manager".to_string(),
+            );
         }
     };
 
@@ -213,18 +257,27 @@ pub fn update_snippet_parameter_value(
     let parameter = match snippet_manager.find_parameter(&parameter_uuid) {
         Some(some) => some,
         None => {
-            return Err("could not find parameter in snippet manager");
+            return Err("could not find parameter in snippet manager".to_string());
         }
     };
 
-    // update value in parameter
-    match parameter.update_value(value) {
+    let old_value = parameter.get_storage().to_string();
+
+    // update value in parameter, failing with why the value does not fit the parameter
+    match parameter.update_value(value.to_owned()) {
         Ok(_) => (),
         Err(e) => {
             return Err(e);
         }
     };
 
+    // the value as set, as the mask of a secret leaves the secret unchanged
+    history_manager.record(HistoryOperation::UpdateParameterValue {
+        parameter_uuid: parameter_uuid,
+        old_value: old_value,
+        new_value: parameter.get_storage().to_string(),
+    });
+
     return Ok(());
 }
 
@@ -233,6 +286,7 @@ pub fn update_snippet_parameter_value(
 pub fn update_snippet_position(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_uuid: Uuid,
     x_position: f64,
     y_position: f64,
@@ -252,10 +306,19 @@ pub fn update_snippet_position(
         }
     };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
     //borrow split
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
+    let history_manager = &mut project_manager.history_manager;
 
     // front to internal id
     let snippet_uuid = match visual_snippet_component_manager.find_snippet_uuid(&front_uuid) {
@@ -265,6 +328,13 @@ pub fn update_snippet_position(
         }
     };
 
+    let old_position = match snippet_manager.find_snippet(&snippet_uuid) {
+        Some(result) => result.get_position(),
+        None => {
+            return Err("could not find snippet component from snippet uuid");
+        }
+    };
+
     // update_snippet_position
     match snippet_manager.update_snippet_position(snippet_uuid, x_position, y_position) {
         Ok(result) => result,
@@ -274,11 +344,23 @@ pub fn update_snippet_position(
         }
     };
 
+    // snippet was clicked without being moved
+    if old_position == (x_position, y_position) {
+        return Ok(());
+    }
+
+    history_manager.record(HistoryOperation::MoveSnippet {
+        snippet_uuid: snippet_uuid,
+        old_position: old_position,
+        new_position: (x_position, y_position),
+    });
+
     return Ok(());
 }
 
-/// deletes snippet
+/// deletes snippet and the pipelines connected to it
 /// including all front and root components
+/// returns the changes the front end needs to apply
 ///
 /// # Arguments
 /// * 'front_uuid' - uuid of the snippet
@@ -286,8 +368,9 @@ pub fn update_snippet_position(
 pub fn delete_snippet(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_uuid: Uuid,
-) -> Result<(), &str> {
+) -> Result<FrontGraphChanges, &str> {
     // get the state
     let state_guard = &mut application_state.0.lock().unwrap();
     let state = state_guard.deref_mut();
@@ -303,10 +386,20 @@ pub fn delete_snippet(
         }
     };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
     //borrow split
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let sequential_id_generator = &mut state.sequential_id_generator;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
+    let history_manager = &mut project_manager.history_manager;
 
     //get snippet uuid from front uuid
     let snippet_uuid = match visual_snippet_component_manager.find_snippet_uuid(&front_uuid) {
@@ -324,53 +417,39 @@ pub fn delete_snippet(
         }
     };
 
-    //get pipeline connectors in snippet
-    let pipeline_connector_uuids = snippet_component.get_pipeline_connector_uuids();
+    // keep copies of the snippet and its pipelines, so the delete can be undone
+    let mut pipelines = Vec::new();
 
-    //get pipelines from pipeline connectors
-    for pipeline_connector_uuid in pipeline_connector_uuids.iter() {
-        //if any pipelines are left connected, return error, cannot delete snippet if connected
-        match snippet_manager.find_pipeline(&pipeline_connector_uuid) {
-            Some(_) => {
-                return Err(
-                    "cannot delete snippet becuase pipelines are still connected to this snippet",
-                );
-            }
+    for pipeline_uuid in snippet_manager.find_pipeline_uuids_from_snippet(&snippet_uuid) {
+        match snippet_manager.find_pipeline(&pipeline_uuid) {
+            Some(result) => pipelines.push(result.clone()),
             None => {
-                continue;
+                return Err("could not find pipeline from pipeline uuid");
             }
         };
     }
 
-    //delete pipeline connectors from fornt service
-    for pipeline_connector_uuid in pipeline_connector_uuids.iter() {
-        match visual_snippet_component_manager
-            .delete_pipeline_connector_by_internal(&pipeline_connector_uuid)
-        {
-            Ok(_) => (),
-            Err(err) => {
-                return Err(err);
-            }
-        }
-    }
-
-    //delete snippet from front service
-    match visual_snippet_component_manager.delete_snippet_by_internal(&snippet_uuid) {
-        Ok(_) => (),
-        Err(err) => {
-            return Err(err);
-        }
+    let operation = HistoryOperation::DeleteSnippet {
+        snippet: snippet_component.clone(),
+        pipelines: pipelines,
     };
 
-    //delete snippet from snippet manager
-    match snippet_manager.delete_snippet(&snippet_uuid) {
-        Ok(_) => (),
+    //delete snippet and its pipelines from the front service and snippet manager
+    let front_graph_changes = match apply_operation(
+        &operation,
+        snippet_manager,
+        visual_snippet_component_manager,
+        sequential_id_generator,
+    ) {
+        Ok(result) => result,
         Err(err) => {
             return Err(err);
         }
     };
 
-    return Ok(());
+    history_manager.record(operation);
+
+    return Ok(front_graph_changes);
 }
 
 /// create new pipeline
@@ -383,6 +462,7 @@ pub fn delete_snippet(
 pub fn new_pipeline(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     from_front_uuid: Uuid,
     to_front_uuid: Uuid,
 ) -> Result<FrontPipelineContent, &str> {
@@ -401,12 +481,21 @@ pub fn new_pipeline(
         }
     };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
     //borrow split
     let mut sequential_id_generator = &mut state.sequential_id_generator;
 
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
+    let history_manager = &mut project_manager.history_manager;
 
     //get from and to component uuids from front uuids
     let from_uuid =
@@ -443,10 +532,18 @@ pub fn new_pipeline(
     let pipeline = snippet_manager.find_pipeline(&pipeline_uuid).unwrap();
 
     // get pipeline front content and add to virtaul manager
-    let pipeline_front = pipeline.create_pipeline_as_front_content(
-        visual_snippet_component_manager,
-        sequential_id_generator,
-    );
+    let pipeline_front = match pipeline
+        .create_pipeline_as_front_content(visual_snippet_component_manager, sequential_id_generator)
+    {
+        Ok(result) => result,
+        Err(err) => {
+            return Err(err);
+        }
+    };
+
+    history_manager.record(HistoryOperation::CreatePipeline {
+        pipeline: pipeline.clone(),
+    });
 
     return Ok(pipeline_front);
 }
@@ -455,6 +552,7 @@ pub fn new_pipeline(
 pub fn delete_pipeline(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_uuid: Uuid,
 ) -> Result<(), &str> {
     // get the state
@@ -472,10 +570,19 @@ pub fn delete_pipeline(
         }
     };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
     //borrow split
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
+    let history_manager = &mut project_manager.history_manager;
 
     //get pipeline uuid from front uuid
     let pipeline_uuid = match visual_snippet_component_manager.find_pipeline_uuid(&front_uuid) {
@@ -485,6 +592,14 @@ pub fn delete_pipeline(
         }
     };
 
+    // keep a copy of the pipeline, so the delete can be undone
+    let pipeline = match snippet_manager.find_pipeline(&pipeline_uuid) {
+        Some(result) => result.clone(),
+        None => {
+            return Err("could not find pipeline from pipeline uuid");
+        }
+    };
+
     //delete from visual snippet manager
     match visual_snippet_component_manager.delete_pipeline_by_pipeline_front(&front_uuid) {
         Ok(_) => (),
@@ -501,9 +616,403 @@ pub fn delete_pipeline(
         }
     };
 
+    history_manager.record(HistoryOperation::DeletePipeline { pipeline: pipeline });
+
     return Ok(());
 }
 
+/// copy snippets, their parameter values and the pipelines between them to the clipboard
+/// returns the clipboard content, which can be pasted into any window session
+///
+/// # Arguments
+/// * 'front_uuids' - front uuids of the snippets to copy
+#[tauri::command]
+pub fn copy_snippets(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    front_uuids: Vec<Uuid>,
+) -> Result<String, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    //borrow split
+    let external_snippet_manager = &state.external_snippet_manager;
+
+    let snippet_uuids = find_snippet_uuids_from_front_uuids(project_manager, &front_uuids)?;
+
+    let clipboard = project_manager.copy_snippets(external_snippet_manager, &snippet_uuids)?;
+
+    state.clipboard = Some(clipboard.to_owned());
+
+    return Ok(clipboard);
+}
+
+/// paste snippets into the window session
+/// returns the changes the front end needs to apply
+///
+/// # Arguments
+/// * 'clipboard' - clipboard content to paste, or none to paste the last copied snippets
+/// * 'x_offset' - horizontal offset of the pasted snippets from the copied snippets
+/// * 'y_offset' - vertical offset of the pasted snippets from the copied snippets
+#[tauri::command]
+pub fn paste_snippets(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    clipboard: Option<String>,
+    x_offset: f64,
+    y_offset: f64,
+) -> Result<FrontGraphChanges, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    //borrow split
+    let sequential_id_generator = &mut state.sequential_id_generator;
+    let external_snippet_manager = &state.external_snippet_manager;
+
+    let clipboard = match clipboard.or(state.clipboard.to_owned()) {
+        Some(result) => result,
+        None => {
+            return Err("nothing has been copied to paste".to_string());
+        }
+    };
+
+    return project_manager.paste_snippets(
+        external_snippet_manager,
+        sequential_id_generator,
+        &clipboard,
+        x_offset,
+        y_offset,
+    );
+}
+
+/// duplicate snippets, their parameter values and the pipelines between them
+/// without changing the clipboard
+/// returns the changes the front end needs to apply
+///
+/// # Arguments
+/// * 'front_uuids' - front uuids of the snippets to duplicate
+/// * 'x_offset' - horizontal offset of the duplicates from the snippets
+/// * 'y_offset' - vertical offset of the duplicates from the snippets
+#[tauri::command]
+pub fn duplicate_snippets(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    front_uuids: Vec<Uuid>,
+    x_offset: f64,
+    y_offset: f64,
+) -> Result<FrontGraphChanges, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    //borrow split
+    let sequential_id_generator = &mut state.sequential_id_generator;
+    let external_snippet_manager = &state.external_snippet_manager;
+
+    let snippet_uuids = find_snippet_uuids_from_front_uuids(project_manager, &front_uuids)?;
+
+    let copied_snippets =
+        project_manager.copy_snippets(external_snippet_manager, &snippet_uuids)?;
+
+    return project_manager.paste_snippets(
+        external_snippet_manager,
+        sequential_id_generator,
+        &copied_snippets,
+        x_offset,
+        y_offset,
+    );
+}
+
+/// collapse the snippets into a composite snippet, which is saved to the snippet directory
+/// so it can be reused in any project
+/// returns the changes the front end needs to apply
+///
+/// # Arguments
+/// * 'front_uuids' - front uuids of the snippets in the composite snippet
+/// * 'name' - name of the composite snippet
+#[tauri::command]
+pub fn create_composite_snippet(
+    application_state: tauri::State<SharedApplicationState>,
+    app_handle: tauri::AppHandle,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    front_uuids: Vec<Uuid>,
+    name: String,
+) -> Result<FrontGraphChanges, String> {
+    // the name is the directory name and part of the package path of the composite snippet
+    if name.len() == 0
+        || !name
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || character == '_')
+    {
+        return Err(format!(
+            "Composite snippet name {} can only contain letters, numbers and underscores",
+            name
+        ));
+    }
+
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    //borrow split
+    let sequential_id_generator = &mut state.sequential_id_generator;
+    let external_snippet_manager = &mut state.external_snippet_manager;
+    let directory_manager = &mut state.directory_manager;
+
+    let snippet_uuids = find_snippet_uuids_from_front_uuids(project_manager, &front_uuids)?;
+
+    let composite_snippet_definition = project_manager
+        .create_composite_snippet_definition(external_snippet_manager, &snippet_uuids)?;
+
+    // save to the snippet directory
+    let composite_snippet_directory = directory_manager
+        .get_composite_snippets_directory()?
+        .join(&name);
+
+    if composite_snippet_directory.exists() {
+        return Err(format!("A composite snippet named {} already exists", name));
+    }
+
+    write_composite_snippet_definition(
+        &composite_snippet_definition,
+        composite_snippet_directory.join(COMPOSITE_SNIPPET_FILE),
+    )?;
+
+    let (directory_uuid, package_path) = directory_manager.add_snippet_entry(
+        &COMPOSITE_SNIPPETS_CATEGORY.to_string(),
+        name.to_owned(),
+        composite_snippet_directory,
+        sequential_id_generator,
+    )?;
+
+    let external_snippet_uuid = external_snippet_manager
+        .create_snippet_from_composite_snippet_definition(
+            &composite_snippet_definition,
+            name,
+            package_path,
+            directory_uuid,
+            sequential_id_generator,
+        )?;
+
+    // can safely unwrap as we just created the external snippet above
+    let composite_external_snippet = external_snippet_manager
+        .find_external_snippet(external_snippet_uuid)
+        .unwrap();
+
+    let front_graph_changes = project_manager.collapse_snippets_into_composite(
+        external_snippet_manager,
+        sequential_id_generator,
+        &composite_snippet_definition,
+        composite_external_snippet,
+        &snippet_uuids,
+    )?;
+
+    // let the front end know to reload the snippet directory
+    match app_handle.emit_all("snippet_directory_changed", "".to_string()) {
+        Ok(()) => (),
+        Err(e) => {
+            println!("Could not emit snippet directory changed event: {}", e);
+        }
+    };
+
+    return Ok(front_graph_changes);
+}
+
+/// get the snippet uuids of the front snippet uuids
+fn find_snippet_uuids_from_front_uuids(
+    project_manager: &ProjectManager,
+    front_uuids: &Vec<Uuid>,
+) -> Result<Vec<Uuid>, String> {
+    let mut snippet_uuids = Vec::<Uuid>::with_capacity(front_uuids.len());
+
+    for front_uuid in front_uuids.iter() {
+        match project_manager
+            .visual_component_manager
+            .find_snippet_uuid(front_uuid)
+        {
+            Some(result) => snippet_uuids.push(result),
+            None => {
+                return Err(format!(
+                    "could not find snippet uuid from front snippet uuid {}",
+                    front_uuid
+                ));
+            }
+        };
+    }
+
+    return Ok(snippet_uuids);
+}
+
+/// undo the last graph edit of the window session
+/// returns the changes the front end needs to apply
+#[tauri::command]
+pub fn undo(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+) -> Result<FrontGraphChanges, &str> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found");
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
+    //borrow split
+    let sequential_id_generator = &mut state.sequential_id_generator;
+
+    return project_manager.history_manager.undo(
+        &mut project_manager.snippet_manager,
+        &mut project_manager.visual_component_manager,
+        sequential_id_generator,
+    );
+}
+
+/// redo the last undone graph edit of the window session
+/// returns the changes the front end needs to apply
+#[tauri::command]
+pub fn redo(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+) -> Result<FrontGraphChanges, &str> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found");
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
+    //borrow split
+    let sequential_id_generator = &mut state.sequential_id_generator;
+
+    return project_manager.history_manager.redo(
+        &mut project_manager.snippet_manager,
+        &mut project_manager.visual_component_manager,
+        sequential_id_generator,
+    );
+}
+
 /// validate a possible pipeline connection
 /// from_uuid and to_uuid order/direction not considered
 ///
@@ -514,6 +1023,7 @@ pub fn delete_pipeline(
 pub fn validate_pipeline_connection(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     from_front_uuid: Uuid,
     to_front_uuid: Uuid,
 ) -> Result<bool, &str> {
@@ -532,9 +1042,17 @@ pub fn validate_pipeline_connection(
         }
     };
 
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
 
     //get from and to component uuids from front uuids
     let from_uuid =
@@ -568,6 +1086,7 @@ pub fn validate_pipeline_connection(
 pub fn get_snippet_pipelines(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     snippet_front_uuid: Uuid,
 ) -> Result<Vec<Uuid>, &str> {
     // get the state
@@ -585,9 +1104,17 @@ pub fn get_snippet_pipelines(
         }
     };
 
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
 
     //get snippet uuid from front uuid
     let snippet_uuid = match visual_snippet_component_manager.find_snippet_uuid(&snippet_front_uuid)
@@ -646,6 +1173,7 @@ pub fn get_snippet_pipelines(
 pub fn check_pipeline_connector_capacity_full(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_pipeline_connector_uuid: Uuid,
 ) -> Result<bool, &str> {
     // get the state
@@ -663,10 +1191,18 @@ pub fn check_pipeline_connector_capacity_full(
         }
     };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
     //borrow check
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
 
     //get pipeline connector uuid from front pipeline connector uuid
     let pipeline_connector_uuid = match visual_snippet_component_manager
@@ -696,6 +1232,7 @@ pub struct FrontPipelineConnectorResult {
 pub fn get_pipeline_connector_uuids_from_pipeline(
     application_state: tauri::State<SharedApplicationState>,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
     front_pipeline_uuid: Uuid,
 ) -> Result<FrontPipelineConnectorResult, &str> {
     // get the state
@@ -713,10 +1250,18 @@ pub fn get_pipeline_connector_uuids_from_pipeline(
         }
     };
 
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found");
+            }
+        };
+
     //borrow split
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
 
     //get pipeline uuid from front uuid
     let pipeline_uuid =
@@ -784,31 +1329,26 @@ pub fn get_id(application_state: tauri::State<SharedApplicationState>) -> Uuid {
     return id;
 }
 
-/// spawn run snippets
+/// spawn run snippets, with the parameters referencing variables resolved with the variables of the profile
 #[tauri::command]
 pub fn spawn_run_snippets(
     application_state: tauri::State<SharedApplicationState>,
     app_handle: tauri::AppHandle,
     window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    profile_name: Option<String>,
 ) -> Result<u32, String> {
     // get the state
     let mut state_guard: MutexGuard<ApplicationState> = application_state.0.lock().unwrap();
     let state = state_guard.deref_mut();
 
-    // create log file and stream from window uuid
-    // that way the log instance is specific to the window uuid
-    let logging_instance = state
-        .logging_manager
-        .create_new_stream(app_handle, window_session_uuid)
-        .unwrap();
-    let stream_i = logging_instance.get_stream_i();
-
     // get shared reference to state
     // note this is a custom clone implementation utilizing on arc::clone
     //let application_state_ref : SharedApplicationState = SharedApplicationState(Arc::clone(&application_state.0));
 
     // lock the application state
 
+    let logging_manager = &mut state.logging_manager;
     let external_snippet_manager = &mut state.external_snippet_manager;
     let sequential_id_generator = &mut state.sequential_id_generator;
     let directory_manager = &mut state.directory_manager;
@@ -823,9 +1363,27 @@ pub fn spawn_run_snippets(
         }
     };
 
-    let snippet_manager = &mut window_session.project_manager.snippet_manager;
-    let visual_snippet_component_manager =
-        &mut window_session.project_manager.visual_component_manager;
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    // without a profile, no variables can be referenced
+    let variables = match &profile_name {
+        Some(profile_name) => project_manager
+            .variable_profiles
+            .get_variables(profile_name)?
+            .clone(),
+        None => BTreeMap::new(),
+    };
+
+    let project_directory = project_manager.get_project_directory();
+    let snippet_manager = &mut project_manager.snippet_manager;
+    let visual_snippet_component_manager = &mut project_manager.visual_component_manager;
 
     // create build initialized state
     let build_state = match InitializedPythonSnippetRunnerBuilder::build(
@@ -834,6 +1392,8 @@ pub fn spawn_run_snippets(
         directory_manager,
         visual_snippet_component_manager,
         sequential_id_generator,
+        &project_directory,
+        &variables,
     ) {
         Ok(some) => some,
         Err(e) => {
@@ -841,9 +1401,23 @@ pub fn spawn_run_snippets(
         }
     };
 
+    // create a log stream of its own for the run,
+    // so runs of other window sessions can log at the same time
+    let logging_instance = logging_manager.create_new_stream(
+        app_handle,
+        sequential_id_generator,
+        window_session_uuid,
+        project_manager.get_logs_directory(),
+    )?;
+    let stream_i = logging_instance.get_stream_i();
+    let runs_directory = project_manager.get_runs_directory();
+
+    // the run is cancelled when the window session is closed
+    let cancelled = window_session.add_active_run();
+
     // spawn process, passing ownership of shared application state
     tauri::async_runtime::spawn(async move {
-        spawn_run_snippets_event(build_state, logging_instance).await;
+        spawn_run_snippets_event(build_state, logging_instance, cancelled, runs_directory).await;
     });
 
     return Ok(stream_i);
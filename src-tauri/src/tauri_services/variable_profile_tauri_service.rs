@@ -0,0 +1,188 @@
+use crate::{
+    core_services::{project_service::ProjectManager, variable_profile_service::VariableProfiles},
+    state_management::{window_manager::WindowSession, SharedApplicationState},
+    utils::sequential_id_generator::Uuid,
+};
+use std::ops::DerefMut;
+
+/// get the variable profiles of the project, with their variables
+#[tauri::command]
+pub fn get_project_variable_profiles(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+) -> Result<VariableProfiles, String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &WindowSession = match state
+        .window_manager
+        .find_window_session(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &ProjectManager = match window_session.find_project_manager(project_uuid) {
+        Some(result) => result,
+        None => {
+            return Err("project could not be found".to_string());
+        }
+    };
+
+    return Ok(project_manager.variable_profiles.clone());
+}
+
+/// add an empty variable profile to the project
+#[tauri::command]
+pub fn add_project_variable_profile(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    profile_name: String,
+) -> Result<(), String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    return project_manager.variable_profiles.add_profile(profile_name);
+}
+
+/// remove a variable profile of the project, with its variables
+#[tauri::command]
+pub fn remove_project_variable_profile(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    profile_name: String,
+) -> Result<(), String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    return project_manager
+        .variable_profiles
+        .remove_profile(&profile_name);
+}
+
+/// set the value of a variable of a variable profile of the project, adding it if it does not exist
+#[tauri::command]
+pub fn set_project_variable(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    profile_name: String,
+    variable_name: String,
+    value: String,
+) -> Result<(), String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    return project_manager
+        .variable_profiles
+        .set_variable(&profile_name, variable_name, value);
+}
+
+/// remove a variable of a variable profile of the project
+#[tauri::command]
+pub fn remove_project_variable(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+    project_uuid: Uuid,
+    profile_name: String,
+    variable_name: String,
+) -> Result<(), String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    //find window session
+    let window_session: &mut WindowSession = match state
+        .window_manager
+        .find_window_session_mut(window_session_uuid)
+    {
+        Some(result) => result,
+        None => {
+            return Err("window session could not be found".to_string());
+        }
+    };
+
+    //find project
+    let project_manager: &mut ProjectManager =
+        match window_session.find_project_manager_mut(project_uuid) {
+            Some(result) => result,
+            None => {
+                return Err("project could not be found".to_string());
+            }
+        };
+
+    return project_manager
+        .variable_profiles
+        .remove_variable(&profile_name, &variable_name);
+}
@@ -3,7 +3,10 @@ use std::ops::DerefMut;
 
 ///the service for commands between tauri and the front end
 #[tauri::command]
-pub fn new_window_session(application_state: tauri::State<SharedApplicationState>) -> Uuid {
+pub fn new_window_session(
+    application_state: tauri::State<SharedApplicationState>,
+    window: tauri::Window,
+) -> Uuid {
     // get the state
     let state_guard = &mut application_state.0.lock().unwrap();
     let state = &mut state_guard.deref_mut();
@@ -13,7 +16,21 @@ pub fn new_window_session(application_state: tauri::State<SharedApplicationState
     let window_manager = &mut state.window_manager;
 
     // create new window session
-    let window_id = window_manager.new_window_session(sequential_id_generator);
+    let window_id =
+        window_manager.new_window_session(sequential_id_generator, window.label().to_string());
 
     return window_id;
 }
+
+/// close the window session, such as when the window is reloaded
+#[tauri::command]
+pub fn close_window_session(
+    application_state: tauri::State<SharedApplicationState>,
+    window_session_uuid: Uuid,
+) -> Result<(), String> {
+    // get the state
+    let state_guard = &mut application_state.0.lock().unwrap();
+    let state = &mut state_guard.deref_mut();
+
+    return state.close_window_session(window_session_uuid);
+}
@@ -4,6 +4,7 @@ import { invoke } from "@tauri-apps/api";
 export function generateSnippet(
   id,
   name,
+  tooltip,
   visualComponents,
   xPos,
   yPos,
@@ -51,6 +52,7 @@ export function generateSnippet(
           pipeline_connectors[i].id,
           visualComponents,
           pipeline_connectors[i].name,
+          pipeline_connectors[i].description,
           xPos,
           yPos + textHeight + 8,
           true,
@@ -63,6 +65,7 @@ export function generateSnippet(
           pipeline_connectors[i].id,
           visualComponents,
           pipeline_connectors[i].name,
+          pipeline_connectors[i].description,
           xPos,
           yPos + textHeight + 8,
           false,
@@ -174,6 +177,8 @@ export function generateSnippet(
   snippet_group.on("dragend", () => {
     dragEnd(id);
   });
+  setTooltip(titleBackgroundRect, tooltip);
+  setTooltip(titleText, tooltip);
   //singlePipeInsert.pipe.on('click', () => {});
 
   snippet_group.add(backgroundRect);
@@ -267,6 +272,7 @@ function createPipeInsert(
   id,
   visualComponents,
   name,
+  description,
   xPos,
   yPos,
   left = false,
@@ -376,6 +382,8 @@ function createPipeInsert(
   backgroundRect.on("click", () => {
     spawnPipeline(id, pipelineConnectorPositionOffset);
   });
+  setTooltip(backgroundRect, description);
+  setTooltip(titleText, description);
 
   //calculate dimensions
   let totalWidth = titleTextWidth + 4 + 8;
@@ -389,6 +397,44 @@ function createPipeInsert(
   };
 }
 
+// show the tooltip while hovering the node, nothing if it is empty
+function setTooltip(node, tooltip) {
+  if (!tooltip) {
+    return;
+  }
+
+  node.on("mouseenter", () => {
+    node.getStage().container().title = tooltip;
+  });
+  node.on("mouseleave", () => {
+    node.getStage().container().title = "";
+  });
+}
+
+// tooltip of a snippet from its description, version, author and tags
+export function getMetadataTooltip(metadata) {
+  if (metadata == null) {
+    return "";
+  }
+
+  let lines = [];
+
+  if (metadata.description != null) {
+    lines.push(metadata.description);
+  }
+  if (metadata.version != null) {
+    lines.push("version " + metadata.version);
+  }
+  if (metadata.author != null) {
+    lines.push("by " + metadata.author);
+  }
+  if (metadata.tags.length > 0) {
+    lines.push("tags: " + metadata.tags.join(", "));
+  }
+
+  return lines.join("\n");
+}
+
 export function getChild(node, id) {
   return node.getChildren(function (node) {
     return node.getId() === id;